@@ -34,6 +34,7 @@ $rt test-with-features wtx hashbrown
 $rt test-with-features wtx hmac
 $rt test-with-features wtx http-client-framework
 $rt test-with-features wtx http-server-framework
+$rt test-with-features wtx http1
 $rt test-with-features wtx http2
 $rt test-with-features wtx httparse
 $rt test-with-features wtx matchit
@@ -52,6 +53,7 @@ $rt test-with-features wtx serde_json
 $rt test-with-features wtx sha1
 $rt test-with-features wtx sha2
 $rt test-with-features wtx simdutf8
+$rt test-with-features wtx static-files
 $rt test-with-features wtx std
 $rt test-with-features wtx tokio
 $rt test-with-features wtx tokio-rustls
@@ -2,14 +2,14 @@
 //!
 //! This snippet requires ~25 dependencies and has an optimized binary size of ~700K.
 //!
-//! Currently, only HTTP/2 is supported.
+//! Plaintext connections don't negotiate protocols, as such, HTTP/1.1 is explicitly chosen.
 
 extern crate tokio;
 extern crate wtx;
 extern crate wtx_instances;
 
 use wtx::{
-  http::{client_framework::ClientFramework, Method, ReqResBuffer},
+  http::{client_framework::ClientFramework, Method, ReqResBuffer, Version},
   misc::{from_utf8_basic, Uri},
 };
 
@@ -17,7 +17,7 @@ use wtx::{
 async fn main() -> wtx::Result<()> {
   let uri = Uri::new("http://www.example.com");
   let buffer = ReqResBuffer::empty();
-  let client = ClientFramework::tokio(1).build();
+  let client = ClientFramework::tokio(1).version(Version::Http1_1).build();
   let res = client.send(Method::Get, buffer, &uri.to_ref()).await?;
   println!("{}", from_utf8_basic(&res.rrd.body)?);
   Ok(())
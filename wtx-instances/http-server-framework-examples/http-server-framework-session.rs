@@ -16,7 +16,8 @@
 //! CREATE TABLE "session" (
 //!   id BYTEA NOT NULL PRIMARY KEY,
 //!   user_id INT NOT NULL,
-//!   expires_at TIMESTAMPTZ NOT NULL
+//!   expires_at TIMESTAMPTZ NOT NULL,
+//!   csrf BYTEA NOT NULL
 //! );
 //! ALTER TABLE "session" ADD CONSTRAINT session__user__fk FOREIGN KEY (user_id) REFERENCES "user" (id);
 //! ```
@@ -52,11 +53,11 @@ async fn main() -> wtx::Result<()> {
       eprintln!("{err}");
     }
   });
-  let rng_clone = rng.clone();
   ServerFrameworkBuilder::new(router)
     .with_conn_aux(move || ConnAux {
       pool: pool.clone(),
-      rng: rng_clone.clone(),
+      // Each connection must have its own CSPRNG, otherwise CSRF tokens would repeat.
+      rng: ChaCha20Rng::from_entropy(),
       session_manager: sm.clone(),
       session_state: None,
     })
@@ -1,10 +1,13 @@
 //! HTTP/2 server that uses optioned parameters.
+//!
+//! Pressing Ctrl-C gives ongoing streams 10 seconds to finish before the server stops.
 
 extern crate tokio;
 extern crate tokio_rustls;
 extern crate wtx;
 extern crate wtx_instances;
 
+use core::time::Duration;
 use tokio::{io::WriteHalf, net::TcpStream};
 use tokio_rustls::server::TlsStream;
 use wtx::{
@@ -43,6 +46,12 @@ async fn main() -> wtx::Result<()> {
         },
       ))
     },
+    (
+      async {
+        let _rslt = tokio::signal::ctrl_c().await;
+      },
+      Duration::from_secs(10),
+    ),
     || Ok((Vector::new(), ReqResBuffer::empty())),
     (
       || {
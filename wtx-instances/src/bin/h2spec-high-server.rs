@@ -2,6 +2,7 @@
 
 #![expect(clippy::print_stderr, reason = "internal")]
 
+use core::time::Duration;
 use tokio::net::tcp::OwnedWriteHalf;
 use wtx::{
   http::{
@@ -21,6 +22,7 @@ async fn main() -> wtx::Result<()> {
     |error| eprintln!("{error}"),
     manual,
     |_, _, _, _| Ok(((), OperationMode::Auto)),
+    (core::future::pending(), Duration::ZERO),
     || Ok(((), ReqResBuffer::empty())),
     (|| Ok(()), |_| {}, |_, stream| async move { Ok(stream.into_split()) }),
   )
@@ -8,14 +8,17 @@ use crate::{
     pkg::{Package, PkgsAux},
     Api,
   },
-  http::{client_framework::ClientFramework, Header, KnownHeaderName, ReqResBuffer},
-  http2::{Http2, Http2Buffer, Http2Data},
-  misc::{Lock, RefCounter, StreamWriter},
+  http::{
+    client_framework::{ClientFramework, ClientFrameworkConn},
+    Header, KnownHeaderName, ReqResBuffer,
+  },
+  http2::{Http2Buffer, Http2Data},
+  misc::{Lock, RefCounter, StreamReader, StreamWriter},
   pool::{ResourceManager, SimplePoolResource},
 };
 use core::{mem, ops::Range};
 
-impl<DRSR, HD, RL, RM, SW> RecievingTransport<DRSR> for ClientFramework<RL, RM>
+impl<DRSR, HD, RL, RM, SR, SW> RecievingTransport<DRSR> for ClientFramework<RL, RM>
 where
   HD: RefCounter + 'static,
   HD::Item: Lock<Resource = Http2Data<Http2Buffer, SW, true>>,
@@ -24,8 +27,9 @@ where
     CreateAux = str,
     Error = crate::Error,
     RecycleAux = str,
-    Resource = Http2<HD, true>,
+    Resource = ClientFrameworkConn<HD, SR, SW>,
   >,
+  SR: StreamReader,
   SW: StreamWriter,
   for<'any> RL: 'any,
   for<'any> RM: 'any,
@@ -42,7 +46,7 @@ where
   }
 }
 
-impl<DRSR, HD, RL, RM, SW> SendingTransport<DRSR> for ClientFramework<RL, RM>
+impl<DRSR, HD, RL, RM, SR, SW> SendingTransport<DRSR> for ClientFramework<RL, RM>
 where
   HD: RefCounter + 'static,
   HD::Item: Lock<Resource = Http2Data<Http2Buffer, SW, true>>,
@@ -51,8 +55,9 @@ where
     CreateAux = str,
     Error = crate::Error,
     RecycleAux = str,
-    Resource = Http2<HD, true>,
+    Resource = ClientFrameworkConn<HD, SR, SW>,
   >,
+  SR: StreamReader,
   SW: StreamWriter,
   for<'any> RL: 'any,
   for<'any> RM: 'any,
@@ -77,7 +82,7 @@ impl<DRSR, RL, RM> Transport<DRSR> for ClientFramework<RL, RM> {
   type Params = HttpParams;
 }
 
-impl<DRSR, HD, RL, RM, SW> RecievingTransport<DRSR> for &ClientFramework<RL, RM>
+impl<DRSR, HD, RL, RM, SR, SW> RecievingTransport<DRSR> for &ClientFramework<RL, RM>
 where
   HD: RefCounter + 'static,
   HD::Item: Lock<Resource = Http2Data<Http2Buffer, SW, true>>,
@@ -86,8 +91,9 @@ where
     CreateAux = str,
     Error = crate::Error,
     RecycleAux = str,
-    Resource = Http2<HD, true>,
+    Resource = ClientFrameworkConn<HD, SR, SW>,
   >,
+  SR: StreamReader,
   SW: StreamWriter,
   for<'any> RL: 'any,
   for<'any> RM: 'any,
@@ -104,7 +110,7 @@ where
   }
 }
 
-impl<DRSR, HD, RL, RM, SW> SendingTransport<DRSR> for &ClientFramework<RL, RM>
+impl<DRSR, HD, RL, RM, SR, SW> SendingTransport<DRSR> for &ClientFramework<RL, RM>
 where
   HD: RefCounter + 'static,
   HD::Item: Lock<Resource = Http2Data<Http2Buffer, SW, true>>,
@@ -113,8 +119,9 @@ where
     CreateAux = str,
     Error = crate::Error,
     RecycleAux = str,
-    Resource = Http2<HD, true>,
+    Resource = ClientFrameworkConn<HD, SR, SW>,
   >,
+  SR: StreamReader,
   SW: StreamWriter,
   for<'any> RL: 'any,
   for<'any> RM: 'any,
@@ -134,7 +141,7 @@ where
   }
 }
 
-impl<DRSR, HD, RL, RM, SW> Transport<DRSR> for &ClientFramework<RL, RM>
+impl<DRSR, HD, RL, RM, SR, SW> Transport<DRSR> for &ClientFramework<RL, RM>
 where
   HD: RefCounter + 'static,
   HD::Item: Lock<Resource = Http2Data<Http2Buffer, SW, true>>,
@@ -143,8 +150,9 @@ where
     CreateAux = str,
     Error = crate::Error,
     RecycleAux = str,
-    Resource = Http2<HD, true>,
+    Resource = ClientFrameworkConn<HD, SR, SW>,
   >,
+  SR: StreamReader,
   SW: StreamWriter,
   for<'any> RL: 'any,
   for<'any> RM: 'any,
@@ -153,7 +161,7 @@ where
   type Params = HttpParams;
 }
 
-async fn response<A, DRSR, HD, P, RL, RM, SW>(
+async fn response<A, DRSR, HD, P, RL, RM, SR, SW>(
   client: &ClientFramework<RL, RM>,
   pkg: &mut P,
   pkgs_aux: &mut PkgsAux<A, DRSR, HttpParams>,
@@ -168,8 +176,9 @@ where
     CreateAux = str,
     Error = crate::Error,
     RecycleAux = str,
-    Resource = Http2<HD, true>,
+    Resource = ClientFrameworkConn<HD, SR, SW>,
   >,
+  SR: StreamReader,
   SW: StreamWriter,
   for<'any> RL: 'any,
   for<'any> RM: 'any,
@@ -409,6 +409,40 @@ async fn reuses_cached_statement() {
   let _record = exec.fetch_with_stmt("SELECT 1 WHERE 0=$1", (0,)).await.unwrap();
 }
 
+#[cfg(feature = "http-session")]
+#[tokio::test]
+async fn session_store() {
+  use crate::http::{SessionState, SessionStore};
+  use chrono::{DateTime, Utc};
+
+  let mut exec = executor::<crate::Error>().await;
+  exec
+    .execute(
+      "
+        DROP TABLE IF EXISTS session;
+        CREATE TABLE session (
+          id BYTEA NOT NULL PRIMARY KEY,
+          expires_at TIMESTAMPTZ NOT NULL,
+          user_state INT NOT NULL,
+          csrf BYTEA NOT NULL
+        );
+      ",
+      |_| {},
+    )
+    .await
+    .unwrap();
+  let expire = DateTime::<Utc>::from_timestamp(4_000_000_000, 0);
+  let state = SessionState { csrf: [2; 16], custom_state: 3i32, expire, id: [1; 16] };
+  exec.create(&state).await.unwrap();
+  assert_eq!(SessionStore::<i32, _>::read(&mut exec, &state.id).await.unwrap(), Some(state));
+  let new_state = SessionState { csrf: [4; 16], custom_state: 5i32, expire, id: [6; 16] };
+  exec.update(&state.id, &new_state).await.unwrap();
+  assert_eq!(
+    SessionStore::<i32, _>::read(&mut exec, &new_state.id).await.unwrap(),
+    Some(new_state)
+  );
+}
+
 #[cfg(feature = "serde_json")]
 #[tokio::test]
 async fn serde_json() {
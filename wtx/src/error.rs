@@ -54,6 +54,8 @@ pub enum Error {
   MatchitInsertError(Box<matchit::InsertError>),
   #[cfg(feature = "digest")]
   MacError(digest::MacError),
+  #[cfg(feature = "argon2")]
+  PasswordHash(argon2::password_hash::Error),
   #[cfg(feature = "postgres")]
   PostgresDbError(Box<crate::database::client::postgres::DbError>),
   #[cfg(feature = "quick-protobuf")]
@@ -147,6 +149,8 @@ pub enum Error {
   FromRadix10Error(FromRadix10Error),
   #[cfg(feature = "http")]
   HttpError(crate::http::HttpError),
+  #[cfg(feature = "http1")]
+  Http1Error(crate::http1::Http1Error),
   #[cfg(feature = "http2")]
   Http2ErrorGoAway(crate::http2::Http2ErrorCode, Option<crate::http2::Http2Error>),
   #[cfg(feature = "http2")]
@@ -353,6 +357,14 @@ impl From<digest::MacError> for Error {
   }
 }
 
+#[cfg(feature = "argon2")]
+impl From<argon2::password_hash::Error> for Error {
+  #[inline]
+  fn from(from: argon2::password_hash::Error) -> Self {
+    Self::PasswordHash(from)
+  }
+}
+
 impl From<core::num::ParseIntError> for Error {
   #[inline]
   fn from(from: core::num::ParseIntError) -> Self {
@@ -490,6 +502,14 @@ impl From<crate::http::HttpError> for Error {
   }
 }
 
+#[cfg(feature = "http1")]
+impl From<crate::http1::Http1Error> for Error {
+  #[inline]
+  fn from(from: crate::http1::Http1Error) -> Self {
+    Self::Http1Error(from)
+  }
+}
+
 #[cfg(feature = "client-api-framework")]
 impl From<crate::client_api_framework::ClientApiFrameworkError> for Error {
   #[inline]
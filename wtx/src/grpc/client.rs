@@ -5,11 +5,11 @@ use crate::{
   },
   grpc::serialize,
   http::{
-    client_framework::ClientFramework, Header, Headers, KnownHeaderName, Method, ReqResBuffer,
-    ReqUri, Response,
+    client_framework::{ClientFramework, ClientFrameworkConn},
+    Header, Headers, KnownHeaderName, Method, ReqResBuffer, ReqUri, Response,
   },
-  http2::{Http2, Http2Buffer, Http2Data},
-  misc::{Lock, RefCounter, StreamWriter},
+  http2::{Http2Buffer, Http2Data},
+  misc::{Lock, RefCounter, StreamReader, StreamWriter},
   pool::{ResourceManager, SimplePoolResource},
 };
 
@@ -22,7 +22,7 @@ pub struct Client<DRSR, RL, RM> {
   drsr: DRSR,
 }
 
-impl<DRSR, HD, RL, RM, SW> Client<DRSR, RL, RM>
+impl<DRSR, HD, RL, RM, SR, SW> Client<DRSR, RL, RM>
 where
   HD: RefCounter + 'static,
   HD::Item: Lock<Resource = Http2Data<Http2Buffer, SW, true>>,
@@ -31,8 +31,9 @@ where
     CreateAux = str,
     Error = crate::Error,
     RecycleAux = str,
-    Resource = Http2<HD, true>,
+    Resource = ClientFrameworkConn<HD, SR, SW>,
   >,
+  SR: StreamReader,
   SW: StreamWriter,
   for<'any> RL: 'any,
   for<'any> RM: 'any,
@@ -1,5 +1,6 @@
 //! Generic HTTP elements
 
+mod cache_control;
 #[cfg(feature = "http-client-framework")]
 pub mod client_framework;
 #[cfg(any(feature = "http-client-framework", feature = "http-server-framework"))]
@@ -32,7 +33,8 @@ mod session;
 mod status_code;
 mod version;
 
-#[cfg(feature = "http-session")]
+pub use cache_control::CacheControl;
+#[cfg(feature = "http-cookie")]
 pub use cookie::*;
 pub use generic_header::GenericHeader;
 pub use generic_request::GenericRequest;
@@ -0,0 +1,193 @@
+use crate::http::{Header, Headers, KnownHeaderName};
+use core::fmt::{Display, Formatter};
+
+/// Directives of the `Cache-Control` response header.
+///
+/// ```rust
+/// let cc = wtx::http::CacheControl::new().max_age(60).private(true);
+/// assert_eq!(cc.to_string(), "private, max-age=60");
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct CacheControl {
+  immutable: bool,
+  max_age: Option<u32>,
+  must_revalidate: bool,
+  no_cache: bool,
+  no_store: bool,
+  private: bool,
+  public: bool,
+  s_maxage: Option<u32>,
+  stale_while_revalidate: Option<u32>,
+}
+
+impl CacheControl {
+  /// Instance without directives.
+  #[inline]
+  pub const fn new() -> Self {
+    Self {
+      immutable: false,
+      max_age: None,
+      must_revalidate: false,
+      no_cache: false,
+      no_store: false,
+      private: false,
+      public: false,
+      s_maxage: None,
+      stale_while_revalidate: None,
+    }
+  }
+
+  /// The response will not be updated while it is fresh.
+  #[inline]
+  #[must_use]
+  pub const fn immutable(mut self, elem: bool) -> Self {
+    self.immutable = elem;
+    self
+  }
+
+  /// Number of seconds during which the response remains fresh.
+  #[inline]
+  #[must_use]
+  pub const fn max_age(mut self, secs: u32) -> Self {
+    self.max_age = Some(secs);
+    self
+  }
+
+  /// Stale responses must be validated before being reused.
+  #[inline]
+  #[must_use]
+  pub const fn must_revalidate(mut self, elem: bool) -> Self {
+    self.must_revalidate = elem;
+    self
+  }
+
+  /// Stored responses must always be validated before being reused.
+  #[inline]
+  #[must_use]
+  pub const fn no_cache(mut self, elem: bool) -> Self {
+    self.no_cache = elem;
+    self
+  }
+
+  /// The response must not be stored by any cache.
+  #[inline]
+  #[must_use]
+  pub const fn no_store(mut self, elem: bool) -> Self {
+    self.no_store = elem;
+    self
+  }
+
+  /// The response can only be stored by private caches, such as the ones of browsers.
+  #[inline]
+  #[must_use]
+  pub const fn private(mut self, elem: bool) -> Self {
+    self.private = elem;
+    self
+  }
+
+  /// The response can be stored by shared caches even when it would normally be forbidden.
+  #[inline]
+  #[must_use]
+  pub const fn public(mut self, elem: bool) -> Self {
+    self.public = elem;
+    self
+  }
+
+  /// Version of [`Self::max_age`] that only affects shared caches.
+  #[inline]
+  #[must_use]
+  pub const fn s_maxage(mut self, secs: u32) -> Self {
+    self.s_maxage = Some(secs);
+    self
+  }
+
+  /// Number of seconds during which a stale response can be reused while it is revalidated in
+  /// the background.
+  #[inline]
+  #[must_use]
+  pub const fn stale_while_revalidate(mut self, secs: u32) -> Self {
+    self.stale_while_revalidate = Some(secs);
+    self
+  }
+
+  /// If the `no-store` directive is present.
+  #[inline]
+  pub const fn is_no_store(&self) -> bool {
+    self.no_store
+  }
+
+  /// Pushes a `Cache-Control` header into `headers`. Nothing is pushed if there are no
+  /// directives.
+  #[inline]
+  pub fn push_header(&self, headers: &mut Headers) -> crate::Result<()> {
+    if *self == Self::new() {
+      return Ok(());
+    }
+    headers.push_from_fmt(Header::from_name_and_value(
+      KnownHeaderName::CacheControl.into(),
+      format_args!("{self}"),
+    ))
+  }
+}
+
+impl Display for CacheControl {
+  #[inline]
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    let mut sep = "";
+    let mut flag = |f: &mut Formatter<'_>, is_present: bool, name: &str| {
+      if is_present {
+        f.write_fmt(format_args!("{sep}{name}"))?;
+        sep = ", ";
+      }
+      Ok::<_, core::fmt::Error>(())
+    };
+    flag(f, self.public, "public")?;
+    flag(f, self.private, "private")?;
+    flag(f, self.no_cache, "no-cache")?;
+    flag(f, self.no_store, "no-store")?;
+    flag(f, self.must_revalidate, "must-revalidate")?;
+    flag(f, self.immutable, "immutable")?;
+    for (name, value) in [
+      ("max-age", self.max_age),
+      ("s-maxage", self.s_maxage),
+      ("stale-while-revalidate", self.stale_while_revalidate),
+    ] {
+      if let Some(elem) = value {
+        f.write_fmt(format_args!("{sep}{name}={elem}"))?;
+        sep = ", ";
+      }
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::http::{CacheControl, Headers, KnownHeaderName};
+  use alloc::string::ToString;
+
+  #[test]
+  fn directives() {
+    assert_eq!(CacheControl::new().to_string(), "");
+    assert_eq!(CacheControl::new().no_store(true).to_string(), "no-store");
+    assert_eq!(
+      CacheControl::new()
+        .public(true)
+        .immutable(true)
+        .max_age(31_536_000)
+        .stale_while_revalidate(60)
+        .to_string(),
+      "public, immutable, max-age=31536000, stale-while-revalidate=60"
+    );
+  }
+
+  #[test]
+  fn push_header() {
+    let mut headers = Headers::new();
+    CacheControl::new().push_header(&mut headers).unwrap();
+    assert_eq!(headers.headers_len(), 0);
+    CacheControl::new().max_age(60).push_header(&mut headers).unwrap();
+    let header = headers.get_by_name(KnownHeaderName::CacheControl.into()).unwrap();
+    assert_eq!(header.value, b"max-age=60");
+  }
+}
@@ -1,6 +1,8 @@
 //! Client framework
 
 mod client_framework_builder;
+#[cfg(feature = "http-cookie")]
+mod cookie_jar;
 #[cfg(all(
   feature = "_async-tests",
   feature = "_integration-tests",
@@ -9,17 +11,31 @@ mod client_framework_builder;
   test
 ))]
 mod integration_tests;
+mod origin_pools;
+mod redirect_policy;
 mod req_builder;
+#[cfg(all(feature = "_async-tests", feature = "tokio", test))]
+mod tests;
 
 use crate::{
-  http::{conn_params::ConnParams, Method, ReqResBuffer, ReqUri, Request, Response},
+  http::{
+    conn_params::ConnParams, Header, Headers, HttpError, KnownHeaderName, Method, ReqResBuffer,
+    ReqUri, Request, Response, Version,
+  },
+  http1::{Http1, Http1Buffer},
   http2::{Http2, Http2Buffer, Http2Data, Http2ErrorCode, Http2RecvStatus},
-  misc::{Lock, RefCounter, StreamWriter},
+  misc::{from_utf8_basic, Arc, Lock, RefCounter, StreamReader, StreamWriter, UriString, Vector},
   pool::{Pool, ResourceManager, SimplePool, SimplePoolResource},
 };
-use core::marker::PhantomData;
+use alloc::string::String;
+use core::{marker::PhantomData, mem};
+use origin_pools::{origin, OriginPools};
+use redirect_policy::_resolve_location;
 
 pub use client_framework_builder::ClientFrameworkBuilder;
+#[cfg(feature = "http-cookie")]
+pub use cookie_jar::CookieJar;
+pub use redirect_policy::{RedirectPolicy, RedirectedResponse};
 pub use req_builder::ReqBuilder;
 #[cfg(feature = "tokio")]
 pub use tokio::ClientFrameworkTokio;
@@ -28,10 +44,60 @@ pub use tokio_rustls::ClientFrameworkTokioRustls;
 
 /// An optioned pool of different HTTP connections lazily constructed from different URIs.
 ///
-/// Currently supports only one domain with multiple connections.
-#[derive(Clone, Debug)]
+/// Connections are grouped by origin, i.e., by the scheme, the host and the port of URIs. Each
+/// origin has its own set of connections and origins that aren't used during
+/// [`ClientFrameworkBuilder::idle_timeout`] have their connections closed.
+#[derive(Debug)]
 pub struct ClientFramework<RL, RM> {
-  pool: SimplePool<RL, RM>,
+  #[cfg(feature = "http-cookie")]
+  cookie_jar: Option<Arc<std::sync::Mutex<CookieJar>>>,
+  pools: Arc<OriginPools<RL, RM>>,
+  redirect_policy: RedirectPolicy,
+}
+
+impl<RL, RM> Clone for ClientFramework<RL, RM> {
+  #[inline]
+  fn clone(&self) -> Self {
+    Self {
+      #[cfg(feature = "http-cookie")]
+      cookie_jar: self.cookie_jar.clone(),
+      pools: Arc::clone(&self.pools),
+      redirect_policy: self.redirect_policy,
+    }
+  }
+}
+
+/// A pooled connection of [`ClientFramework`], whose protocol is decided when the connection is
+/// established.
+#[derive(Debug)]
+pub enum ClientFrameworkConn<HD, SR, SW> {
+  /// Sequential HTTP/1.1 exchanges over a keep-alive connection.
+  Http1(Http1<SR, SW, true>),
+  /// Multiplexed HTTP/2 streams.
+  Http2(Http2<HD, true>),
+}
+
+impl<HD, SR, SW> ClientFrameworkConn<HD, SR, SW>
+where
+  HD: RefCounter,
+  HD::Item: Lock<Resource = Http2Data<Http2Buffer, SW, true>>,
+  SW: StreamWriter,
+{
+  #[inline]
+  pub(crate) fn _is_closed(&self) -> bool {
+    match self {
+      Self::Http1(elem) => elem.is_closing(),
+      Self::Http2(elem) => elem.connection_state().is_closed(),
+    }
+  }
+
+  #[inline]
+  pub(crate) async fn _swap_buffers(&mut self, h1b: &mut Http1Buffer, h2b: &mut Http2Buffer) {
+    match self {
+      Self::Http1(elem) => elem._swap_buffer(h1b),
+      Self::Http2(elem) => elem._swap_buffers(h2b).await,
+    }
+  }
 }
 
 /// Resource manager for [`ClientFramework`].
@@ -39,9 +105,10 @@ pub struct ClientFramework<RL, RM> {
 pub struct ClientFrameworkRM<S> {
   _cp: ConnParams,
   _phantom: PhantomData<S>,
+  _version: Version,
 }
 
-impl<HD, RL, RM, SW> ClientFramework<RL, RM>
+impl<HD, RL, RM, SR, SW> ClientFramework<RL, RM>
 where
   HD: RefCounter + 'static,
   HD::Item: Lock<Resource = Http2Data<Http2Buffer, SW, true>>,
@@ -50,26 +117,37 @@ where
     CreateAux = str,
     Error = crate::Error,
     RecycleAux = str,
-    Resource = Http2<HD, true>,
+    Resource = ClientFrameworkConn<HD, SR, SW>,
   >,
+  SR: StreamReader,
   SW: StreamWriter,
   for<'any> RL: 'any,
   for<'any> RM: 'any,
 {
-  /// Closes all active connections
+  /// Closes all active connections of all origins.
   #[inline]
   pub async fn close_all(&self) {
-    self
-      .pool
-      ._into_for_each(|elem| async move {
-        elem.send_go_away(Http2ErrorCode::NoError).await;
-      })
-      .await;
+    for pool in self.pools.drain() {
+      Self::close_pool(&pool).await;
+    }
   }
 
-  /// Sends an arbitrary request.
+  /// Cookies stored from previous responses, if [`ClientFrameworkBuilder::cookie_jar`] was
+  /// called.
+  #[cfg(feature = "http-cookie")]
+  #[inline]
+  pub fn cookie_jar(&self) -> Option<&std::sync::Mutex<CookieJar>> {
+    self.cookie_jar.as_deref()
+  }
+
+  /// Sends an arbitrary request, following redirections according to
+  /// [`ClientFrameworkBuilder::redirect_policy`].
+  ///
+  /// If the pool of the origin of the URI is full, then this method will block until a
+  /// connection is available.
   ///
-  /// If the pool is full, then this method will block until a connection is available.
+  /// If `req_uri` is [`ReqUri::Param`], then its contents are written into the URI of `rrb`
+  /// because the returned buffer holds the effective URI of the request.
   #[inline]
   pub async fn send(
     &self,
@@ -77,48 +155,245 @@ where
     rrb: ReqResBuffer,
     req_uri: impl Into<ReqUri<'_>>,
   ) -> crate::Result<Response<ReqResBuffer>> {
-    let actual_req_uri = req_uri.into();
-    let uri = match actual_req_uri {
-      ReqUri::Data => &rrb.uri.to_ref(),
-      ReqUri::Param(elem) => elem,
-    };
-    let mut guard = self.pool.get(uri.as_str(), uri.as_str()).await?;
-    let mut stream = guard.stream().await?;
-    if stream.send_req(Request::http2(method, &rrb), actual_req_uri).await?.is_closed() {
-      return Err(crate::Error::ClosedConnection);
+    Ok(self.send_redirected(method, rrb, req_uri).await?.res)
+  }
+
+  /// Similar to [`Self::send`] but also returns all followed redirections.
+  ///
+  /// The URI of the final response is the effective URI of the request. Like [`Self::send`], the
+  /// URI of `rrb` is overwritten when `req_uri` is [`ReqUri::Param`].
+  #[inline]
+  pub async fn send_redirected(
+    &self,
+    mut method: Method,
+    mut rrb: ReqResBuffer,
+    req_uri: impl Into<ReqUri<'_>>,
+  ) -> crate::Result<RedirectedResponse> {
+    if let ReqUri::Param(elem) = req_uri.into() {
+      rrb.uri.reset(format_args!("{}", elem.as_str()))?;
     }
-    let (hrs, res_rrb) = stream.recv_res(rrb).await?;
-    let status_code = match hrs {
-      Http2RecvStatus::Eos(elem) => elem,
-      _ => return Err(crate::Error::ClosedConnection),
+    let max_hops = self.redirect_policy._max_hops();
+    let mut redirects = Vector::new();
+    if max_hops == 0 {
+      let res = self.exchange(method, &mut rrb, None).await?;
+      return Ok(RedirectedResponse { redirects, res });
+    }
+    // Responses are received into another buffer to keep the request for eventual redirections.
+    let mut res_rrb = ReqResBuffer::empty();
+    loop {
+      let res = self.exchange(method, &mut rrb, Some(res_rrb)).await?;
+      let Some((next_method, has_body)) =
+        self.redirect_policy._next_method(method, res.status_code)
+      else {
+        return Ok(RedirectedResponse { redirects, res });
+      };
+      let location = res.rrd.headers.get_by_name(KnownHeaderName::Location.into());
+      let Some(Ok(location_str)) = location.map(|el| from_utf8_basic(el.value)) else {
+        return Ok(RedirectedResponse { redirects, res });
+      };
+      let mut next_uri = UriString::new(String::new());
+      _resolve_location(&rrb.uri.to_ref(), location_str, &mut next_uri)?;
+      let (prev_uri, next_uri_ref) = (rrb.uri.to_ref(), next_uri.to_ref());
+      let is_cross_origin = origin(&prev_uri)? != origin(&next_uri_ref)?;
+      if !self.redirect_policy._is_followed(&prev_uri, &next_uri_ref, is_cross_origin) {
+        return Ok(RedirectedResponse { redirects, res });
+      }
+      if redirects.len() >= usize::from(max_hops) {
+        return Err(HttpError::TooManyRedirects.into());
+      }
+      redirects.push((res.status_code, mem::replace(&mut rrb.uri, next_uri)))?;
+      if is_cross_origin || !has_body {
+        let mut headers = Headers::new();
+        for header in rrb.headers.iter() {
+          let is_removed = match KnownHeaderName::try_from(header.name.as_bytes()) {
+            Ok(
+              KnownHeaderName::Authorization
+              | KnownHeaderName::Cookie
+              | KnownHeaderName::ProxyAuthorization,
+            ) => is_cross_origin,
+            Ok(KnownHeaderName::ContentLength | KnownHeaderName::ContentType) => !has_body,
+            _ => false,
+          };
+          if !is_removed {
+            copy_header(header, &mut headers)?;
+          }
+        }
+        rrb.headers = headers;
+      }
+      if !has_body {
+        rrb.body.clear();
+      }
+      method = next_method;
+      res_rrb = res.rrd;
+    }
+  }
+
+  /// Sends `req` and receives the response into `res_rrb` or, if `None`, into `req`.
+  #[inline]
+  async fn exchange(
+    &self,
+    method: Method,
+    req: &mut ReqResBuffer,
+    res_rrb: Option<ReqResBuffer>,
+  ) -> crate::Result<Response<ReqResBuffer>> {
+    let is_kept = res_rrb.is_some();
+    // Received responses clear the buffer, which also includes the URI.
+    let uri_string = mem::replace(&mut req.uri, UriString::_empty(String::new()));
+    let uri = &uri_string.to_ref();
+    #[cfg(feature = "http-cookie")]
+    let mut jar_cookies = None;
+    #[cfg(feature = "http-cookie")]
+    if let Some(elem) = &self.cookie_jar {
+      let cookie_name = <&str>::from(KnownHeaderName::Cookie);
+      let caller_cookies_len =
+        req.headers.iter().filter(|el| el.name == cookie_name).fold(None, |acc, el| {
+          Some(
+            acc.map_or(el.value.len(), |len: usize| {
+              len.wrapping_add(2).wrapping_add(el.value.len())
+            }),
+          )
+        });
+      let is_pushed = crate::misc::SyncLock::lock(&**elem).push_request_cookies(
+        &mut req.headers,
+        crate::misc::GenericTime::timestamp()?,
+        uri,
+      )?;
+      if is_pushed {
+        jar_cookies = Some(caller_cookies_len);
+      }
+    }
+    let (pool, idle_pools) = self.pools.pool(uri)?;
+    for idle_pool in idle_pools {
+      Self::close_pool(&idle_pool).await;
+    }
+    let mut guard = pool.get(uri.as_str(), uri.as_str()).await?;
+    let mut res = match &mut ***guard {
+      ClientFrameworkConn::Http1(http1) => {
+        let version = Version::Http1_1;
+        http1.send_req(Request { method, rrd: &*req, version }, uri).await?;
+        let mut rrd = res_rrb.unwrap_or_else(|| mem::replace(req, ReqResBuffer::empty()));
+        let status_code = http1.recv_res(&mut rrd).await?;
+        Response { rrd, status_code, version }
+      }
+      ClientFrameworkConn::Http2(http2) => {
+        let mut stream = http2.stream().await?;
+        if stream.send_req(Request::http2(method, &*req), uri).await?.is_closed() {
+          return Err(crate::Error::ClosedConnection);
+        }
+        let rrd = res_rrb.unwrap_or_else(|| mem::replace(req, ReqResBuffer::empty()));
+        let (hrs, res_rrb) = stream.recv_res(rrd).await?;
+        let status_code = match hrs {
+          Http2RecvStatus::Eos(elem) => elem,
+          _ => return Err(crate::Error::ClosedConnection),
+        };
+        stream.common().clear(false).await?;
+        Response::http2(res_rrb, status_code)
+      }
     };
-    stream.common().clear(false).await?;
-    Ok(Response::http2(res_rrb, status_code))
+    #[cfg(feature = "http-cookie")]
+    if let Some(elem) = &self.cookie_jar {
+      crate::misc::SyncLock::lock(&**elem).store_response_cookies(
+        &res.rrd.headers,
+        crate::misc::GenericTime::timestamp()?,
+        uri,
+      )?;
+    }
+    if is_kept {
+      #[cfg(feature = "http-cookie")]
+      if let Some(elem) = jar_cookies {
+        remove_jar_cookies(&mut req.headers, elem)?;
+      }
+      res.rrd.uri.reset(format_args!("{}", uri_string.as_str()))?;
+      req.uri = uri_string;
+    } else {
+      res.rrd.uri = uri_string;
+    }
+    Ok(res)
   }
+
+  #[inline]
+  async fn close_pool(pool: &SimplePool<RL, RM>) {
+    pool
+      ._into_for_each(|elem| async move {
+        match elem {
+          ClientFrameworkConn::Http1(_) => {}
+          ClientFrameworkConn::Http2(http2) => http2.send_go_away(Http2ErrorCode::NoError).await,
+        }
+      })
+      .await;
+  }
+}
+
+/// Removes the `Cookie` header pushed by the jar, whose first `caller_cookies_len` bytes are the
+/// cookies that were already present in the request.
+#[cfg(feature = "http-cookie")]
+#[inline]
+fn remove_jar_cookies(
+  headers: &mut Headers,
+  caller_cookies_len: Option<usize>,
+) -> crate::Result<()> {
+  let caller_cookies = match (caller_cookies_len, headers.iter().last()) {
+    (Some(len), Some(header)) => Vector::from_slice(header.value.get(..len).unwrap_or_default())?,
+    _ => Vector::new(),
+  };
+  let _ = headers.pop();
+  if !caller_cookies.is_empty() {
+    headers.push_from_iter(Header::from_name_and_value(
+      KnownHeaderName::Cookie.into(),
+      [caller_cookies.as_slice()],
+    ))?;
+  }
+  Ok(())
+}
+
+#[inline]
+fn copy_header(header: Header<'_, &[u8]>, headers: &mut Headers) -> crate::Result<()> {
+  headers.push_from_iter(Header {
+    is_sensitive: header.is_sensitive,
+    is_trailer: header.is_trailer,
+    name: header.name,
+    value: [header.value],
+  })
 }
 
 #[cfg(feature = "tokio")]
 mod tokio {
   use crate::{
-    http::client_framework::{ClientFramework, ClientFrameworkBuilder, ClientFrameworkRM},
-    http2::{Http2Buffer, Http2Tokio},
+    http::{
+      client_framework::{
+        ClientFramework, ClientFrameworkBuilder, ClientFrameworkConn, ClientFrameworkRM,
+      },
+      Version,
+    },
+    http1::{Http1, Http1Buffer},
+    http2::{Http2Buffer, Http2DataTokio, Http2Tokio},
     misc::UriRef,
     pool::{ResourceManager, SimplePoolResource},
   };
   use tokio::{
-    net::{tcp::OwnedWriteHalf, TcpStream},
+    net::{
+      tcp::{OwnedReadHalf, OwnedWriteHalf},
+      TcpStream,
+    },
     sync::Mutex,
   };
 
   /// A [`ClientFramework`] using the elements of `tokio`.
   pub type ClientFrameworkTokio =
     ClientFramework<Mutex<SimplePoolResource<Instance>>, ClientFrameworkRM<TcpStream>>;
-  type Instance = Http2Tokio<Http2Buffer, OwnedWriteHalf, true>;
+  type Instance = ClientFrameworkConn<
+    Http2DataTokio<Http2Buffer, OwnedWriteHalf, true>,
+    OwnedReadHalf,
+    OwnedWriteHalf,
+  >;
 
   impl ClientFrameworkTokio {
-    /// Creates a new builder with the maximum number of connections delimited by `len`.
+    /// Creates a new builder with the maximum number of connections of each origin delimited by
+    /// `len`.
     ///
-    /// Connection is established using the elements provided by the `tokio` project.
+    /// Connection is established using the elements provided by the `tokio` project. Because
+    /// there is no protocol negotiation, HTTP/2 is used with prior knowledge unless another
+    /// version is specified in [`ClientFrameworkBuilder::version`].
     #[inline]
     pub fn tokio(
       len: usize,
@@ -135,20 +410,12 @@ mod tokio {
 
     #[inline]
     async fn create(&self, aux: &Self::CreateAux) -> Result<Self::Resource, Self::Error> {
-      let uri = UriRef::new(aux);
-      let (frame_reader, http2) = Http2Tokio::connect(
-        Http2Buffer::default(),
-        self._cp._to_hp(),
-        TcpStream::connect(uri.hostname_with_implied_port()).await?.into_split(),
-      )
-      .await?;
-      let _jh = tokio::spawn(frame_reader);
-      Ok(http2)
+      connect((Http1Buffer::default(), Http2Buffer::default()), self, aux).await
     }
 
     #[inline]
     async fn is_invalid(&self, resource: &Self::Resource) -> bool {
-      resource.connection_state().is_closed()
+      resource._is_closed()
     }
 
     #[inline]
@@ -157,43 +424,65 @@ mod tokio {
       aux: &Self::RecycleAux,
       resource: &mut Self::Resource,
     ) -> Result<(), Self::Error> {
-      let uri = UriRef::new(aux);
-      let mut buffer = Http2Buffer::default();
-      resource._swap_buffers(&mut buffer).await;
-      let (frame_reader, http2) = Http2Tokio::connect(
-        buffer,
-        self._cp._to_hp(),
-        TcpStream::connect(uri.hostname_with_implied_port()).await?.into_split(),
-      )
-      .await?;
-      let _jh = tokio::spawn(frame_reader);
-      *resource = http2;
+      let mut buffers = (Http1Buffer::default(), Http2Buffer::default());
+      resource._swap_buffers(&mut buffers.0, &mut buffers.1).await;
+      *resource = connect(buffers, self, aux).await?;
       Ok(())
     }
   }
+
+  async fn connect(
+    (h1b, h2b): (Http1Buffer, Http2Buffer),
+    rm: &ClientFrameworkRM<TcpStream>,
+    uri: &str,
+  ) -> crate::Result<Instance> {
+    let uri = UriRef::new(uri);
+    let parts = TcpStream::connect(uri.hostname_with_implied_port()).await?.into_split();
+    if rm._version == Version::Http2 {
+      let (frame_reader, http2) = Http2Tokio::connect(h2b, rm._cp._to_hp(), parts).await?;
+      let _jh = tokio::spawn(frame_reader);
+      Ok(ClientFrameworkConn::Http2(http2))
+    } else {
+      Ok(ClientFrameworkConn::Http1(Http1::connect(h1b, rm._cp._to_h1p(), parts)))
+    }
+  }
 }
 
 #[cfg(feature = "tokio-rustls")]
 mod tokio_rustls {
   use crate::{
-    http::client_framework::{ClientFramework, ClientFrameworkBuilder, ClientFrameworkRM},
-    http2::{Http2Buffer, Http2Tokio},
+    http::{
+      client_framework::{
+        ClientFramework, ClientFrameworkBuilder, ClientFrameworkConn, ClientFrameworkRM,
+      },
+      Version,
+    },
+    http1::{Http1, Http1Buffer},
+    http2::{Http2Buffer, Http2DataTokio, Http2Tokio},
     misc::{TokioRustlsConnector, UriRef},
     pool::{ResourceManager, SimplePoolResource},
   };
-  use tokio::{io::WriteHalf, net::TcpStream, sync::Mutex};
+  use tokio::{
+    io::{ReadHalf, WriteHalf},
+    net::TcpStream,
+    sync::Mutex,
+  };
   use tokio_rustls::client::TlsStream;
 
   /// A [`ClientFramework`] using the elements of `tokio-rustls`.
   pub type ClientFrameworkTokioRustls =
     ClientFramework<Mutex<SimplePoolResource<Instance>>, ClientFrameworkRM<Writer>>;
-  type Instance = Http2Tokio<Http2Buffer, Writer, true>;
+  type Instance = ClientFrameworkConn<Http2DataTokio<Http2Buffer, Writer, true>, Reader, Writer>;
+  type Reader = ReadHalf<TlsStream<TcpStream>>;
   type Writer = WriteHalf<TlsStream<TcpStream>>;
 
   impl ClientFrameworkTokioRustls {
-    /// Creates a new builder with the maximum number of connections delimited by `len`.
+    /// Creates a new builder with the maximum number of connections of each origin delimited by
+    /// `len`.
     ///
-    /// Connection is established using the elements provided by the `tokio-rustls` project.
+    /// Connection is established using the elements provided by the `tokio-rustls` project. The
+    /// protocol is selected through ALPN, which means that servers that don't support HTTP/2
+    /// are contacted using HTTP/1.1.
     #[inline]
     pub fn tokio_rustls(
       len: usize,
@@ -210,28 +499,12 @@ mod tokio_rustls {
 
     #[inline]
     async fn create(&self, aux: &Self::CreateAux) -> Result<Self::Resource, Self::Error> {
-      let uri = UriRef::new(aux);
-      let (frame_reader, http2) = Http2Tokio::connect(
-        Http2Buffer::default(),
-        self._cp._to_hp(),
-        tokio::io::split(
-          TokioRustlsConnector::from_auto()?
-            .http2()
-            .connect_without_client_auth(
-              uri.hostname(),
-              TcpStream::connect(uri.hostname_with_implied_port()).await?,
-            )
-            .await?,
-        ),
-      )
-      .await?;
-      let _jh = tokio::spawn(frame_reader);
-      Ok(http2)
+      connect((Http1Buffer::default(), Http2Buffer::default()), self, aux).await
     }
 
     #[inline]
     async fn is_invalid(&self, resource: &Self::Resource) -> bool {
-      resource.connection_state().is_closed()
+      resource._is_closed()
     }
 
     #[inline]
@@ -240,26 +513,38 @@ mod tokio_rustls {
       aux: &Self::RecycleAux,
       resource: &mut Self::Resource,
     ) -> Result<(), Self::Error> {
-      let uri = UriRef::new(aux);
-      let mut buffer = Http2Buffer::default();
-      resource._swap_buffers(&mut buffer).await;
-      let (frame_reader, http2) = Http2Tokio::connect(
-        Http2Buffer::default(),
-        self._cp._to_hp(),
-        tokio::io::split(
-          TokioRustlsConnector::from_auto()?
-            .http2()
-            .connect_without_client_auth(
-              uri.hostname(),
-              TcpStream::connect(uri.hostname_with_implied_port()).await?,
-            )
-            .await?,
-        ),
+      let mut buffers = (Http1Buffer::default(), Http2Buffer::default());
+      resource._swap_buffers(&mut buffers.0, &mut buffers.1).await;
+      *resource = connect(buffers, self, aux).await?;
+      Ok(())
+    }
+  }
+
+  async fn connect(
+    (h1b, h2b): (Http1Buffer, Http2Buffer),
+    rm: &ClientFrameworkRM<Writer>,
+    uri: &str,
+  ) -> crate::Result<Instance> {
+    let uri = UriRef::new(uri);
+    let mut connector = TokioRustlsConnector::from_auto()?;
+    if rm._version == Version::Http2 {
+      connector = connector.http2();
+    }
+    let tls_stream = connector
+      .push_alpn_protocol(b"http/1.1")
+      .connect_without_client_auth(
+        uri.hostname(),
+        TcpStream::connect(uri.hostname_with_implied_port()).await?,
       )
       .await?;
+    let is_http2 = tls_stream.get_ref().1.alpn_protocol() == Some(b"h2");
+    let parts = tokio::io::split(tls_stream);
+    if is_http2 {
+      let (frame_reader, http2) = Http2Tokio::connect(h2b, rm._cp._to_hp(), parts).await?;
       let _jh = tokio::spawn(frame_reader);
-      *resource = http2;
-      Ok(())
+      Ok(ClientFrameworkConn::Http2(http2))
+    } else {
+      Ok(ClientFrameworkConn::Http1(Http1::connect(h1b, rm._cp._to_h1p(), parts)))
     }
   }
 }
@@ -1,19 +1,30 @@
 use crate::{
   http::{
-    client_framework::{ClientFramework, ClientFrameworkRM},
+    client_framework::{
+      origin_pools::{origin, OriginPools},
+      ClientFramework, ClientFrameworkRM, RedirectPolicy,
+    },
     conn_params::ConnParams,
+    Version,
   },
-  misc::Lock,
-  pool::{ResourceManager, SimplePool, SimplePoolResource},
+  misc::{Arc, Lock, UriRef, Vector},
+  pool::{ResourceManager, SimplePoolResource},
 };
-use core::marker::PhantomData;
+use alloc::string::String;
+use core::{marker::PhantomData, time::Duration};
 
 /// Allows the customization of parameters that control HTTP requests and responses.
 #[derive(Debug)]
 pub struct ClientFrameworkBuilder<RL, S> {
+  #[cfg(feature = "http-cookie")]
+  cookie_jar: Option<crate::http::client_framework::CookieJar>,
   cp: ConnParams,
+  idle_timeout: Duration,
   len: usize,
+  origin_lens: Vector<(String, usize)>,
   phantom: PhantomData<(RL, S)>,
+  redirect_policy: RedirectPolicy,
+  version: Version,
 }
 
 impl<RL, S> ClientFrameworkBuilder<RL, S>
@@ -25,16 +36,93 @@ where
 {
   #[inline]
   pub(crate) fn _new(len: usize) -> Self {
-    Self { cp: ConnParams::default(), len, phantom: PhantomData }
+    Self {
+      #[cfg(feature = "http-cookie")]
+      cookie_jar: None,
+      cp: ConnParams::default(),
+      idle_timeout: Duration::from_secs(90),
+      len,
+      origin_lens: Vector::new(),
+      phantom: PhantomData,
+      redirect_policy: RedirectPolicy::none(),
+      version: Version::Http2,
+    }
   }
 
   /// Creates a new client with inner parameters.
   #[inline]
   pub fn build(self) -> ClientFramework<RL, ClientFrameworkRM<S>> {
     ClientFramework {
-      pool: SimplePool::new(self.len, ClientFrameworkRM { _cp: self.cp, _phantom: PhantomData }),
+      #[cfg(feature = "http-cookie")]
+      cookie_jar: self.cookie_jar.map(|el| crate::misc::Arc::new(std::sync::Mutex::new(el))),
+      pools: Arc::new(OriginPools::new(
+        self.idle_timeout,
+        self.len,
+        self.origin_lens,
+        ClientFrameworkRM { _cp: self.cp, _phantom: PhantomData, _version: self.version },
+      )),
+      redirect_policy: self.redirect_policy,
     }
   }
 
+  /// Stores the cookies of all responses and attaches them to subsequent requests. `jar` can
+  /// contain cookies of previous sessions.
+  ///
+  /// Cookies are not stored by default.
+  #[cfg(feature = "http-cookie")]
+  #[inline]
+  #[must_use]
+  pub fn cookie_jar(mut self, jar: crate::http::client_framework::CookieJar) -> Self {
+    self.cookie_jar = Some(jar);
+    self
+  }
+
+  /// Origins that don't receive requests during this period have their connections closed. The
+  /// verification is performed when requests are sent and origins with in-flight requests are
+  /// skipped.
+  ///
+  /// Defaults to 90 seconds.
+  #[inline]
+  #[must_use]
+  pub fn idle_timeout(mut self, elem: Duration) -> Self {
+    self.idle_timeout = elem;
+    self
+  }
+
+  /// Overrides the maximum number of connections of the origin of `uri`, i.e., of its scheme,
+  /// host and port. Other origins use the number provided in the constructor.
+  #[inline]
+  pub fn origin_len(mut self, uri: &str, len: usize) -> crate::Result<Self> {
+    let key = origin(&UriRef::new(uri))?;
+    self.origin_lens.retain(|el| el.0 != key);
+    self.origin_lens.push((key, len))?;
+    Ok(self)
+  }
+
+  /// How responses with redirection status codes are handled.
+  ///
+  /// Defaults to [`RedirectPolicy::none`].
+  #[inline]
+  #[must_use]
+  pub fn redirect_policy(mut self, elem: RedirectPolicy) -> Self {
+    self.redirect_policy = elem;
+    self
+  }
+
+  /// Preferred HTTP version
+  ///
+  /// Plaintext connections are established using this version, which means that
+  /// [`Version::Http2`] implies prior knowledge. Encrypted connections advertise `http/1.1` as a
+  /// fallback of `h2` through ALPN unless HTTP/1.1 or HTTP/1 is specified, in which case only
+  /// `http/1.1` is advertised.
+  ///
+  /// Defaults to [`Version::Http2`].
+  #[inline]
+  #[must_use]
+  pub fn version(mut self, elem: Version) -> Self {
+    self.version = elem;
+    self
+  }
+
   _conn_params_methods!();
 }
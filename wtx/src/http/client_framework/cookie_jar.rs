@@ -0,0 +1,387 @@
+use crate::{
+  http::{cookie::cookie_bytes::CookieBytes, Header, Headers, KnownHeaderName, SameSite},
+  misc::{bytes_split1, bytes_split_once1, from_utf8_basic, UriRef, Vector},
+};
+use core::{net::Ipv4Addr, time::Duration};
+
+const MAX_COOKIES: usize = 1024;
+
+/// Stores cookies received through `Set-Cookie` headers and attaches them to subsequent requests
+/// whose URIs match their domains and paths.
+///
+/// * Cookies are replaced or removed when a server sends a new cookie with the same name, domain
+///   and path. Expired cookies are discarded.
+/// * `Secure` cookies are only stored and sent through `https` URIs.
+/// * `SameSite=None` cookies without the `Secure` attribute are rejected. Because requests are
+///   always initiated by the client itself, other cookies are sent regardless of `SameSite`.
+/// * `Domain` attributes with a single label, like `com`, are only accepted when they are equal
+///   to the host and hosts that are IP addresses only match themselves. The public suffix list
+///   isn't consulted, which means that attributes like `co.uk` are still accepted.
+#[derive(Debug, Default)]
+pub struct CookieJar {
+  buffer: Vector<u8>,
+  cookies: Vector<StoredCookie>,
+}
+
+impl CookieJar {
+  /// New empty instance
+  #[inline]
+  pub const fn new() -> Self {
+    Self { buffer: Vector::new(), cookies: Vector::new() }
+  }
+
+  /// Removes all stored cookies.
+  #[inline]
+  pub fn clear(&mut self) {
+    self.cookies.clear();
+  }
+
+  /// Value of the non-expired cookie called `name` that would be sent to `uri`.
+  #[inline]
+  pub fn get(&self, name: &[u8], now: Duration, uri: &UriRef<'_>) -> Option<&[u8]> {
+    self
+      .cookies
+      .iter()
+      .find(|el| el.name.as_slice() == name && !el.is_expired(now) && el.matches(uri))
+      .map(|el| el.value.as_slice())
+  }
+
+  /// If there are no stored cookies.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.cookies.is_empty()
+  }
+
+  /// Number of stored cookies, including the ones that expired but weren't discarded yet.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.cookies.len()
+  }
+
+  /// Discards expired cookies and pushes a `Cookie` header with all cookies that match `uri`.
+  /// Cookies with longer paths are listed first.
+  ///
+  /// Existing `Cookie` headers are merged into the pushed header, after their own cookies.
+  /// Returns `true` if a header was pushed.
+  ///
+  /// `now` is the UNIX timestamp of the current time.
+  #[inline]
+  pub fn push_request_cookies(
+    &mut self,
+    headers: &mut Headers,
+    now: Duration,
+    uri: &UriRef<'_>,
+  ) -> crate::Result<bool> {
+    self.cookies.retain(|el| !el.is_expired(now));
+    let mut matched = Vector::new();
+    for cookie in self.cookies.iter() {
+      if cookie.matches(uri) {
+        matched.push(cookie)?;
+      }
+    }
+    if matched.is_empty() {
+      return Ok(false);
+    }
+    matched.sort_by(|a, b| b.path.len().cmp(&a.path.len()));
+    let cookie_name = <&str>::from(KnownHeaderName::Cookie);
+    self.buffer.clear();
+    if headers.iter().any(|el| el.name == cookie_name) {
+      let mut others = Headers::new();
+      for header in headers.iter() {
+        if header.name != cookie_name {
+          others.push_from_iter(Header {
+            is_sensitive: header.is_sensitive,
+            is_trailer: header.is_trailer,
+            name: header.name,
+            value: [header.value],
+          })?;
+          continue;
+        }
+        if !self.buffer.is_empty() {
+          self.buffer.extend_from_copyable_slice(b"; ")?;
+        }
+        self.buffer.extend_from_copyable_slice(header.value)?;
+      }
+      *headers = others;
+    }
+    let prev: &[u8] = &self.buffer;
+    headers.push_from_iter(Header::from_name_and_value(
+      cookie_name,
+      [prev].into_iter().chain(matched.iter().enumerate().flat_map(|(idx, el)| {
+        let sep: &[u8] = if idx == 0 && prev.is_empty() { b"" } else { b"; " };
+        [sep, el.name.as_slice(), b"=", el.value.as_slice()]
+      })),
+    ))?;
+    self.buffer.clear();
+    Ok(true)
+  }
+
+  /// Stores the cookies of all `Set-Cookie` headers of a response to a request sent to `uri`.
+  /// Malformed cookies or cookies that `uri` isn't allowed to set are ignored.
+  ///
+  /// `now` is the UNIX timestamp of the current time.
+  #[inline]
+  pub fn store_response_cookies(
+    &mut self,
+    headers: &Headers,
+    now: Duration,
+    uri: &UriRef<'_>,
+  ) -> crate::Result<()> {
+    let host = uri.hostname().as_bytes();
+    let is_https = uri.scheme().eq_ignore_ascii_case("https");
+    for header in headers.iter() {
+      if header.name != <&str>::from(KnownHeaderName::SetCookie) {
+        continue;
+      }
+      let Ok(CookieBytes { generic }) = CookieBytes::parse(header.value, &mut self.buffer) else {
+        continue;
+      };
+      if (generic.secure && !is_https)
+        || (matches!(generic.same_site, Some(SameSite::None)) && !generic.secure)
+      {
+        continue;
+      }
+      let domain_attr = generic.domain.strip_prefix(b".").unwrap_or(generic.domain);
+      let (domain, host_only) = if domain_attr.is_empty() {
+        (host, true)
+      } else if !domain_attr.contains(&b'.') || is_ip(host) {
+        if !host.eq_ignore_ascii_case(domain_attr) {
+          continue;
+        }
+        (host, true)
+      } else if domain_matches(host, domain_attr) {
+        (domain_attr, false)
+      } else {
+        continue;
+      };
+      let path = if generic.path.starts_with(b"/") {
+        generic.path
+      } else {
+        default_path(uri.path().as_bytes())
+      };
+      let expire = match (generic.max_age, generic.expire) {
+        (Some(elem), _) => Some(now.saturating_add(elem)),
+        (None, Some(elem)) => {
+          Some(Duration::from_secs(u64::try_from(elem.timestamp()).unwrap_or(0)))
+        }
+        (None, None) => None,
+      };
+      // The name and the value are sent back exactly as received, without percent-decoding.
+      let first = bytes_split1(header.value, b';').next().unwrap_or_default();
+      let (name, value) = bytes_split_once1(first, b'=').unwrap_or_default();
+      let (name, value) = (name.trim_ascii(), value.trim_ascii());
+      self.cookies.retain(|el| {
+        el.name.as_slice() != name
+          || !el.domain.eq_ignore_ascii_case(domain)
+          || el.path.as_slice() != path
+      });
+      if expire.is_some_and(|el| el <= now) {
+        continue;
+      }
+      if self.cookies.len() >= MAX_COOKIES {
+        self.cookies.retain(|el| !el.is_expired(now));
+        if self.cookies.len() >= MAX_COOKIES {
+          let _ = self.cookies.remove(0);
+        }
+      }
+      let mut lower_domain = Vector::from_slice(domain)?;
+      lower_domain.make_ascii_lowercase();
+      self.cookies.push(StoredCookie {
+        domain: lower_domain,
+        expire,
+        host_only,
+        name: Vector::from_slice(name)?,
+        path: Vector::from_slice(path)?,
+        secure: generic.secure,
+        value: Vector::from_slice(value)?,
+      })?;
+    }
+    self.buffer.clear();
+    Ok(())
+  }
+}
+
+#[derive(Debug)]
+struct StoredCookie {
+  domain: Vector<u8>,
+  expire: Option<Duration>,
+  host_only: bool,
+  name: Vector<u8>,
+  path: Vector<u8>,
+  secure: bool,
+  value: Vector<u8>,
+}
+
+impl StoredCookie {
+  #[inline]
+  fn is_expired(&self, now: Duration) -> bool {
+    self.expire.is_some_and(|el| el <= now)
+  }
+
+  #[inline]
+  fn matches(&self, uri: &UriRef<'_>) -> bool {
+    let host = uri.hostname().as_bytes();
+    let is_domain_match = if self.host_only {
+      host.eq_ignore_ascii_case(&self.domain)
+    } else {
+      domain_matches(host, &self.domain)
+    };
+    is_domain_match
+      && path_matches(uri.path().as_bytes(), &self.path)
+      && (!self.secure || uri.scheme().eq_ignore_ascii_case("https"))
+  }
+}
+
+// https://datatracker.ietf.org/doc/html/rfc6265#section-5.1.4
+#[inline]
+fn default_path(uri_path: &[u8]) -> &[u8] {
+  match uri_path.iter().rposition(|el| *el == b'/') {
+    Some(0) | None => b"/",
+    Some(idx) => uri_path.get(..idx).unwrap_or_default(),
+  }
+}
+
+// https://datatracker.ietf.org/doc/html/rfc6265#section-5.1.3
+#[inline]
+fn domain_matches(host: &[u8], domain: &[u8]) -> bool {
+  if host.eq_ignore_ascii_case(domain) {
+    return true;
+  }
+  if is_ip(host) {
+    return false;
+  }
+  let Some(idx) = host.len().checked_sub(domain.len()) else {
+    return false;
+  };
+  let (prefix, suffix) = host.split_at(idx);
+  prefix.ends_with(b".") && suffix.eq_ignore_ascii_case(domain)
+}
+
+#[inline]
+fn is_ip(host: &[u8]) -> bool {
+  host.starts_with(b"[") || from_utf8_basic(host).is_ok_and(|el| el.parse::<Ipv4Addr>().is_ok())
+}
+
+// https://datatracker.ietf.org/doc/html/rfc6265#section-5.1.4
+#[inline]
+fn path_matches(uri_path: &[u8], cookie_path: &[u8]) -> bool {
+  let uri_path = if uri_path.is_empty() { b"/" } else { uri_path };
+  match uri_path.strip_prefix(cookie_path) {
+    Some(rest) => rest.is_empty() || cookie_path.ends_with(b"/") || rest.starts_with(b"/"),
+    None => false,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    http::{client_framework::CookieJar, Header, Headers, KnownHeaderName},
+    misc::UriRef,
+  };
+  use core::time::Duration;
+
+  const NOW: Duration = Duration::from_secs(1_700_000_000);
+
+  #[test]
+  fn attributes() {
+    let mut jar = CookieJar::new();
+    store(
+      &mut jar,
+      "https://www.example.com/app/login",
+      &[
+        "sid=a%3Bb; Path=/; Secure; HttpOnly",
+        "pref=dark; Domain=.example.com; Path=/; Max-Age=60",
+        "local=1",
+        "lax=1; SameSite=Lax",
+        "insecure=1; SameSite=None",
+        "stolen=1; Domain=other.com",
+        "old=1; Expires=Thu, 01 Jan 1970 00:00:00 GMT",
+      ],
+    );
+    assert_eq!(jar.len(), 4);
+    assert_eq!(
+      cookies(&mut jar, NOW, "https://www.example.com/app/x"),
+      Some("local=1; lax=1; sid=a%3Bb; pref=dark".into())
+    );
+    assert_eq!(cookies(&mut jar, NOW, "http://www.example.com/"), Some("pref=dark".into()));
+    assert_eq!(cookies(&mut jar, NOW, "https://api.example.com/"), Some("pref=dark".into()));
+    assert_eq!(cookies(&mut jar, NOW, "https://example.org/"), None);
+    assert_eq!(cookies(&mut jar, NOW + Duration::from_secs(60), "https://api.example.com/"), None);
+    assert_eq!(jar.len(), 3);
+  }
+
+  #[test]
+  fn merges_existing_cookie_headers() {
+    let mut jar = CookieJar::new();
+    let uri = "http://localhost/";
+    store(&mut jar, uri, &["id=1"]);
+    let mut headers = Headers::new();
+    headers
+      .push_from_iter_many([
+        Header::from_name_and_value(
+          KnownHeaderName::Cookie.into(),
+          [b"a=b".as_slice()].into_iter(),
+        ),
+        Header::from_name_and_value(
+          KnownHeaderName::Accept.into(),
+          [b"*/*".as_slice()].into_iter(),
+        ),
+      ])
+      .unwrap();
+    let _ = jar.push_request_cookies(&mut headers, NOW, &UriRef::new(uri)).unwrap();
+    assert_eq!(headers.headers_len(), 2);
+    let [accept, cookie] =
+      headers.get_many_by_name([KnownHeaderName::Accept.into(), KnownHeaderName::Cookie.into()]);
+    assert_eq!(accept.unwrap().value, b"*/*");
+    assert_eq!(cookie.unwrap().value, b"a=b; id=1");
+  }
+
+  #[test]
+  fn rejects_broad_domains() {
+    let mut jar = CookieJar::new();
+    store(&mut jar, "https://www.example.com/", &["a=1; Domain=com", "b=2; Domain=.com"]);
+    assert!(jar.is_empty());
+    store(&mut jar, "http://localhost/", &["c=3; Domain=localhost"]);
+    assert_eq!(cookies(&mut jar, NOW, "http://localhost/"), Some("c=3".into()));
+    assert_eq!(cookies(&mut jar, NOW, "http://www.localhost/"), None);
+    jar.clear();
+    store(&mut jar, "http://10.0.0.1/", &["d=4; Domain=0.0.1", "e=5; Domain=10.0.0.1"]);
+    assert_eq!(jar.len(), 1);
+    assert_eq!(cookies(&mut jar, NOW, "http://10.0.0.1/"), Some("e=5".into()));
+    assert_eq!(cookies(&mut jar, NOW, "http://110.0.0.1/"), None);
+  }
+
+  #[test]
+  fn replaces_and_deletes() {
+    let mut jar = CookieJar::new();
+    let uri = "http://localhost/a/b";
+    store(&mut jar, uri, &["id=1", "id=2; Path=/"]);
+    store(&mut jar, uri, &["id=3"]);
+    assert_eq!(cookies(&mut jar, NOW, uri), Some("id=3; id=2".into()));
+    assert_eq!(cookies(&mut jar, NOW, "http://localhost/ab"), Some("id=2".into()));
+    store(&mut jar, uri, &["id=; Max-Age=0"]);
+    assert_eq!(jar.get(b"id", NOW, &UriRef::new(uri)), Some(&b"2"[..]));
+    store(&mut jar, uri, &["id=; Path=/; Max-Age=-1"]);
+    assert!(jar.is_empty());
+  }
+
+  fn cookies(jar: &mut CookieJar, now: Duration, uri: &str) -> Option<alloc::string::String> {
+    let mut headers = Headers::new();
+    let _ = jar.push_request_cookies(&mut headers, now, &UriRef::new(uri)).unwrap();
+    let header = headers.get_by_name(KnownHeaderName::Cookie.into())?;
+    Some(crate::misc::from_utf8_basic(header.value).unwrap().into())
+  }
+
+  fn store(jar: &mut CookieJar, uri: &str, set_cookies: &[&str]) {
+    let mut headers = Headers::new();
+    for set_cookie in set_cookies {
+      headers
+        .push_from_iter(Header::from_name_and_value(
+          KnownHeaderName::SetCookie.into(),
+          [set_cookie.as_bytes()],
+        ))
+        .unwrap();
+    }
+    jar.store_response_cookies(&headers, NOW, &UriRef::new(uri)).unwrap();
+  }
+}
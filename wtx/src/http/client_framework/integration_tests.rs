@@ -1,7 +1,7 @@
 use crate::{
   http::{
     client_framework::{ClientFramework, ReqBuilder},
-    ReqResBuffer,
+    ReqResBuffer, Version,
   },
   misc::Uri,
 };
@@ -21,3 +21,13 @@ async fn popular_sites() {
     .await
     .unwrap();
 }
+
+#[tokio::test]
+async fn popular_sites_with_http1() {
+  let client = ClientFramework::tokio_rustls(1).version(Version::Http1_1).build();
+  let res = ReqBuilder::get(ReqResBuffer::empty())
+    .send(&client, &Uri::new("https://www.google.com"))
+    .await
+    .unwrap();
+  assert_eq!(res.version, Version::Http1_1);
+}
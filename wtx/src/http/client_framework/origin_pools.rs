@@ -0,0 +1,118 @@
+use crate::{
+  misc::{Arc, Lock, SyncLock, UriRef, Vector},
+  pool::{ResourceManager, SimplePool, SimplePoolResource},
+};
+use alloc::string::String;
+use core::{fmt::Write, time::Duration};
+use hashbrown::HashMap;
+use std::{sync::Mutex, time::Instant};
+
+/// Set of [`SimplePool`]s indexed by the scheme, the host and the port of URIs.
+#[derive(Debug)]
+pub(crate) struct OriginPools<RL, RM> {
+  idle_timeout: Duration,
+  len: usize,
+  origin_lens: Vector<(String, usize)>,
+  pools: Mutex<HashMap<String, (Instant, SimplePool<RL, RM>)>>,
+  rm: Arc<RM>,
+}
+
+impl<RL, RM> OriginPools<RL, RM> {
+  #[inline]
+  pub(crate) fn new(
+    idle_timeout: Duration,
+    len: usize,
+    origin_lens: Vector<(String, usize)>,
+    rm: RM,
+  ) -> Self {
+    Self { idle_timeout, len, origin_lens, pools: Mutex::new(HashMap::new()), rm: Arc::new(rm) }
+  }
+
+  /// Removes all pools.
+  #[inline]
+  pub(crate) fn drain(&self) -> Vector<SimplePool<RL, RM>> {
+    let mut pools = SyncLock::lock(&self.pools);
+    let mut rslt = Vector::new();
+    let _rslt = rslt.extend_from_iter(pools.drain().map(|(_, (_, pool))| pool));
+    rslt
+  }
+}
+
+impl<R, RL, RM> OriginPools<RL, RM>
+where
+  RL: Lock<Resource = SimplePoolResource<R>>,
+  RM: ResourceManager<Resource = R>,
+  for<'any> RL: 'any,
+  for<'any> RM: 'any,
+{
+  /// Returns the pool that manages the connections of the origin of `uri` as well as all pools
+  /// whose origins weren't used during the idle timeout. The later are removed and should be
+  /// closed by the caller.
+  ///
+  /// Pools that are still used by other requests are never considered idle, which means that
+  /// closing the returned pools doesn't wait for in-flight requests.
+  #[inline]
+  pub(crate) fn pool(
+    &self,
+    uri: &UriRef<'_>,
+  ) -> crate::Result<(SimplePool<RL, RM>, Vector<SimplePool<RL, RM>>)> {
+    let key = origin(uri)?;
+    let now = Instant::now();
+    let mut pools = SyncLock::lock(&self.pools);
+    let mut idle = Vector::new();
+    for (_, (_, pool)) in pools.extract_if(|local_key, (last_used, pool)| {
+      *local_key != key
+        && now.saturating_duration_since(*last_used) >= self.idle_timeout
+        && pool._is_unique()
+    }) {
+      idle.push(pool)?;
+    }
+    let len = self.origin_lens.iter().find(|el| el.0 == key).map_or(self.len, |el| el.1);
+    let (last_used, pool) = pools
+      .entry(key)
+      .or_insert_with(|| (now, SimplePool::_with_shared_rm(len, Arc::clone(&self.rm))));
+    *last_used = now;
+    Ok((pool.clone(), idle))
+  }
+}
+
+/// Lowercase representation of the scheme, the host and the port of `uri`.
+#[inline]
+pub(crate) fn origin(uri: &UriRef<'_>) -> crate::Result<String> {
+  let mut rslt = String::new();
+  rslt.write_fmt(format_args!(
+    "{}://{}:{}",
+    uri.scheme(),
+    uri.hostname(),
+    uri.port().unwrap_or_default()
+  ))?;
+  rslt.make_ascii_lowercase();
+  Ok(rslt)
+}
+
+#[cfg(all(feature = "tokio", test))]
+mod tests {
+  use crate::{
+    http::client_framework::origin_pools::OriginPools,
+    misc::{UriRef, Vector},
+    pool::SimplePoolResource,
+  };
+  use core::time::Duration;
+
+  #[test]
+  fn in_use_pools_are_not_idle() {
+    let pools = OriginPools::<tokio::sync::Mutex<SimplePoolResource<()>>, ()>::new(
+      Duration::ZERO,
+      1,
+      Vector::new(),
+      (),
+    );
+    let (first, idle) = pools.pool(&UriRef::new("http://a.com")).unwrap();
+    assert!(idle.is_empty());
+    let (_, idle) = pools.pool(&UriRef::new("http://b.com")).unwrap();
+    assert!(idle.is_empty());
+    drop(first);
+    let (_, idle) = pools.pool(&UriRef::new("http://b.com")).unwrap();
+    assert_eq!(idle.len(), 1);
+  }
+}
@@ -0,0 +1,285 @@
+use crate::{
+  http::{Method, ReqResBuffer, Response, StatusCode},
+  misc::{str_split1, UriRef, UriString, Vector},
+};
+use alloc::string::String;
+
+/// Controls how [`crate::http::client_framework::ClientFramework`] follows responses that have
+/// a redirection status code and a `Location` header.
+///
+/// * `301 Moved Permanently` and `302 Found`: `POST` requests are resent as `GET` requests
+///   without a body unless [`Self::rewrite_post`] is `false`. Other methods are kept.
+/// * `303 See Other`: Requests are resent as `GET` requests without a body, except `HEAD`
+///   requests.
+/// * `307 Temporary Redirect` and `308 Permanent Redirect`: The method and the body are kept.
+///
+/// `Authorization`, `Cookie` and `Proxy-Authorization` headers are removed when a redirection
+/// points to a different origin. Cookies of a jar are still attached if they match the new
+/// origin.
+#[derive(Clone, Copy, Debug)]
+pub struct RedirectPolicy {
+  https_downgrade: bool,
+  max_hops: u8,
+  rewrite_post: bool,
+  same_origin: bool,
+}
+
+impl RedirectPolicy {
+  /// Follows at most `max_hops` redirections. Receiving another redirection after the limit
+  /// was reached results in an error.
+  #[inline]
+  pub const fn new(max_hops: u8) -> Self {
+    Self { https_downgrade: false, max_hops, rewrite_post: true, same_origin: false }
+  }
+
+  /// If redirections from `https` URIs to `http` URIs should be followed. Otherwise, such
+  /// responses are returned as is.
+  ///
+  /// Defaults to `false`.
+  #[inline]
+  #[must_use]
+  pub const fn https_downgrade(mut self, elem: bool) -> Self {
+    self.https_downgrade = elem;
+    self
+  }
+
+  /// Redirections are not followed, which means that responses are returned as is.
+  #[inline]
+  pub const fn none() -> Self {
+    Self::new(0)
+  }
+
+  /// If `POST` requests should become `GET` requests after `301` or `302` responses, like
+  /// browsers do.
+  ///
+  /// Defaults to `true`.
+  #[inline]
+  #[must_use]
+  pub const fn rewrite_post(mut self, elem: bool) -> Self {
+    self.rewrite_post = elem;
+    self
+  }
+
+  /// If redirections to other schemes, hosts or ports should be ignored. Such responses are
+  /// returned as is.
+  ///
+  /// Defaults to `false`.
+  #[inline]
+  #[must_use]
+  pub const fn same_origin(mut self, elem: bool) -> Self {
+    self.same_origin = elem;
+    self
+  }
+
+  /// If a redirection from `from` to `to` should be followed, where `is_cross_origin` indicates
+  /// if both URIs have different origins.
+  #[inline]
+  pub(crate) fn _is_followed(
+    &self,
+    from: &UriRef<'_>,
+    to: &UriRef<'_>,
+    is_cross_origin: bool,
+  ) -> bool {
+    if is_cross_origin && self.same_origin {
+      return false;
+    }
+    let is_downgrade =
+      from.scheme().eq_ignore_ascii_case("https") && to.scheme().eq_ignore_ascii_case("http");
+    !is_downgrade || self.https_downgrade
+  }
+
+  #[inline]
+  pub(crate) const fn _max_hops(&self) -> u8 {
+    self.max_hops
+  }
+
+  /// Method of the subsequent request and if the body should be kept. `None` if `status_code`
+  /// isn't a followed redirection.
+  #[inline]
+  pub(crate) fn _next_method(
+    &self,
+    method: Method,
+    status_code: StatusCode,
+  ) -> Option<(Method, bool)> {
+    Some(match status_code {
+      StatusCode::MovedPermanently | StatusCode::Found => {
+        if self.rewrite_post && method == Method::Post {
+          (Method::Get, false)
+        } else {
+          (method, true)
+        }
+      }
+      StatusCode::SeeOther => {
+        if method == Method::Head {
+          (method, false)
+        } else {
+          (Method::Get, false)
+        }
+      }
+      StatusCode::TemporaryRedirect | StatusCode::PermanentRedirect => (method, true),
+      _ => return None,
+    })
+  }
+}
+
+impl Default for RedirectPolicy {
+  #[inline]
+  fn default() -> Self {
+    Self::none()
+  }
+}
+
+/// Response of [`crate::http::client_framework::ClientFramework::send_redirected`].
+#[derive(Debug)]
+pub struct RedirectedResponse {
+  /// Status codes and URIs of all followed redirections, in order. Empty if the first response
+  /// wasn't redirected.
+  pub redirects: Vector<(StatusCode, UriString)>,
+  /// Final response, whose URI is the effective URI of the request.
+  pub res: Response<ReqResBuffer>,
+}
+
+/// Writes into `buffer` the target of a `Location` header received from `uri`.
+///
+/// <https://datatracker.ietf.org/doc/html/rfc3986#section-5.2>
+#[inline]
+pub(crate) fn _resolve_location(
+  uri: &UriRef<'_>,
+  location: &str,
+  buffer: &mut UriString,
+) -> crate::Result<()> {
+  if has_scheme(location) {
+    return buffer.reset(format_args!("{location}"));
+  }
+  if location.starts_with("//") {
+    return buffer.reset(format_args!("{}:{location}", uri.scheme()));
+  }
+  let (location_path, suffix) =
+    location.find(['?', '#']).map_or((location, ""), |idx| location.split_at(idx));
+  let base_path = uri.path();
+  let mut path = String::new();
+  if location_path.is_empty() {
+    path.push_str(base_path);
+    if suffix.is_empty() || suffix.starts_with('#') {
+      let query_and_fragment = uri.query_and_fragment();
+      path.push_str(query_and_fragment.split_once('#').map_or(query_and_fragment, |el| el.0));
+    }
+  } else if location_path.starts_with('/') {
+    push_without_dot_segments(location_path, &mut path)?;
+  } else {
+    let mut merged = String::new();
+    match base_path.rfind('/') {
+      Some(idx) => merged.push_str(base_path.get(..=idx).unwrap_or_default()),
+      None => merged.push('/'),
+    }
+    merged.push_str(location_path);
+    push_without_dot_segments(&merged, &mut path)?;
+  }
+  buffer.reset(format_args!("{}://{}{path}{suffix}", uri.scheme(), uri.authority()))
+}
+
+/// If `location` starts with a scheme, i.e., with a letter followed by letters, digits, `+`, `-`
+/// or `.` and then `:`.
+#[inline]
+fn has_scheme(location: &str) -> bool {
+  let Some((scheme, _)) = location.split_once(':') else {
+    return false;
+  };
+  let mut bytes = scheme.bytes();
+  bytes.next().is_some_and(|el| el.is_ascii_alphabetic())
+    && bytes.all(|el| el.is_ascii_alphanumeric() || matches!(el, b'+' | b'-' | b'.'))
+}
+
+// https://datatracker.ietf.org/doc/html/rfc3986#section-5.2.4
+#[inline]
+fn push_without_dot_segments(path: &str, rslt: &mut String) -> crate::Result<()> {
+  let mut segments = Vector::new();
+  let mut iter = str_split1(path, b'/').skip(1).peekable();
+  while let Some(segment) = iter.next() {
+    match segment {
+      "." => {}
+      ".." => {
+        let _ = segments.pop();
+      }
+      _ => segments.push(segment)?,
+    }
+    if matches!(segment, "." | "..") && iter.peek().is_none() {
+      segments.push("")?;
+    }
+  }
+  if segments.is_empty() {
+    rslt.push('/');
+  }
+  for segment in segments.iter() {
+    rslt.push('/');
+    rslt.push_str(segment);
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    http::{
+      client_framework::{redirect_policy::_resolve_location, RedirectPolicy},
+      Method, StatusCode,
+    },
+    misc::{UriRef, UriString},
+  };
+  use alloc::string::String;
+
+  #[test]
+  fn next_method() {
+    let policy = RedirectPolicy::new(1);
+    assert_eq!(policy._next_method(Method::Post, StatusCode::Found), Some((Method::Get, false)));
+    assert_eq!(
+      policy.rewrite_post(false)._next_method(Method::Post, StatusCode::MovedPermanently),
+      Some((Method::Post, true))
+    );
+    assert_eq!(policy._next_method(Method::Put, StatusCode::SeeOther), Some((Method::Get, false)));
+    assert_eq!(
+      policy._next_method(Method::Post, StatusCode::PermanentRedirect),
+      Some((Method::Post, true))
+    );
+    assert_eq!(policy._next_method(Method::Get, StatusCode::NotModified), None);
+  }
+
+  #[test]
+  fn is_followed() {
+    let (https, http) = (UriRef::new("https://a.com/"), UriRef::new("http://a.com/"));
+    let policy = RedirectPolicy::new(1);
+    assert!(policy._is_followed(&http, &https, true));
+    assert!(!policy._is_followed(&https, &http, true));
+    assert!(policy.https_downgrade(true)._is_followed(&https, &http, true));
+    assert!(!policy.same_origin(true)._is_followed(&http, &https, true));
+    assert!(policy.same_origin(true)._is_followed(&http, &http, false));
+  }
+
+  #[test]
+  fn resolve_location() {
+    let uri = UriRef::new("http://localhost:8080/a/b?c=d");
+    let mut buffer = UriString::new(String::new());
+    for (location, expected) in [
+      ("https://example.com/x", "https://example.com/x"),
+      ("custom+v1.2:opaque", "custom+v1.2:opaque"),
+      ("//example.com/x", "http://example.com/x"),
+      ("/x?y=z", "http://localhost:8080/x?y=z"),
+      ("/login?next=https://app/", "http://localhost:8080/login?next=https://app/"),
+      ("x", "http://localhost:8080/a/x"),
+      ("x:y/z", "x:y/z"),
+      ("./x:y", "http://localhost:8080/a/x:y"),
+      ("?e=f", "http://localhost:8080/a/b?e=f"),
+      ("#g", "http://localhost:8080/a/b?c=d#g"),
+      ("", "http://localhost:8080/a/b?c=d"),
+      ("./", "http://localhost:8080/a/"),
+      ("./x?next=a://b", "http://localhost:8080/a/x?next=a://b"),
+      ("../x", "http://localhost:8080/x"),
+      ("../../../x", "http://localhost:8080/x"),
+      ("..", "http://localhost:8080/"),
+      ("/a/./b/../c/.", "http://localhost:8080/a/c/"),
+    ] {
+      _resolve_location(&uri, location, &mut buffer).unwrap();
+      assert_eq!(buffer.as_str(), expected);
+    }
+  }
+}
@@ -1,10 +1,10 @@
 use crate::{
   http::{
-    client_framework::ClientFramework, Header, KnownHeaderName, Method, Mime, ReqResBuffer, ReqUri,
-    Response,
+    client_framework::{ClientFramework, ClientFrameworkConn},
+    Header, KnownHeaderName, Method, Mime, ReqResBuffer, ReqUri, Response,
   },
-  http2::{Http2, Http2Buffer, Http2Data},
-  misc::{LeaseMut, Lock, RefCounter, StreamWriter},
+  http2::{Http2Buffer, Http2Data},
+  misc::{LeaseMut, Lock, RefCounter, StreamReader, StreamWriter},
   pool::{ResourceManager, SimplePoolResource},
 };
 
@@ -38,7 +38,7 @@ impl ReqBuilder {
 
   /// Sends a request with inner parameters.
   #[inline]
-  pub async fn send<HD, RL, RM, SW>(
+  pub async fn send<HD, RL, RM, SR, SW>(
     self,
     client: &ClientFramework<RL, RM>,
     req_uri: impl Into<ReqUri<'_>>,
@@ -51,8 +51,9 @@ impl ReqBuilder {
       CreateAux = str,
       Error = crate::Error,
       RecycleAux = str,
-      Resource = Http2<HD, true>,
+      Resource = ClientFrameworkConn<HD, SR, SW>,
     >,
+    SR: StreamReader,
     SW: StreamWriter,
     for<'any> RL: 'any,
     for<'any> RM: 'any,
@@ -0,0 +1,226 @@
+use crate::{
+  http::{
+    client_framework::{ClientFramework, RedirectPolicy, ReqBuilder},
+    Header, HttpError, KnownHeaderName, Method, ReqResBuffer, ReqUri, Response, StatusCode,
+    Version,
+  },
+  http1::{Http1, Http1Buffer, Http1Params},
+  misc::{from_utf8_basic, UriString},
+  tests::_uri,
+};
+use alloc::{borrow::ToOwned, format, sync::Arc, vec::Vec};
+use core::{
+  sync::atomic::{AtomicUsize, Ordering},
+  time::Duration,
+};
+use tokio::{net::TcpListener, task::JoinHandle};
+
+#[tokio::test]
+async fn pools_connections_by_origin() {
+  let (first_uri, second_uri) = (_uri(), _uri());
+  let first_server = server(&first_uri, |_, rrb| body(rrb, b"first")).await;
+  let second_server = server(&second_uri, |_, rrb| body(rrb, b"second")).await;
+  let client =
+    ClientFramework::tokio(1).idle_timeout(Duration::ZERO).version(Version::Http1_1).build();
+  for (uri, body) in [(&first_uri, &b"first"[..]), (&second_uri, b"second")] {
+    let mut rrb = ReqResBuffer::empty();
+    rrb.uri.reset(format_args!("{}/", uri.as_str())).unwrap();
+    let res = ReqBuilder::get(rrb).send(&client, ReqUri::Data).await.unwrap();
+    assert_eq!(res.rrd.body.as_slice(), body);
+  }
+  // The first origin was idle when the second one was contacted
+  assert_eq!(first_server.await.unwrap(), 1);
+  client.close_all().await;
+  assert_eq!(second_server.await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn follows_redirects() {
+  let (first_uri, second_uri) = (_uri(), _uri());
+  let second_str = second_uri.as_str().to_owned();
+  let _first_server = server(&first_uri, move |_, rrb| {
+    let (status_code, location) = match rrb.uri.path() {
+      "/a" => (StatusCode::Found, "/b".to_owned()),
+      "/b" => (StatusCode::TemporaryRedirect, format!("{second_str}/c")),
+      _ => (StatusCode::PermanentRedirect, "/loop".to_owned()),
+    };
+    rrb.clear();
+    let header =
+      Header::from_name_and_value(KnownHeaderName::Location.into(), [location.as_bytes()]);
+    rrb.headers.push_from_iter(header).unwrap();
+    status_code
+  })
+  .await;
+  let _second_server = server(&second_uri, |method, rrb| {
+    let has_auth = rrb.headers.get_many_by_name([
+      KnownHeaderName::Authorization.into(),
+      KnownHeaderName::Cookie.into(),
+      KnownHeaderName::ProxyAuthorization.into(),
+    ]) != [None; 3];
+    let has_body = !rrb.body.is_empty();
+    body(rrb, format!("{method} {has_auth} {has_body}").as_bytes())
+  })
+  .await;
+  let client = ClientFramework::tokio(1)
+    .redirect_policy(RedirectPolicy::new(2))
+    .version(Version::Http1_1)
+    .build();
+
+  let mut rrb = ReqResBuffer::empty();
+  rrb.uri.reset(format_args!("{}/a", first_uri.as_str())).unwrap();
+  for name in
+    [KnownHeaderName::Authorization, KnownHeaderName::Cookie, KnownHeaderName::ProxyAuthorization]
+  {
+    rrb.headers.push_from_iter(Header::from_name_and_value(name.into(), [&b"secret"[..]])).unwrap();
+  }
+  rrb.body.extend_from_copyable_slice(b"data").unwrap();
+  let res = client.send_redirected(Method::Post, rrb, ReqUri::Data).await.unwrap();
+  assert_eq!(res.res.status_code, StatusCode::Ok);
+  assert_eq!(res.res.rrd.body.as_slice(), b"Get false false");
+  assert_eq!(res.res.rrd.uri.as_str(), format!("{}/c", second_uri.as_str()));
+  let redirects: Vec<_> = res.redirects.iter().map(|el| (el.0, el.1.path())).collect();
+  assert_eq!(redirects, [(StatusCode::Found, "/a"), (StatusCode::TemporaryRedirect, "/b")]);
+
+  let mut rrb = ReqResBuffer::empty();
+  rrb.uri.reset(format_args!("{}/loop", first_uri.as_str())).unwrap();
+  let err = client.send(Method::Get, rrb, ReqUri::Data).await.unwrap_err();
+  assert!(matches!(err, crate::Error::HttpError(HttpError::TooManyRedirects)));
+}
+
+#[tokio::test]
+async fn keeps_requests_of_same_origin_redirects() {
+  let uri = _uri();
+  let _server = server(&uri, |method, rrb| {
+    if rrb.uri.path() == "/keep" {
+      rrb.clear();
+      for (name, value) in
+        [(KnownHeaderName::Location, "/echo"), (KnownHeaderName::SetCookie, "a=1")]
+      {
+        rrb
+          .headers
+          .push_from_iter(Header::from_name_and_value(name.into(), [value.as_bytes()]))
+          .unwrap();
+      }
+      return StatusCode::TemporaryRedirect;
+    }
+    let cookie =
+      rrb.headers.get_by_name(KnownHeaderName::Cookie.into()).map(|el| from_utf8_basic(el.value));
+    let data = format!("{method} {cookie:?} {:?}", from_utf8_basic(&rrb.body));
+    body(rrb, data.as_bytes())
+  })
+  .await;
+  let builder = ClientFramework::tokio(1).redirect_policy(RedirectPolicy::new(1));
+  #[cfg(feature = "http-cookie")]
+  let builder = builder.cookie_jar(crate::http::client_framework::CookieJar::new());
+  let client = builder.version(Version::Http1_1).build();
+  // The second iteration also sends the cookie stored by the first one
+  for _ in 0..2 {
+    let mut rrb = ReqResBuffer::empty();
+    rrb.uri.reset(format_args!("{}/keep", uri.as_str())).unwrap();
+    rrb
+      .headers
+      .push_from_iter(Header::from_name_and_value(KnownHeaderName::Cookie.into(), [&b"c=0"[..]]))
+      .unwrap();
+    rrb.body.extend_from_copyable_slice(b"data").unwrap();
+    let res = client.send(Method::Put, rrb, ReqUri::Data).await.unwrap();
+    let cookie = if cfg!(feature = "http-cookie") { "c=0; a=1" } else { "c=0" };
+    assert_eq!(
+      res.rrd.body.as_slice(),
+      format!("Put Some(Ok({cookie:?})) Ok(\"data\")").as_bytes()
+    );
+    assert_eq!(res.rrd.uri.as_str(), format!("{}/echo", uri.as_str()));
+  }
+}
+
+#[tokio::test]
+async fn limits_connections_per_origin() {
+  for (origin_len, expected) in [(None, 1), (Some(2), 2)] {
+    let uri = _uri();
+    let listener = TcpListener::bind(uri.hostname_with_implied_port()).await.unwrap();
+    let conns = Arc::new(AtomicUsize::new(0));
+    let local_conns = Arc::clone(&conns);
+    let _server = tokio::spawn(async move {
+      loop {
+        let (tcp_stream, _) = listener.accept().await.unwrap();
+        let _ = local_conns.fetch_add(1, Ordering::Relaxed);
+        let _conn = tokio::spawn(async move {
+          let parts = tcp_stream.into_split();
+          let mut http1 = Http1::accept(Http1Buffer::new(), Http1Params::default(), parts);
+          let mut rrb = ReqResBuffer::empty();
+          while let Ok(Some(_)) = http1.recv_req(&mut rrb).await {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            let status_code = body(&mut rrb, b"");
+            let _ = http1.send_res(Response::http2(&rrb, status_code)).await.unwrap();
+          }
+        });
+      }
+    });
+    let mut builder = ClientFramework::tokio(1).version(Version::Http1_1);
+    if let Some(elem) = origin_len {
+      builder = builder.origin_len(uri.as_str(), elem).unwrap();
+    }
+    let client = builder.build();
+    let send = || async {
+      let mut rrb = ReqResBuffer::empty();
+      rrb.uri.reset(format_args!("{}/", uri.as_str())).unwrap();
+      ReqBuilder::get(rrb).send(&client, ReqUri::Data).await.unwrap().status_code
+    };
+    assert_eq!(tokio::join!(send(), send()), (StatusCode::Ok, StatusCode::Ok));
+    assert_eq!(conns.load(Ordering::Relaxed), expected);
+    client.close_all().await;
+  }
+}
+
+#[tokio::test]
+async fn reuses_http1_connections() {
+  let uri = _uri();
+  let listener = TcpListener::bind(uri.hostname_with_implied_port()).await.unwrap();
+  let _server = tokio::spawn(async move {
+    let (tcp_stream, _) = listener.accept().await.unwrap();
+    drop(listener);
+    let parts = tcp_stream.into_split();
+    let mut http1 = Http1::accept(Http1Buffer::new(), Http1Params::default(), parts);
+    let mut rrb = ReqResBuffer::empty();
+    while http1.recv_req(&mut rrb).await.unwrap().is_some() {
+      let path_len = rrb.uri.path().len();
+      rrb.clear();
+      rrb.body.extend_from_copyable_slice(&path_len.to_be_bytes()).unwrap();
+      let _ = http1.send_res(Response::http2(&rrb, StatusCode::Ok)).await.unwrap();
+    }
+  });
+  let client = ClientFramework::tokio(1).version(Version::Http1_1).build();
+  for path in ["/a", "/bb"] {
+    let mut rrb = ReqResBuffer::empty();
+    rrb.uri.reset(format_args!("{}{path}", uri.as_str())).unwrap();
+    let res = ReqBuilder::get(rrb).send(&client, ReqUri::Data).await.unwrap();
+    assert_eq!(res.status_code, StatusCode::Ok);
+    assert_eq!(res.version, Version::Http1_1);
+    assert_eq!(res.rrd.body.as_slice(), &path.len().to_be_bytes());
+  }
+}
+
+async fn server(
+  uri: &UriString,
+  cb: impl Fn(Method, &mut ReqResBuffer) -> StatusCode + Send + 'static,
+) -> JoinHandle<usize> {
+  let listener = TcpListener::bind(uri.hostname_with_implied_port()).await.unwrap();
+  tokio::spawn(async move {
+    let (tcp_stream, _) = listener.accept().await.unwrap();
+    let mut http1 =
+      Http1::accept(Http1Buffer::new(), Http1Params::default(), tcp_stream.into_split());
+    let mut rrb = ReqResBuffer::empty();
+    let mut counter = 0;
+    while let Ok(Some((method, _))) = http1.recv_req(&mut rrb).await {
+      counter += 1;
+      let status_code = cb(method, &mut rrb);
+      let _ = http1.send_res(Response::http2(&rrb, status_code)).await.unwrap();
+    }
+    counter
+  })
+}
+
+fn body(rrb: &mut ReqResBuffer, body: &[u8]) -> StatusCode {
+  rrb.clear();
+  rrb.body.extend_from_copyable_slice(body).unwrap();
+  StatusCode::Ok
+}
@@ -3,12 +3,15 @@ mod cookie_error;
 pub(crate) mod cookie_generic;
 mod same_site;
 
-use crate::misc::{mem_transfer::_shift_copyable_chunks, ArrayVector, Rng, Vector};
+use crate::misc::ArrayVector;
+#[cfg(feature = "http-cookie-secure")]
+use crate::misc::{mem_transfer::_shift_copyable_chunks, Rng, Vector};
 pub use cookie_error::CookieError;
-use core::str;
 pub use same_site::SameSite;
 
+#[cfg(feature = "http-cookie-secure")]
 const NONCE_LEN: usize = 12;
+#[cfg(feature = "http-cookie-secure")]
 const TAG_LEN: usize = 16;
 
 static FMT1: &str = "%a, %d %b %Y %H:%M:%S GMT";
@@ -70,7 +70,7 @@ impl<'bytes> CookieBytes<'bytes> {
       let (name, value) = if let Some(elem) = bytes_split_once1(semicolon, b'=') {
         (elem.0.trim_ascii(), elem.1.trim_ascii())
       } else {
-        return Err(crate::Error::from(CookieError::IrregularCookie));
+        (semicolon.trim_ascii(), &[][..])
       };
       make_lowercase::<12>(&mut lower_case, name);
       match (lower_case.as_ref(), value) {
@@ -124,3 +124,40 @@ impl<'bytes> CookieBytes<'bytes> {
     Ok(Self { generic: cookie })
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    http::{cookie::cookie_bytes::CookieBytes, SameSite},
+    misc::Vector,
+  };
+  use core::time::Duration;
+
+  #[test]
+  fn flag_attributes() {
+    let mut vector = Vector::new();
+    let cookie = CookieBytes::parse(
+      b"id=a%20b; Secure; HttpOnly; Partitioned; Path=/; Max-Age=60; SameSite=Lax",
+      &mut vector,
+    )
+    .unwrap()
+    .generic;
+    assert_eq!(cookie.name, b"id");
+    assert_eq!(cookie.value, b"a b");
+    assert!(cookie.http_only);
+    assert!(cookie.secure);
+    assert_eq!(cookie.path, b"/");
+    assert_eq!(cookie.max_age, Some(Duration::from_secs(60)));
+    assert!(matches!(cookie.same_site, Some(SameSite::Lax)));
+    let cookie = CookieBytes::parse(b"id=1; Domain; Path", &mut vector).unwrap().generic;
+    assert_eq!((cookie.domain, cookie.path), (&[][..], &[][..]));
+    assert!(!cookie.http_only && !cookie.secure);
+  }
+
+  #[test]
+  fn irregular_cookies() {
+    let mut vector = Vector::new();
+    assert!(CookieBytes::parse(b"Secure", &mut vector).is_err());
+    assert!(CookieBytes::parse(b"=1; Secure", &mut vector).is_err());
+  }
+}
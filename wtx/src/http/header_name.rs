@@ -188,8 +188,11 @@ create_statics! {
   ContentLocation = "content-location";
   ContentMd5 = "content-md5";
   ContentRange = "content-range";
+  ContentSecurityPolicy = "content-security-policy";
   ContentType = "content-type";
   Cookie = "cookie";
+  CrossOriginEmbedderPolicy = "cross-origin-embedder-policy";
+  CrossOriginOpenerPolicy = "cross-origin-opener-policy";
   Date = "date";
   Etag = "etag";
   Expect = "expect";
@@ -208,12 +211,14 @@ create_statics! {
   Location = "location";
   MaxForwards = "max-forwards";
   Origin = "origin";
+  PermissionsPolicy = "permissions-policy";
   Pragma = "pragma";
   ProxyAuthenticate = "proxy-authenticate";
   ProxyAuthorization = "proxy-authorization";
   ProxyConnection = "proxy-connection";
   Range = "range";
   Referer = "referer";
+  ReferrerPolicy = "referrer-policy";
   Refresh = "refresh";
   RetryAfter = "retry-after";
   SecWebsocketAccept = "sec-websocket-accept";
@@ -236,4 +241,5 @@ create_statics! {
   Via = "via";
   Warning = "warning";
   WwwAuthenticate = "www-authenticate";
+  XContentTypeOptions = "x-content-type-options";
 }
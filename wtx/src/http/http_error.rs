@@ -18,6 +18,8 @@ pub enum HttpError {
   MissingRequestMethod,
   /// Received response does not contain a status code field
   MissingResponseStatusCode,
+  /// The maximum number of followed redirections was reached
+  TooManyRedirects,
   /// Content-Type mismatch
   UnexpectedContentType,
   /// HTTP version does not match the expected method.
@@ -13,7 +13,7 @@ _create_enum! {
     /// Options
     Options = (4, "OPTIONS" | "options"),
     /// Patch
-    Patch = (5, "PATCH" | "path"),
+    Patch = (5, "PATCH" | "patch"),
     /// Post
     Post = (6, "POST" | "post"),
     /// Put
@@ -66,3 +66,20 @@ mod serde {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use crate::http::Method;
+
+  #[test]
+  fn strings() {
+    for method in Method::ALL {
+      let [upper, lower] = method.strings().custom;
+      assert_eq!(upper.to_ascii_lowercase(), lower);
+      assert_eq!(Method::try_from(upper).unwrap(), method);
+      assert_eq!(Method::try_from(lower.as_bytes()).unwrap(), method);
+    }
+    assert_eq!(Method::try_from("patch").unwrap(), Method::Patch);
+    assert!(Method::try_from("path").is_err());
+  }
+}
@@ -3,18 +3,50 @@
 pub enum Mime {
   /// Opaque bytes
   Bytes,
+  /// CSS
+  Css,
   /// Anything
   Custom(&'static str),
+  /// Server-Sent Events
+  EventStream,
+  /// Multipart form
+  FormData,
+  /// URL-encoded form
+  FormUrlEncoded,
+  /// GIF image
+  Gif,
   /// Grpc
   Grpc,
+  /// HTML
+  Html,
+  /// Icon
+  Ico,
+  /// JavaScript
+  Javascript,
+  /// JPEG image
+  Jpeg,
   /// JSON
   Json,
   /// JSON:API
   JsonApi,
+  /// PDF
+  Pdf,
+  /// PNG image
+  Png,
   /// Protocol buffer
   Protobuf,
+  /// SVG image
+  Svg,
   /// Plain text
   Text,
+  /// WebAssembly
+  Wasm,
+  /// WebP image
+  Webp,
+  /// WOFF font
+  Woff,
+  /// WOFF2 font
+  Woff2,
   /// XML
   Xml,
   /// YAML
@@ -22,17 +54,66 @@ pub enum Mime {
 }
 
 impl Mime {
+  /// Guesses the MIME type of a file based on its extension. The comparison is case-insensitive.
+  ///
+  /// ```rust
+  /// assert_eq!(wtx::http::Mime::from_extension("HTML"), Some(wtx::http::Mime::Html));
+  /// ```
+  #[inline]
+  pub fn from_extension(extension: &str) -> Option<Self> {
+    const PAIRS: &[(&str, Mime)] = &[
+      ("css", Mime::Css),
+      ("gif", Mime::Gif),
+      ("htm", Mime::Html),
+      ("html", Mime::Html),
+      ("ico", Mime::Ico),
+      ("jpeg", Mime::Jpeg),
+      ("jpg", Mime::Jpeg),
+      ("js", Mime::Javascript),
+      ("json", Mime::Json),
+      ("mjs", Mime::Javascript),
+      ("pdf", Mime::Pdf),
+      ("png", Mime::Png),
+      ("svg", Mime::Svg),
+      ("txt", Mime::Text),
+      ("wasm", Mime::Wasm),
+      ("webp", Mime::Webp),
+      ("woff", Mime::Woff),
+      ("woff2", Mime::Woff2),
+      ("xml", Mime::Xml),
+      ("yaml", Mime::Yaml),
+      ("yml", Mime::Yaml),
+    ];
+    PAIRS.iter().find(|(ext, _)| ext.eq_ignore_ascii_case(extension)).map(|(_, mime)| *mime)
+  }
+
   /// Common string representation.
   #[inline]
   pub fn as_str(&self) -> &'static str {
     match self {
       Self::Bytes => "application/octet-stream",
+      Self::Css => "text/css",
       Self::Custom(el) => el,
+      Self::EventStream => "text/event-stream",
+      Self::FormData => "multipart/form-data",
+      Self::FormUrlEncoded => "application/x-www-form-urlencoded",
+      Self::Gif => "image/gif",
       Self::Grpc => "application/grpc",
+      Self::Html => "text/html",
+      Self::Ico => "image/vnd.microsoft.icon",
+      Self::Javascript => "text/javascript",
+      Self::Jpeg => "image/jpeg",
       Self::Json => "application/json",
       Self::JsonApi => "application/vnd.api+json",
+      Self::Pdf => "application/pdf",
+      Self::Png => "image/png",
       Self::Protobuf => "application/vnd.google.protobuf",
+      Self::Svg => "image/svg+xml",
       Self::Text => "text/plain",
+      Self::Wasm => "application/wasm",
+      Self::Webp => "image/webp",
+      Self::Woff => "font/woff",
+      Self::Woff2 => "font/woff2",
       Self::Xml => "application/xml",
       Self::Yaml => "application/yaml",
     }
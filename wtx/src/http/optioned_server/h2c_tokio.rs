@@ -0,0 +1,251 @@
+use crate::{
+  http::{
+    optioned_server::{
+      http1_tokio::{manage_http1_conn, manage_recv_err},
+      http2_tokio::manage_http2_conn,
+      OptionedServer,
+    },
+    AutoStream, Headers, KnownHeaderName, ManualServerStreamTokio, OperationMode, Protocol,
+    ReqResBuffer, Request, Response, Version,
+  },
+  http1::{misc::read_more, Http1, Http1Buffer, Http1Error, Http1Params},
+  http2::{Http2Buffer, Http2Params, Http2Tokio, PREFACE},
+  misc::{bytes_split1, Either, FnFut, StreamWriter, _select},
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use core::{future::Future, pin::pin, time::Duration};
+use tokio::{
+  net::{
+    tcp::{OwnedReadHalf, OwnedWriteHalf},
+    TcpListener,
+  },
+  sync::watch,
+};
+
+const SWITCHING_PROTOCOLS: &[u8] =
+  b"HTTP/1.1 101 Switching Protocols\r\nconnection: Upgrade\r\nupgrade: h2c\r\n\r\n";
+
+impl OptionedServer {
+  /// Optioned cleartext HTTP/1.1 and HTTP/2 server using tokio.
+  ///
+  /// The protocol of each connection is decided by its initial bytes.
+  ///
+  /// 1. Connections that start with the HTTP/2 preface are handled as HTTP/2 connections (prior
+  ///    knowledge).
+  /// 2. HTTP/1.1 requests with `Upgrade: h2c` and a valid `HTTP2-Settings` header are answered with
+  ///    `101 Switching Protocols` and become the first stream of a new HTTP/2 connection.
+  /// 3. Everything else is handled as HTTP/1.1, where requests that would be managed manually are
+  ///    rejected.
+  ///
+  /// Once `shutdown` resolves, the server stops accepting connections and HTTP/2 connections are
+  /// drained as described in [`OptionedServer::http2_tokio`]. HTTP/1.1 connections also have
+  /// `drain_timeout` to finish their current request before being closed. Returns after all
+  /// connections are closed.
+  #[inline]
+  pub async fn h2c_tokio<A, CA, E, HA, M, OM, SA>(
+    addr: &str,
+    auto_cb: A,
+    conn_cb: impl Clone + Fn() -> crate::Result<(CA, Http1Buffer, Http1Params)> + Send + 'static,
+    err_cb: impl Clone + Fn(E) + Send + 'static,
+    http2_cb: impl Clone + Fn() -> crate::Result<(Http2Buffer, Http2Params)> + Send + 'static,
+    manual_cb: M,
+    operation_mode: OM,
+    (shutdown, drain_timeout): (impl Future<Output = ()>, Duration),
+    stream_cb: impl Clone + Fn() -> crate::Result<(SA, ReqResBuffer)> + Send + 'static,
+  ) -> crate::Result<()>
+  where
+    A: Clone
+      + FnFut<(HA, AutoStream<CA, SA>), Result = Result<Response<ReqResBuffer>, E>>
+      + Send
+      + 'static,
+    A::Future: Send,
+    CA: Clone + Send + 'static,
+    E: From<crate::Error> + Send + 'static,
+    HA: Send + 'static,
+    M: Clone
+      + FnFut<
+        (HA, ManualServerStreamTokio<CA, Http2Buffer, SA, OwnedWriteHalf>),
+        Result = Result<(), E>,
+      > + Send
+      + 'static,
+    M::Future: Send,
+    OM: Clone
+      + Fn(&CA, Option<Protocol>, Request<&mut ReqResBuffer>, &SA) -> Result<(HA, OperationMode), E>
+      + Send
+      + 'static,
+    SA: Send + 'static,
+    for<'any> &'any A: Send,
+    for<'any> &'any CA: Send,
+    for<'any> &'any M: Send,
+    for<'any> &'any OM: Send,
+    for<'any> &'any SA: Send,
+  {
+    let listener = TcpListener::bind(addr).await?;
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let mut shutdown_pin = pin!(shutdown);
+    loop {
+      let tcp_stream = match _select(listener.accept(), shutdown_pin.as_mut()).await {
+        Either::Left(elem) => elem?.0,
+        Either::Right(()) => break,
+      };
+      let peer = tcp_stream.peer_addr()?.ip();
+      let conn_auto_cb = auto_cb.clone();
+      let conn_conn_cb = conn_cb.clone();
+      let conn_err_cb = err_cb.clone();
+      let conn_http2_cb = http2_cb.clone();
+      let conn_manual_cb = manual_cb.clone();
+      let conn_op_cb = operation_mode.clone();
+      let conn_stream_cb = stream_cb.clone();
+      let mut conn_shutdown_rx = shutdown_rx.clone();
+      let _conn_jh = tokio::spawn(async move {
+        let another_err_cb = conn_err_cb.clone();
+        let mut http2_shutdown_rx = conn_shutdown_rx.clone();
+        let http2_shutdown = async move {
+          if http2_shutdown_rx.wait_for(|is_shutting_down| *is_shutting_down).await.is_err() {
+            core::future::pending::<()>().await;
+          }
+          drain_timeout
+        };
+        let fun = async move {
+          let (conn_ca, mut h1b, h1p) = conn_conn_cb()?;
+          let (mut sr, sw) = tcp_stream.into_split();
+          if has_preface(&mut h1b, &mut sr).await? {
+            let (h2b, h2p) = conn_http2_cb()?;
+            let (frame_reader, http2, _) =
+              Http2Tokio::_accept(h2b, h2p, h1b._pending(), None, (sr, sw)).await?;
+            let _frame_reader_jh = tokio::spawn(frame_reader);
+            manage_http2_conn(
+              conn_auto_cb,
+              conn_ca,
+              conn_err_cb,
+              http2,
+              conn_manual_cb,
+              conn_op_cb,
+              peer,
+              http2_shutdown,
+              conn_stream_cb,
+              None,
+            )
+            .await;
+            return Ok(());
+          }
+          let mut http1 = Http1::accept(h1b, h1p, (sr, sw));
+          let mut rrb = ReqResBuffer::empty();
+          let (method, version) = match http1.recv_req(&mut rrb).await {
+            Err(err) => return Err(manage_recv_err(err, &mut http1, &mut rrb).await.into()),
+            Ok(None) => return Ok(()),
+            Ok(Some(elem)) => elem,
+          };
+          let mut settings_buffer = [0; 128];
+          if let Some(settings) = h2c_settings(&mut settings_buffer, &rrb.headers) {
+            let (h1b, sr, mut sw) = http1.into_parts();
+            sw.write_all(SWITCHING_PROTOCOLS).await?;
+            let (stream_aux, _) = conn_stream_cb()?;
+            let req = Request { method, rrd: &mut rrb, version: Version::Http2 };
+            let (headers_aux, om) = conn_op_cb(&conn_ca, None, req, &stream_aux)?;
+            let (sorp_rrb, opt) = match om {
+              OperationMode::Auto => (rrb, None),
+              OperationMode::Manual => (ReqResBuffer::empty(), Some(rrb)),
+            };
+            let (h2b, h2p) = conn_http2_cb()?;
+            let (frame_reader, http2, stream) = Http2Tokio::_accept(
+              h2b,
+              h2p,
+              h1b._pending(),
+              Some((method, sorp_rrb, settings)),
+              (sr, sw),
+            )
+            .await?;
+            let _frame_reader_jh = tokio::spawn(frame_reader);
+            manage_http2_conn(
+              conn_auto_cb,
+              conn_ca,
+              conn_err_cb,
+              http2,
+              conn_manual_cb,
+              conn_op_cb,
+              peer,
+              http2_shutdown,
+              conn_stream_cb,
+              stream.map(|elem| (headers_aux, opt, elem, stream_aux)),
+            )
+            .await;
+            return Ok(());
+          }
+          manage_http1_conn(
+            conn_auto_cb,
+            conn_ca,
+            move |ca: &CA, req: Request<&mut ReqResBuffer>, sa: &SA| {
+              let (headers_aux, om) = conn_op_cb(ca, None, req, sa)?;
+              if om == OperationMode::Manual {
+                return Err(E::from(Http1Error::UnsupportedOperationMode.into()));
+              }
+              Ok(headers_aux)
+            },
+            http1,
+            Some((method, version)),
+            peer,
+            rrb,
+            move || Ok(conn_stream_cb()?.0),
+          )
+          .await
+        };
+        let deadline = async {
+          if conn_shutdown_rx.wait_for(|is_shutting_down| *is_shutting_down).await.is_err() {
+            core::future::pending::<()>().await;
+          }
+          tokio::time::sleep(drain_timeout).await;
+        };
+        if let Either::Left(Err(err)) = _select(fun, deadline).await {
+          another_err_cb(err);
+        }
+        // The server only returns after all receivers are dropped.
+        drop(conn_shutdown_rx);
+      });
+    }
+    drop(listener);
+    drop(shutdown_rx);
+    let _rslt = shutdown_tx.send(true);
+    shutdown_tx.closed().await;
+    Ok(())
+  }
+}
+
+/// Returns the decoded `HTTP2-Settings` payload if `headers` ask for an `h2c` upgrade.
+fn h2c_settings<'buffer>(
+  buffer: &'buffer mut [u8; 128],
+  headers: &Headers,
+) -> Option<&'buffer [u8]> {
+  let has_token = |name: &[u8], token: &[u8]| {
+    headers.get_by_name(name).is_some_and(|header| {
+      bytes_split1(header.value, b',').any(|elem| elem.trim_ascii().eq_ignore_ascii_case(token))
+    })
+  };
+  let connection = KnownHeaderName::Connection.into();
+  if !has_token(KnownHeaderName::Upgrade.into(), b"h2c")
+    || !has_token(connection, b"upgrade")
+    || !has_token(connection, b"http2-settings")
+  {
+    return None;
+  }
+  let value = headers.get_by_name(b"http2-settings")?.value.trim_ascii();
+  let len = URL_SAFE_NO_PAD.decode_slice(value, buffer).ok()?;
+  buffer.get(..len)
+}
+
+/// Reads bytes while they are equal to the beginning of the HTTP/2 preface.
+async fn has_preface(hb: &mut Http1Buffer, sr: &mut OwnedReadHalf) -> crate::Result<bool> {
+  loop {
+    let pending = hb._pending();
+    let len = pending.len().min(PREFACE.len());
+    if pending.get(..len) != PREFACE.get(..len) {
+      return Ok(false);
+    }
+    if len == PREFACE.len() {
+      return Ok(true);
+    }
+    if read_more(0, hb, sr).await? == 0 {
+      return Ok(false);
+    }
+  }
+}
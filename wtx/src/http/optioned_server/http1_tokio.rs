@@ -0,0 +1,170 @@
+use crate::{
+  http::{
+    optioned_server::OptionedServer, AutoStream, Method, ReqResBuffer, Request, Response,
+    StatusCode, Version,
+  },
+  http1::{Http1, Http1Buffer, Http1Params},
+  misc::{FnFut, StreamReader, StreamWriter},
+};
+use core::{future::Future, mem, net::IpAddr};
+use tokio::net::{TcpListener, TcpStream};
+
+impl OptionedServer {
+  /// Optioned HTTP/1.1 server using tokio.
+  ///
+  /// Requests of the same connection are processed sequentially, which means that `auto_cb` is
+  /// only called again after the previous response was sent.
+  #[inline]
+  pub async fn http1_tokio<A, ACPT, CA, E, HA, N, SA, SR, SW>(
+    addr: &str,
+    auto_cb: A,
+    conn_cb: impl Clone + Fn() -> crate::Result<(CA, Http1Buffer, Http1Params)> + Send + 'static,
+    err_cb: impl Clone + Fn(E) + Send + 'static,
+    headers_cb: impl Clone + Fn(&CA, Request<&mut ReqResBuffer>, &SA) -> Result<HA, E> + Send + 'static,
+    stream_cb: impl Clone + Fn() -> crate::Result<SA> + Send + 'static,
+    (acceptor_cb, conn_acceptor_cb, net_cb): (
+      impl FnOnce() -> crate::Result<ACPT> + Send + 'static,
+      impl Clone + Fn(&ACPT) -> ACPT + Send + 'static,
+      impl Clone + Fn(ACPT, TcpStream) -> N + Send + 'static,
+    ),
+  ) -> crate::Result<()>
+  where
+    A: Clone
+      + FnFut<(HA, AutoStream<CA, SA>), Result = Result<Response<ReqResBuffer>, E>>
+      + Send
+      + 'static,
+    A::Future: Send,
+    ACPT: Send + 'static,
+    CA: Clone + Send + 'static,
+    E: From<crate::Error> + Send + 'static,
+    HA: Send + 'static,
+    N: Future<Output = crate::Result<(SR, SW)>> + Send,
+    SA: Send + 'static,
+    SR: Send + StreamReader<read(..): Send> + Unpin + 'static,
+    SW: Send + StreamWriter<write_all(..): Send, write_all_vectored(..): Send> + Unpin + 'static,
+    for<'any> &'any A: Send,
+    for<'any> &'any CA: Send,
+    for<'any> &'any SA: Send,
+  {
+    let listener = TcpListener::bind(addr).await?;
+    let acceptor = acceptor_cb()?;
+    loop {
+      let tcp_stream = listener.accept().await?.0;
+      let peer = tcp_stream.peer_addr()?.ip();
+      let conn_acceptor = conn_acceptor_cb(&acceptor);
+      let conn_auto_cb = auto_cb.clone();
+      let conn_conn_cb = conn_cb.clone();
+      let conn_err_cb = err_cb.clone();
+      let conn_headers_cb = headers_cb.clone();
+      let conn_net_cb = net_cb.clone();
+      let conn_stream_cb = stream_cb.clone();
+      let _conn_jh = tokio::spawn(async move {
+        let fun = async move {
+          let (conn_ca, hb, hp) = conn_conn_cb()?;
+          let parts = conn_net_cb(conn_acceptor, tcp_stream).await?;
+          manage_http1_conn(
+            conn_auto_cb,
+            conn_ca,
+            conn_headers_cb,
+            Http1::accept(hb, hp, parts),
+            None,
+            peer,
+            ReqResBuffer::empty(),
+            conn_stream_cb,
+          )
+          .await
+        };
+        if let Err(err) = fun.await {
+          conn_err_cb(err);
+        }
+      });
+    }
+  }
+}
+
+/// Sequentially handles all requests of an HTTP/1.1 connection.
+///
+/// If `initial` is present, then the first request was already received into `rrb`.
+pub(crate) async fn manage_http1_conn<A, CA, E, HA, SA, SR, SW>(
+  auto_cb: A,
+  conn_ca: CA,
+  headers_cb: impl Fn(&CA, Request<&mut ReqResBuffer>, &SA) -> Result<HA, E>,
+  mut http1: Http1<SR, SW, false>,
+  mut initial: Option<(Method, Version)>,
+  peer: IpAddr,
+  mut rrb: ReqResBuffer,
+  stream_cb: impl Fn() -> crate::Result<SA>,
+) -> Result<(), E>
+where
+  A: FnFut<(HA, AutoStream<CA, SA>), Result = Result<Response<ReqResBuffer>, E>>,
+  CA: Clone,
+  E: From<crate::Error>,
+  SR: StreamReader,
+  SW: StreamWriter,
+{
+  loop {
+    let rslt =
+      if let Some(elem) = initial.take() { Ok(Some(elem)) } else { http1.recv_req(&mut rrb).await };
+    let (method, version) = match rslt {
+      Err(err) => return Err(manage_recv_err(err, &mut http1, &mut rrb).await.into()),
+      Ok(None) => return Ok(()),
+      Ok(Some(elem)) => elem,
+    };
+    let rslt = match stream_cb().map_err(E::from).and_then(|stream_aux| {
+      let rrd = &mut rrb;
+      let headers_aux = headers_cb(&conn_ca, Request { method, rrd, version }, &stream_aux)?;
+      Ok((headers_aux, stream_aux))
+    }) {
+      Err(err) => Err(err),
+      Ok((headers_aux, stream_aux)) => {
+        let auto_stream = AutoStream {
+          conn_aux: conn_ca.clone(),
+          peer,
+          protocol: None,
+          req: Request { method, rrd: mem::take(&mut rrb), version },
+          stream_aux,
+        };
+        auto_cb.call((headers_aux, auto_stream)).await
+      }
+    };
+    let res = match rslt {
+      Err(err) => {
+        http1._set_is_closing();
+        rrb.clear();
+        let status_code = StatusCode::InternalServerError;
+        let _rslt = http1.send_res(Response { rrd: &rrb, status_code, version }).await;
+        return Err(err);
+      }
+      Ok(elem) => elem,
+    };
+    rrb = res.rrd;
+    let local_res = Response { rrd: &rrb, status_code: res.status_code, version };
+    if !http1.send_res(local_res).await? {
+      return Ok(());
+    }
+  }
+}
+
+/// Tries to tell the counterpart why a request couldn't be received, returning the same error.
+pub(crate) async fn manage_recv_err<SR, SW>(
+  err: crate::Error,
+  http1: &mut Http1<SR, SW, false>,
+  rrb: &mut ReqResBuffer,
+) -> crate::Error
+where
+  SR: StreamReader,
+  SW: StreamWriter,
+{
+  let status_code = match &err {
+    crate::Error::Http1Error(elem) => elem.status_code(),
+    crate::Error::HttpParse(httparse::Error::TooManyHeaders) => {
+      StatusCode::RequestHeaderFieldsTooLarge
+    }
+    crate::Error::HttpError(_) | crate::Error::HttpParse(_) => StatusCode::BadRequest,
+    _ => return err,
+  };
+  rrb.clear();
+  let res = Response { rrd: &*rrb, status_code, version: Version::Http1_1 };
+  let _rslt = http1.send_res(res).await;
+  err
+}
@@ -3,14 +3,31 @@ use crate::{
     optioned_server::OptionedServer, AutoStream, ManualServerStreamTokio, OperationMode, Protocol,
     ReqResBuffer, Request, Response,
   },
-  http2::{Http2Buffer, Http2ErrorCode, Http2Params, Http2Tokio},
-  misc::{Either, FnFut, StreamReader, StreamWriter},
+  http2::{Http2Buffer, Http2DataTokio, Http2ErrorCode, Http2Params, Http2Tokio, ServerStream},
+  misc::{Either, FnFut, StreamReader, StreamWriter, _select},
+};
+use core::{
+  future::Future,
+  mem,
+  net::IpAddr,
+  pin::pin,
+  sync::atomic::{AtomicU32, Ordering},
+  time::Duration,
+};
+use tokio::{
+  net::{TcpListener, TcpStream},
+  sync::watch,
 };
-use core::{future::Future, mem};
-use tokio::net::{TcpListener, TcpStream};
+
+type Stream<SW> = ServerStream<Http2DataTokio<Http2Buffer, SW, false>>;
 
 impl OptionedServer {
   /// Optioned HTTP/2 server using tokio.
+  ///
+  /// Once `shutdown` resolves, the server stops accepting connections and each connection sends
+  /// a GOAWAY frame with the identifier of its last processed stream. Active streams then have
+  /// `drain_timeout` to finish before the remaining connections are forcefully closed. Returns
+  /// after all connections are closed.
   #[inline]
   pub async fn http2_tokio<A, ACPT, CA, E, HA, M, N, OM, SA, SR, SW>(
     addr: &str,
@@ -19,6 +36,7 @@ impl OptionedServer {
     err_cb: impl Clone + Fn(E) + Send + 'static,
     manual_cb: M,
     operation_mode: OM,
+    (shutdown, drain_timeout): (impl Future<Output = ()>, Duration),
     stream_cb: impl Clone + Fn() -> crate::Result<(SA, ReqResBuffer)> + Send + 'static,
     (acceptor_cb, conn_acceptor_cb, net_cb): (
       impl FnOnce() -> crate::Result<ACPT> + Send + 'static,
@@ -57,8 +75,13 @@ impl OptionedServer {
   {
     let listener = TcpListener::bind(addr).await?;
     let acceptor = acceptor_cb()?;
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let mut shutdown_pin = pin!(shutdown);
     loop {
-      let tcp_stream = listener.accept().await?.0;
+      let tcp_stream = match _select(listener.accept(), shutdown_pin.as_mut()).await {
+        Either::Left(elem) => elem?.0,
+        Either::Right(()) => break,
+      };
       let peer = tcp_stream.peer_addr()?.ip();
       let conn_acceptor = conn_acceptor_cb(&acceptor);
       let conn_auto_cb = auto_cb.clone();
@@ -68,6 +91,7 @@ impl OptionedServer {
       let conn_net_cb = net_cb.clone();
       let conn_stream_cb = stream_cb.clone();
       let conn_op_cb = operation_mode.clone();
+      let mut conn_shutdown_rx = shutdown_rx.clone();
       let _conn_jh = tokio::spawn(async move {
         let initial = async move {
           let (conn_ca, http2_buffer, http2_params) = conn_conn_cb()?;
@@ -80,93 +104,228 @@ impl OptionedServer {
           .map_err(Into::into)?;
           Ok::<_, E>((conn_ca, frame_reader, http2))
         };
-        let (conn_ca, frame_reader, mut http2) = match initial.await {
+        let (conn_ca, frame_reader, http2) = match initial.await {
           Err(err) => {
             conn_err_cb(err);
             return;
           }
           Ok(elem) => elem,
         };
-        let another_conn_err_cb = conn_err_cb.clone();
-        let another_http2 = http2.clone();
         let _frame_reader_jh = tokio::spawn(frame_reader);
-        let rest = async move {
-          loop {
-            let stream_ca = conn_ca.clone();
-            let (stream_aux, rrb) = conn_stream_cb().map_err(Into::into)?;
-            let (mut stream, rslt) = match http2
-              .stream(rrb, |req, protocol| {
-                let op = conn_op_cb(
-                  &stream_ca,
-                  protocol,
-                  Request { method: req.method, rrd: &mut *req.rrd, version: req.version },
-                  &stream_aux,
-                )?;
-                Ok::<_, E>(match op.1 {
-                  OperationMode::Auto => (op.0, None),
-                  OperationMode::Manual => (op.0, Some(mem::take(req.rrd))),
-                })
-              })
-              .await
-              .map_err(Into::into)?
-            {
-              Either::Left(_) => return Ok(()),
-              Either::Right(elem) => elem,
-            };
-            let (headers_aux, opt) = rslt?;
-            let stream_auto_cb = conn_auto_cb.clone();
-            let stream_err_cb = conn_err_cb.clone();
-            let stream_manual_cb = conn_manual_cb.clone();
-            let _stream_jh = tokio::spawn(async move {
-              let stream_fun = async {
-                if let Some(local_rrb) = opt {
-                  stream_manual_cb
-                    .call((
-                      headers_aux,
-                      ManualServerStreamTokio {
-                        conn_aux: stream_ca,
-                        peer,
-                        protocol: stream.protocol(),
-                        req: Request::http2(stream.method(), local_rrb),
-                        stream: stream.clone(),
-                        stream_aux,
-                      },
-                    ))
-                    .await?;
-                  return Ok(());
-                }
-                let (hrs, local_rrb) = stream.recv_req().await?;
-                if hrs.is_closed() {
-                  return Ok(());
-                }
-                let req = local_rrb.into_http2_request(stream.method());
-                let auto_stream = AutoStream {
-                  conn_aux: stream_ca,
-                  peer,
-                  protocol: stream.protocol(),
-                  req,
-                  stream_aux,
-                };
-                let res = stream_auto_cb.call((headers_aux, auto_stream)).await?;
-                if stream.send_res(res).await?.is_closed() {
-                  return Ok(());
-                }
-                Ok::<_, E>(())
-              };
-              let stream_fun_rslt = stream_fun.await;
-              let _rslt = stream.common().clear(true).await;
-              if let Err(err) = stream_fun_rslt {
-                stream.common().send_go_away(Http2ErrorCode::InternalError).await;
-                stream_err_cb(err);
-              }
-            });
-          }
-        };
-        if let Err(err) = rest.await {
-          another_http2.send_go_away(Http2ErrorCode::NoError).await;
-          another_conn_err_cb(err);
-        }
+        manage_http2_conn(
+          conn_auto_cb,
+          conn_ca,
+          conn_err_cb,
+          http2,
+          conn_manual_cb,
+          conn_op_cb,
+          peer,
+          async {
+            if conn_shutdown_rx.wait_for(|is_shutting_down| *is_shutting_down).await.is_err() {
+              core::future::pending::<()>().await;
+            }
+            drain_timeout
+          },
+          conn_stream_cb,
+          None,
+        )
+        .await;
+        // The server only returns after all receivers are dropped.
+        drop(conn_shutdown_rx);
       });
     }
+    drop(listener);
+    drop(shutdown_rx);
+    let _rslt = shutdown_tx.send(true);
+    shutdown_tx.closed().await;
+    Ok(())
+  }
+}
+
+/// Spawns a new task for each stream of an HTTP/2 connection.
+///
+/// When `shutdown` resolves, new streams are refused and ongoing streams have the returned
+/// duration to finish before the connection is closed.
+///
+/// `upgraded` is a stream whose request was already received through HTTP/1.1 along with the
+/// results of the operation mode callback.
+pub(crate) async fn manage_http2_conn<A, CA, E, HA, M, OM, SA, SW>(
+  auto_cb: A,
+  conn_ca: CA,
+  err_cb: impl Clone + Fn(E) + Send + 'static,
+  mut http2: Http2Tokio<Http2Buffer, SW, false>,
+  manual_cb: M,
+  op_cb: OM,
+  peer: IpAddr,
+  shutdown: impl Future<Output = Duration>,
+  stream_cb: impl Fn() -> crate::Result<(SA, ReqResBuffer)>,
+  upgraded: Option<(HA, Option<ReqResBuffer>, Stream<SW>, SA)>,
+) where
+  A: Clone
+    + FnFut<(HA, AutoStream<CA, SA>), Result = Result<Response<ReqResBuffer>, E>>
+    + Send
+    + 'static,
+  A::Future: Send,
+  CA: Clone + Send + 'static,
+  E: From<crate::Error> + Send + 'static,
+  HA: Send + 'static,
+  M: Clone
+    + FnFut<(HA, ManualServerStreamTokio<CA, Http2Buffer, SA, SW>), Result = Result<(), E>>
+    + Send
+    + 'static,
+  M::Future: Send,
+  OM: Fn(&CA, Option<Protocol>, Request<&mut ReqResBuffer>, &SA) -> Result<(HA, OperationMode), E>,
+  SA: Send + 'static,
+  SW: Send + StreamWriter<write_all(..): Send, write_all_vectored(..): Send> + Unpin + 'static,
+  for<'any> &'any A: Send,
+  for<'any> &'any CA: Send,
+  for<'any> &'any M: Send,
+  for<'any> &'any SA: Send,
+{
+  let another_err_cb = err_cb.clone();
+  let another_http2 = http2.clone();
+  let last_stream_id = AtomicU32::new(u32::MAX);
+  let (streams_tx, _) = watch::channel(());
+  if let Some((headers_aux, opt, stream, stream_aux)) = upgraded {
+    spawn_stream(
+      auto_cb.clone(),
+      conn_ca.clone(),
+      err_cb.clone(),
+      headers_aux,
+      manual_cb.clone(),
+      opt,
+      peer,
+      stream,
+      stream_aux,
+      streams_tx.subscribe(),
+    );
+  }
+  let (last_stream_id_ref, streams_tx_ref) = (&last_stream_id, &streams_tx);
+  let drain_http2 = http2.clone();
+  let drain = async move {
+    let drain_timeout = shutdown.await;
+    let id = drain_http2.send_graceful_go_away().await;
+    last_stream_id_ref.store(id, Ordering::Relaxed);
+    let _ = _select(streams_tx_ref.closed(), tokio::time::sleep(drain_timeout)).await;
+    drain_http2.send_go_away(Http2ErrorCode::NoError).await;
+  };
+  let rest = async move {
+    loop {
+      let stream_ca = conn_ca.clone();
+      let (stream_aux, rrb) = stream_cb().map_err(Into::into)?;
+      let (mut stream, rslt) = match http2
+        .stream(rrb, |req, protocol| {
+          let op = op_cb(
+            &stream_ca,
+            protocol,
+            Request { method: req.method, rrd: &mut *req.rrd, version: req.version },
+            &stream_aux,
+          )?;
+          Ok::<_, E>(match op.1 {
+            OperationMode::Auto => (op.0, None),
+            OperationMode::Manual => (op.0, Some(mem::take(req.rrd))),
+          })
+        })
+        .await
+        .map_err(Into::into)?
+      {
+        Either::Left(_) => return Ok(()),
+        Either::Right(elem) => elem,
+      };
+      if stream.common().stream_id() > last_stream_id_ref.load(Ordering::Relaxed) {
+        stream.common().send_reset(Http2ErrorCode::RefusedStream).await;
+        let _rslt = stream.common().clear(false).await;
+        continue;
+      }
+      let (headers_aux, opt) = rslt?;
+      spawn_stream(
+        auto_cb.clone(),
+        stream_ca,
+        err_cb.clone(),
+        headers_aux,
+        manual_cb.clone(),
+        opt,
+        peer,
+        stream,
+        stream_aux,
+        streams_tx_ref.subscribe(),
+      );
+    }
+  };
+  let mut rest_pin = pin!(rest);
+  let rslt = match _select(rest_pin.as_mut(), drain).await {
+    Either::Left(elem) => elem,
+    Either::Right(()) => rest_pin.await,
+  };
+  if let Err(err) = rslt {
+    another_http2.send_go_away(Http2ErrorCode::NoError).await;
+    another_err_cb(err);
   }
 }
+
+fn spawn_stream<A, CA, E, HA, M, SA, SW>(
+  auto_cb: A,
+  conn_ca: CA,
+  err_cb: impl Fn(E) + Send + 'static,
+  headers_aux: HA,
+  manual_cb: M,
+  opt: Option<ReqResBuffer>,
+  peer: IpAddr,
+  mut stream: Stream<SW>,
+  stream_aux: SA,
+  streams_rx: watch::Receiver<()>,
+) where
+  A: FnFut<(HA, AutoStream<CA, SA>), Result = Result<Response<ReqResBuffer>, E>> + Send + 'static,
+  A::Future: Send,
+  CA: Send + 'static,
+  E: From<crate::Error> + Send + 'static,
+  HA: Send + 'static,
+  M: FnFut<(HA, ManualServerStreamTokio<CA, Http2Buffer, SA, SW>), Result = Result<(), E>>
+    + Send
+    + 'static,
+  M::Future: Send,
+  SA: Send + 'static,
+  SW: Send + StreamWriter<write_all(..): Send, write_all_vectored(..): Send> + Unpin + 'static,
+  for<'any> &'any A: Send,
+  for<'any> &'any M: Send,
+{
+  let _stream_jh = tokio::spawn(async move {
+    let stream_fun = async {
+      if let Some(local_rrb) = opt {
+        manual_cb
+          .call((
+            headers_aux,
+            ManualServerStreamTokio {
+              conn_aux: conn_ca,
+              peer,
+              protocol: stream.protocol(),
+              req: Request::http2(stream.method(), local_rrb),
+              stream: stream.clone(),
+              stream_aux,
+            },
+          ))
+          .await?;
+        return Ok(());
+      }
+      let (hrs, local_rrb) = stream.recv_req().await?;
+      if hrs.is_closed() {
+        return Ok(());
+      }
+      let req = local_rrb.into_http2_request(stream.method());
+      let auto_stream =
+        AutoStream { conn_aux: conn_ca, peer, protocol: stream.protocol(), req, stream_aux };
+      let res = auto_cb.call((headers_aux, auto_stream)).await?;
+      if stream.send_res(res).await?.is_closed() {
+        return Ok(());
+      }
+      Ok::<_, E>(())
+    };
+    let stream_fun_rslt = stream_fun.await;
+    let _rslt = stream.common().clear(true).await;
+    if let Err(err) = stream_fun_rslt {
+      stream.common().send_go_away(Http2ErrorCode::InternalError).await;
+      err_cb(err);
+    }
+    drop(streams_rx);
+  });
+}
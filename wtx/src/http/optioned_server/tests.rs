@@ -0,0 +1,220 @@
+use crate::{
+  http::{
+    AutoStream, ManualServerStreamTokio, Method, OperationMode, OptionedServer, ReqResBuffer,
+    Request, Response, StatusCode,
+  },
+  http1::{Http1Buffer, Http1Params},
+  http2::{Http2Buffer, Http2Params, Http2RecvStatus, Http2Tokio, PREFACE},
+  misc::{from_utf8_basic, simple_seed, UriString, Xorshift64},
+  tests::_uri,
+};
+use alloc::string::String;
+use core::{future::Future, time::Duration};
+use tokio::{
+  io::{AsyncReadExt, AsyncWriteExt},
+  net::{tcp::OwnedWriteHalf, TcpStream},
+  sync::oneshot,
+  task::JoinHandle,
+};
+
+#[tokio::test]
+async fn graceful_shutdown() {
+  async fn auto(
+    _: (),
+    mut auto_stream: AutoStream<(), ()>,
+  ) -> Result<Response<ReqResBuffer>, crate::Error> {
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    auto_stream.req.rrd.clear();
+    auto_stream.req.rrd.body.extend_from_copyable_slice(b"done")?;
+    Ok(auto_stream.req.into_response(StatusCode::Ok))
+  }
+
+  async fn manual(
+    _: (),
+    _: ManualServerStreamTokio<(), Http2Buffer, (), OwnedWriteHalf>,
+  ) -> Result<(), crate::Error> {
+    Ok(())
+  }
+
+  let uri = _uri();
+  let (host, port) = uri.hostname_with_implied_port();
+  let addr = alloc::format!("{host}:{port}");
+  let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+  let server_jh = tokio::spawn(async move {
+    OptionedServer::http2_tokio(
+      &addr,
+      auto,
+      || Ok(((), Http2Buffer::new(Xorshift64::from(simple_seed())), Http2Params::default())),
+      |err: crate::Error| panic!("{err:?}"),
+      manual,
+      |_, _, _, _| Ok(((), OperationMode::Auto)),
+      (
+        async move {
+          let _rslt = shutdown_rx.await;
+        },
+        Duration::from_secs(5),
+      ),
+      || Ok(((), ReqResBuffer::empty())),
+      (|| Ok(()), |_| {}, |_, stream| async move { Ok(stream.into_split()) }),
+    )
+    .await
+    .unwrap();
+  });
+  tokio::time::sleep(Duration::from_millis(100)).await;
+  let mut stream = TcpStream::connect(uri.hostname_with_implied_port()).await.unwrap();
+  stream.write_all(PREFACE).await.unwrap();
+  stream.write_all(&[0, 0, 0, 4, 0, 0, 0, 0, 0]).await.unwrap();
+  stream.write_all(&[0, 0, 3, 1, 5, 0, 0, 0, 1, 130, 134, 132]).await.unwrap();
+  tokio::time::sleep(Duration::from_millis(50)).await;
+  shutdown_tx.send(()).unwrap();
+  // The request takes 200ms, as such, the server must still be waiting for it.
+  tokio::time::sleep(Duration::from_millis(50)).await;
+  assert!(!server_jh.is_finished());
+  let (mut has_go_away, mut has_refused) = (false, false);
+  loop {
+    let mut header = [0; 9];
+    let _ = stream.read_exact(&mut header).await.unwrap();
+    let [a, b, c, ty, _, d, e, f, g] = header;
+    let mut payload = alloc::vec![0; usize::from_be_bytes([0, 0, 0, 0, 0, a, b, c])];
+    let _ = stream.read_exact(&mut payload).await.unwrap();
+    match (ty, u32::from_be_bytes([d, e, f, g])) {
+      (0, 1) => {
+        assert_eq!(payload, b"done");
+        break;
+      }
+      (3, 3) => {
+        assert_eq!(payload, [0, 0, 0, 7]);
+        has_refused = true;
+      }
+      (7, 0) if !has_go_away => {
+        assert_eq!(payload, [0, 0, 0, 1, 0, 0, 0, 0]);
+        has_go_away = true;
+        stream.write_all(&[0, 0, 3, 1, 5, 0, 0, 0, 3, 130, 134, 132]).await.unwrap();
+      }
+      _ => {}
+    }
+  }
+  assert!(has_go_away && has_refused);
+  tokio::time::timeout(Duration::from_secs(1), server_jh).await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn h2c() {
+  let uri = _uri();
+  let _server_jh = server(&uri, core::future::pending());
+  tokio::time::sleep(Duration::from_millis(100)).await;
+  http1(&uri).await;
+  prior_knowledge(&uri).await;
+  upgrade(&uri).await;
+}
+
+#[tokio::test]
+async fn h2c_graceful_shutdown() {
+  let uri = _uri();
+  let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+  let server_jh = server(&uri, async move {
+    let _rslt = shutdown_rx.await;
+  });
+  tokio::time::sleep(Duration::from_millis(100)).await;
+  let mut stream = TcpStream::connect(uri.hostname_with_implied_port()).await.unwrap();
+  stream.write_all(b"GET /hello HTTP/1.1\r\nhost: localhost\r\n\r\n").await.unwrap();
+  let mut bytes = [0; 42];
+  let _ = stream.read_exact(&mut bytes).await.unwrap();
+  shutdown_tx.send(()).unwrap();
+  // The idle keep-alive connection is closed once the drain timeout expires.
+  tokio::time::timeout(Duration::from_secs(1), server_jh).await.unwrap().unwrap();
+  assert_eq!(stream.read(&mut bytes).await.unwrap(), 0);
+}
+
+async fn http1(uri: &UriString) {
+  let mut stream = TcpStream::connect(uri.hostname_with_implied_port()).await.unwrap();
+  stream.write_all(b"GET /hello HTTP/1.1\r\nhost: localhost\r\n\r\n").await.unwrap();
+  let mut bytes = [0; 42];
+  let _ = stream.read_exact(&mut bytes).await.unwrap();
+  assert_eq!(from_utf8_basic(&bytes).unwrap(), "HTTP/1.1 200 \r\ncontent-length: 6\r\n\r\n/hello");
+}
+
+async fn prior_knowledge(uri: &UriString) {
+  let (frame_reader, mut http2) = Http2Tokio::connect(
+    Http2Buffer::new(Xorshift64::from(simple_seed())),
+    Http2Params::default(),
+    TcpStream::connect(uri.hostname_with_implied_port()).await.unwrap().into_split(),
+  )
+  .await
+  .unwrap();
+  let _jh = tokio::spawn(frame_reader);
+  let mut rrb = ReqResBuffer::empty();
+  rrb.uri.reset(format_args!("{}/hello", uri.as_str())).unwrap();
+  let mut stream = http2.stream().await.unwrap();
+  let _ = stream.send_req(rrb.as_http2_request(Method::Get), &rrb.uri.to_ref()).await.unwrap();
+  let (hrs, res_rrb) = stream.recv_res(ReqResBuffer::empty()).await.unwrap();
+  assert!(matches!(hrs, Http2RecvStatus::Eos(StatusCode::Ok)));
+  assert_eq!(res_rrb.body.as_slice(), b"/hello");
+}
+
+async fn upgrade(uri: &UriString) {
+  let mut stream = TcpStream::connect(uri.hostname_with_implied_port()).await.unwrap();
+  stream
+    .write_all(
+      b"GET /hello HTTP/1.1\r\nhost: localhost\r\nconnection: Upgrade, HTTP2-Settings\r\n\
+      upgrade: h2c\r\nhttp2-settings: AAMAAABk\r\n\r\n",
+    )
+    .await
+    .unwrap();
+  let mut bytes = [0; 71];
+  let _ = stream.read_exact(&mut bytes).await.unwrap();
+  assert_eq!(
+    from_utf8_basic(&bytes).unwrap(),
+    "HTTP/1.1 101 Switching Protocols\r\nconnection: Upgrade\r\nupgrade: h2c\r\n\r\n"
+  );
+  stream.write_all(PREFACE).await.unwrap();
+  stream.write_all(&[0, 0, 0, 4, 0, 0, 0, 0, 0]).await.unwrap();
+  loop {
+    let mut header = [0; 9];
+    let _ = stream.read_exact(&mut header).await.unwrap();
+    let [a, b, c, ty, _, d, e, f, g] = header;
+    let mut payload = alloc::vec![0; usize::from_be_bytes([0, 0, 0, 0, 0, a, b, c])];
+    let _ = stream.read_exact(&mut payload).await.unwrap();
+    if ty == 0 && u32::from_be_bytes([d, e, f, g]) == 1 {
+      assert_eq!(payload, b"/hello");
+      break;
+    }
+  }
+}
+
+fn server(uri: &UriString, shutdown: impl Future<Output = ()> + Send + 'static) -> JoinHandle<()> {
+  async fn auto(
+    _: (),
+    mut auto_stream: AutoStream<(), ()>,
+  ) -> Result<Response<ReqResBuffer>, crate::Error> {
+    let path = String::from(auto_stream.req.rrd.uri.path());
+    auto_stream.req.rrd.clear();
+    auto_stream.req.rrd.body.extend_from_copyable_slice(path.as_bytes())?;
+    Ok(auto_stream.req.into_response(StatusCode::Ok))
+  }
+
+  async fn manual(
+    _: (),
+    _: ManualServerStreamTokio<(), Http2Buffer, (), OwnedWriteHalf>,
+  ) -> Result<(), crate::Error> {
+    Ok(())
+  }
+
+  let (host, port) = uri.hostname_with_implied_port();
+  let addr = alloc::format!("{host}:{port}");
+  tokio::spawn(async move {
+    OptionedServer::h2c_tokio(
+      &addr,
+      auto,
+      || Ok(((), Http1Buffer::new(), Http1Params::default())),
+      |err: crate::Error| panic!("{err:?}"),
+      || Ok((Http2Buffer::new(Xorshift64::from(simple_seed())), Http2Params::default())),
+      manual,
+      |_, _, _: Request<&mut ReqResBuffer>, _| Ok(((), OperationMode::Auto)),
+      (shutdown, Duration::from_millis(100)),
+      || Ok(((), ReqResBuffer::empty())),
+    )
+    .await
+    .unwrap();
+  })
+}
@@ -137,7 +137,7 @@ impl<'bytes> PercentDecode<'bytes> {
       }
       return Ok(false);
     };
-    let local_bytes = self.bytes.get(..idx).unwrap_or_default();
+    let local_bytes = self.bytes.get(..idx.wrapping_sub(1)).unwrap_or_default();
     let _ = vector.extend_from_copyable_slices([local_bytes, &[decoded_byte][..]])?;
     while let [byte, rest @ ..] = bytes {
       bytes = rest;
@@ -187,3 +187,25 @@ fn percent_encode_byte(byte: u8) -> &'static str {
   // SAFETY: TABLE is ascii-only
   unsafe { str::from_utf8_unchecked(slice) }
 }
+
+#[cfg(test)]
+mod tests {
+  use crate::{http::PercentDecode, misc::Vector};
+
+  #[test]
+  fn decode_keeps_bytes_before_the_first_escape() {
+    let mut vector = Vector::new();
+    assert!(PercentDecode::new(b"a%20b").decode(&mut vector).unwrap());
+    assert_eq!(vector.as_slice(), b"a b");
+    vector.clear();
+    assert!(PercentDecode::new(b"%41bc%2Fd%").decode(&mut vector).unwrap());
+    assert_eq!(vector.as_slice(), b"Abc/d%");
+  }
+
+  #[test]
+  fn decode_ignores_unmodified_bytes() {
+    let mut vector = Vector::new();
+    assert!(!PercentDecode::new(b"abc%zz").decode(&mut vector).unwrap());
+    assert!(vector.is_empty());
+  }
+}
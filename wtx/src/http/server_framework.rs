@@ -3,50 +3,108 @@
 #[macro_use]
 mod macros;
 
+#[cfg(feature = "tracing")]
+mod access_log_middleware;
 mod arguments;
+#[cfg(all(feature = "argon2", feature = "base64"))]
+mod basic_auth_middleware;
+#[cfg(feature = "argon2")]
+mod basic_auth_store;
+#[cfg(all(feature = "flate2", feature = "std"))]
+mod compression_middleware;
 mod conn_aux;
+mod content_security_policy;
 mod cors_middleware;
 mod endpoint;
 pub(crate) mod endpoint_node;
-mod methods;
+mod entity_tag;
+mod etag_middleware;
+#[cfg(all(feature = "base64", feature = "hmac", feature = "serde_json", feature = "sha2"))]
+mod jwt_middleware;
+mod manual_stream_sender;
+pub(crate) mod methods;
 mod middleware;
+#[cfg(feature = "serde_json")]
+mod openapi;
 mod path_params;
+#[cfg(feature = "std")]
+mod rate_limit_middleware;
+#[cfg(feature = "std")]
+mod rate_limit_store;
 mod redirect;
 mod res_finalizer;
 mod route_match;
-mod router;
+pub(crate) mod router;
+mod scoped;
+mod security_headers_middleware;
 mod server_framework_builder;
 mod server_framework_error;
 mod state;
+#[cfg(feature = "static-files")]
+mod static_files;
 mod stream_aux;
+#[cfg(all(feature = "nightly", feature = "tokio"))]
+mod test_client;
 #[cfg(feature = "nightly")]
 mod tokio;
 
 use crate::{
-  http::{conn_params::ConnParams, AutoStream, OperationMode, ReqResBuffer, Response},
+  http::{conn_params::ConnParams, AutoStream, Method, OperationMode, ReqResBuffer, Response},
   misc::{Arc, ArrayVector},
 };
+#[cfg(feature = "tracing")]
+pub use access_log_middleware::{AccessLogAux, AccessLogMiddleware};
 pub use arguments::*;
+#[cfg(all(feature = "argon2", feature = "base64"))]
+pub use basic_auth_middleware::BasicAuthMiddleware;
+#[cfg(feature = "argon2")]
+pub use basic_auth_store::BasicAuthStore;
+#[cfg(all(feature = "flate2", feature = "std"))]
+pub use compression_middleware::{CompressionMiddleware, ContentEncoding};
 pub use conn_aux::ConnAux;
+pub use content_security_policy::{ContentSecurityPolicy, CspDirective, CspNonce, CspSource};
 pub use cors_middleware::CorsMiddleware;
 pub use endpoint::Endpoint;
 pub use endpoint_node::EndpointNode;
+pub use etag_middleware::EtagMiddleware;
+#[cfg(all(feature = "base64", feature = "hmac", feature = "serde_json", feature = "sha2"))]
+pub use jwt_middleware::{JwtAlgorithm, JwtMiddleware};
+pub use manual_stream_sender::ManualStreamSender;
 pub use methods::{
+  delete::{delete, Delete},
+  dispatch::{methods, MethodEndpoint, Methods},
+  form::{form, Form},
   get::{get, Get},
   json::{json, Json},
+  patch::{patch, Patch},
   post::{post, Post},
+  put::{put, Put},
+  sse::{sse, Sse, SseEvent, SseSender},
+  streaming::{streaming, BodyReader, BodyStream, BodyWriter, Streaming},
   web_socket::{web_socket, WebSocket},
 };
 pub use middleware::Middleware;
+#[cfg(feature = "serde_json")]
+pub use openapi::{documented, Documented, OpenApi, OpenApiNode, OpenApiOperation};
 pub use path_params::PathParams;
+#[cfg(feature = "std")]
+pub use rate_limit_middleware::{RateLimit, RateLimitMiddleware};
+#[cfg(feature = "std")]
+pub use rate_limit_store::{MemoryRateLimitStore, RateLimitStore};
 pub use redirect::Redirect;
 pub use res_finalizer::ResFinalizer;
 pub use route_match::RouteMatch;
 pub use router::Router;
+pub use scoped::{scoped, Scoped};
+pub use security_headers_middleware::SecurityHeadersMiddleware;
 pub use server_framework_builder::ServerFrameworkBuilder;
 pub use server_framework_error::ServerFrameworkError;
 pub use state::{State, StateClean, StateGeneric};
+#[cfg(feature = "static-files")]
+pub use static_files::{static_files, StaticFiles};
 pub use stream_aux::StreamAux;
+#[cfg(all(feature = "nightly", feature = "tokio"))]
+pub use test_client::{TestClient, TestReqBuilder, TestServerStream};
 
 /// Server
 #[derive(Debug)]
@@ -62,6 +120,7 @@ where
   E: From<crate::Error>,
   EN: EndpointNode<CA, E, S, SA>,
   M: Middleware<CA, E, SA>,
+  S: ManualStreamSender,
   SA: StreamAux,
 {
   #[inline]
@@ -78,6 +137,11 @@ where
       stream_aux: SA::stream_aux(cb())?,
     };
     let status_code = router.auto(&mut router_auto_stream, (0, &headers_aux)).await?;
+    // Bodies of `HEAD` responses are only discarded here so that middlewares, like the ETag one,
+    // still see the representation that a `GET` request would receive.
+    if router_auto_stream.req.method == Method::Head {
+      router_auto_stream.req.rrd.body.clear();
+    }
     Ok(Response {
       rrd: router_auto_stream.req.rrd,
       status_code,
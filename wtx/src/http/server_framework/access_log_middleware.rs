@@ -0,0 +1,283 @@
+use crate::{
+  http::{
+    server_framework::Middleware, Header, Method, ReqResBuffer, Request, Response, StatusCode,
+    Version,
+  },
+  misc::{from_utf8_basic, ArrayString, GenericTime, LeaseMut, Rng},
+};
+use alloc::string::String;
+use core::{fmt::Write, marker::PhantomData, net::IpAddr, ops::ControlFlow};
+
+const MAX_ID_LEN: usize = 64;
+
+/// Correlates requests through an identifier and emits one `INFO` event per request with the
+/// method, path, status code, latency, peer, body sizes and HTTP version.
+///
+/// The identifier is taken from the request header, when it is valid, or generated otherwise. It
+/// is then attached to a `request` span that encloses the endpoint as well as the event and echoed
+/// in the response.
+///
+/// Generated identifiers are drawn from the `RNG` instance of the connection auxiliary, which
+/// should be a cryptographically secure generator like `ChaCha20Rng::from_entropy()` to avoid
+/// collisions.
+///
+/// Place this middleware before the others of a tuple to also log and identify requests halted by
+/// them. Requests whose endpoints return an error emit the event without a status code.
+#[derive(Debug)]
+pub struct AccessLogMiddleware<RNG> {
+  header: &'static str,
+  rng: PhantomData<fn() -> RNG>,
+}
+
+impl<RNG> AccessLogMiddleware<RNG> {
+  /// Reads and writes identifiers through the `x-request-id` header.
+  #[inline]
+  pub const fn new() -> Self {
+    Self { header: "x-request-id", rng: PhantomData }
+  }
+
+  /// Lowercase name of the header that contains the identifier.
+  #[inline]
+  #[must_use]
+  pub const fn header(mut self, elem: &'static str) -> Self {
+    self.header = elem;
+    self
+  }
+}
+
+impl<RNG> Default for AccessLogMiddleware<RNG> {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// Request data retained by [`AccessLogMiddleware`] until the response is available.
+#[derive(Debug)]
+pub struct AccessLogAux {
+  id: ArrayString<MAX_ID_LEN>,
+  is_pending: bool,
+  method: Method,
+  path: String,
+  peer: Option<IpAddr>,
+  req_body_len: usize,
+  span: tracing::Span,
+  start: GenericTime,
+  version: Version,
+}
+
+impl AccessLogAux {
+  /// Identifier of the current request
+  #[inline]
+  pub fn id(&self) -> &str {
+    self.id.as_str()
+  }
+
+  fn log(&mut self, res_body_len: usize, status: Option<u16>) {
+    self.is_pending = false;
+    let latency = self.start.elapsed().unwrap_or_default();
+    self.span.in_scope(|| {
+      tracing::info!(
+        latency_us = latency.as_micros(),
+        method = self.method.strings().custom[0],
+        path = self.path.as_str(),
+        peer = ?self.peer,
+        req_body_len = self.req_body_len,
+        res_body_len,
+        status,
+        version = ?self.version,
+        "access"
+      );
+    });
+  }
+}
+
+impl Drop for AccessLogAux {
+  #[inline]
+  fn drop(&mut self) {
+    if self.is_pending {
+      self.log(0, None);
+    }
+  }
+}
+
+impl<CA, E, RNG, SA> Middleware<CA, E, SA> for AccessLogMiddleware<RNG>
+where
+  CA: LeaseMut<RNG>,
+  E: From<crate::Error>,
+  RNG: Rng,
+{
+  type Aux = AccessLogAux;
+
+  #[inline]
+  fn aux(&self) -> Self::Aux {
+    AccessLogAux {
+      id: ArrayString::new(),
+      is_pending: false,
+      method: Method::Get,
+      path: String::new(),
+      peer: None,
+      req_body_len: 0,
+      span: tracing::Span::none(),
+      start: GenericTime::now(),
+      version: Version::Http2,
+    }
+  }
+
+  #[inline]
+  async fn req(
+    &self,
+    ca: &mut CA,
+    mw_aux: &mut Self::Aux,
+    peer: IpAddr,
+    req: &mut Request<ReqResBuffer>,
+    _: &mut SA,
+  ) -> Result<ControlFlow<StatusCode, ()>, E> {
+    mw_aux.start = GenericTime::now();
+    let incoming = req.rrd.headers.get_by_name(self.header.as_bytes()).and_then(|header| {
+      let is_valid = !header.value.is_empty()
+        && header.value.len() <= MAX_ID_LEN
+        && header.value.iter().all(|el| el.is_ascii_graphic());
+      if is_valid {
+        from_utf8_basic(header.value).ok()
+      } else {
+        None
+      }
+    });
+    mw_aux.id.clear();
+    if let Some(elem) = incoming {
+      mw_aux.id.push_str(elem).map_err(crate::Error::from)?;
+    } else {
+      for byte in ca.lease_mut().u8_16() {
+        write!(mw_aux.id, "{byte:02x}").map_err(crate::Error::from)?;
+      }
+    }
+    mw_aux.method = req.method;
+    mw_aux.path.clear();
+    mw_aux.path.push_str(req.rrd.uri.path());
+    mw_aux.peer = Some(peer);
+    mw_aux.req_body_len = req.rrd.body.len();
+    mw_aux.span = tracing::info_span!("request", request_id = mw_aux.id.as_str());
+    mw_aux.version = req.version;
+    mw_aux.is_pending = true;
+    Ok(ControlFlow::Continue(()))
+  }
+
+  #[inline]
+  async fn res(
+    &self,
+    _: &mut CA,
+    mw_aux: &mut Self::Aux,
+    res: Response<&mut ReqResBuffer>,
+    _: &mut SA,
+  ) -> Result<ControlFlow<StatusCode, ()>, E> {
+    res
+      .rrd
+      .headers
+      .push_from_iter(Header::from_name_and_value(self.header, [mw_aux.id.as_str().as_bytes()]))?;
+    mw_aux.log(res.rrd.body.len(), Some(res.status_code.into()));
+    Ok(ControlFlow::Continue(()))
+  }
+
+  #[inline]
+  fn span(&self, mw_aux: &Self::Aux) -> Option<tracing::Span> {
+    Some(mw_aux.span.clone())
+  }
+}
+
+#[cfg(all(feature = "_async-tests", test))]
+mod tests {
+  use crate::{
+    http::{
+      server_framework::{middleware::tests::Halt, AccessLogMiddleware, Middleware},
+      Header, Method, ReqResBuffer, Request, Response, StatusCode,
+    },
+    misc::{simple_seed, Lease, LeaseMut, Xorshift64},
+  };
+  use core::{
+    net::{IpAddr, Ipv4Addr},
+    ops::ControlFlow,
+  };
+
+  struct ConnAux(Xorshift64);
+
+  impl Lease<Xorshift64> for ConnAux {
+    fn lease(&self) -> &Xorshift64 {
+      &self.0
+    }
+  }
+
+  impl LeaseMut<Xorshift64> for ConnAux {
+    fn lease_mut(&mut self) -> &mut Xorshift64 {
+      &mut self.0
+    }
+  }
+
+  #[tokio::test]
+  async fn halted_requests_are_identified() {
+    let mws = (AccessLogMiddleware::<Xorshift64>::new(), Halt(StatusCode::Forbidden));
+    let mut ca = ConnAux(Xorshift64::from(simple_seed()));
+    let mut req = Request::http2(Method::Get, ReqResBuffer::empty());
+    let mut aux = Middleware::<ConnAux, crate::Error, ()>::aux(&mws);
+    let peer = IpAddr::V4(Ipv4Addr::LOCALHOST);
+    let cf = Middleware::<ConnAux, crate::Error, ()>::req(
+      &mws,
+      &mut ca,
+      &mut aux,
+      peer,
+      &mut req,
+      &mut (),
+    )
+    .await
+    .unwrap();
+    assert_eq!(cf, ControlFlow::Break(StatusCode::Forbidden));
+    assert!(Middleware::<ConnAux, crate::Error, ()>::span(&mws, &aux).is_some());
+    let header = req.rrd.headers.get_by_name(b"x-request-id").unwrap();
+    assert_eq!(header.value, aux.0.id().as_bytes());
+  }
+
+  #[tokio::test]
+  async fn request_id() {
+    let mw = AccessLogMiddleware::<Xorshift64>::new();
+    let mut ca = ConnAux(Xorshift64::from(simple_seed()));
+    let mut generated = alloc::vec::Vec::new();
+    for (incoming, is_kept) in
+      [(None, false), (Some("abc-123"), true), (Some("a b"), false), (None, false)]
+    {
+      let mut rrb = ReqResBuffer::empty();
+      if let Some(elem) = incoming {
+        rrb
+          .headers
+          .push_from_iter(Header::from_name_and_value("x-request-id", [elem.as_bytes()]))
+          .unwrap();
+      }
+      let mut req = Request::http2(Method::Post, rrb);
+      let mut aux = Middleware::<ConnAux, crate::Error, ()>::aux(&mw);
+      let peer = IpAddr::V4(Ipv4Addr::LOCALHOST);
+      let _ = Middleware::<ConnAux, crate::Error, ()>::req(
+        &mw,
+        &mut ca,
+        &mut aux,
+        peer,
+        &mut req,
+        &mut (),
+      )
+      .await
+      .unwrap();
+      if is_kept {
+        assert_eq!(Some(aux.id()), incoming);
+      } else {
+        assert_eq!(aux.id().len(), 32);
+        assert!(!generated.contains(&alloc::string::String::from(aux.id())));
+        generated.push(aux.id().into());
+      }
+      req.rrd.clear();
+      let res = Response::http2(&mut req.rrd, StatusCode::Ok);
+      let _ = Middleware::<ConnAux, crate::Error, ()>::res(&mw, &mut ca, &mut aux, res, &mut ())
+        .await
+        .unwrap();
+      let header = req.rrd.headers.get_by_name(b"x-request-id").unwrap();
+      assert_eq!(header.value, aux.id().as_bytes());
+    }
+  }
+}
@@ -1,19 +1,39 @@
+#[cfg(feature = "serde")]
+mod form_url_encoded;
+mod multipart;
 mod path_owned;
 mod path_str;
+#[cfg(feature = "serde")]
+mod query;
+mod query_str;
+mod req_part;
 #[cfg(feature = "serde_json")]
 mod serde_json;
+mod typed_headers;
+#[cfg(feature = "serde")]
+mod url_encoded;
 
 use crate::{
   http::{server_framework::RouteMatch, HttpError},
-  misc::{bytes_split1, UriString},
+  misc::{bytes_split1, str_split_once1, UriString},
 };
+#[cfg(feature = "serde")]
+pub use form_url_encoded::FormUrlEncoded;
+pub use multipart::{Multipart, MultipartPart};
 pub use path_owned::PathOwned;
 pub use path_str::PathStr;
+#[cfg(feature = "serde")]
+pub use query::Query;
+pub use query_str::QueryStr;
+pub use req_part::ReqPart;
 #[cfg(feature = "serde_json")]
 pub use serde_json::SerdeJson;
+#[cfg(feature = "base64")]
+pub use typed_headers::Authorization;
+pub use typed_headers::{Accept, ConnInfo, ContentType, Cookies, Host, UserAgent};
 
 #[inline]
-fn manage_path<'uri>(
+pub(crate) fn manage_path<'uri>(
   path_defs: (u8, &[RouteMatch]),
   uri: &'uri UriString,
 ) -> crate::Result<&'uri str> {
@@ -41,3 +61,9 @@ fn manage_path<'uri>(
   };
   fun().ok_or_else(|| crate::Error::from(HttpError::UriMismatch))
 }
+
+#[inline]
+fn manage_query(uri: &UriString) -> &str {
+  let query = uri.query_and_fragment().strip_prefix('?').unwrap_or_default();
+  str_split_once1(query, b'#').map_or(query, |el| el.0)
+}
@@ -0,0 +1,87 @@
+use crate::{
+  http::{
+    server_framework::{
+      arguments::url_encoded::deserialize_url_encoded, Endpoint, ResFinalizer, RouteMatch,
+      StateGeneric,
+    },
+    AutoStream, ReqResBuffer, StatusCode,
+  },
+  misc::{from_utf8_basic, FnFut, FnFutWrapper},
+};
+use serde::de::DeserializeOwned;
+
+/// Body of type `application/x-www-form-urlencoded` deserialized into an owned type.
+///
+/// Bodies that can't be deserialized are answered with `400 Bad Request` without calling the
+/// endpoint. Should be used along [`crate::http::server_framework::form`].
+#[derive(Debug)]
+pub struct FormUrlEncoded<T>(
+  /// Arbitrary type
+  pub T,
+);
+
+impl<CA, E, F, RES, S, SA, T> Endpoint<CA, E, S, SA> for FnFutWrapper<(FormUrlEncoded<T>,), F>
+where
+  E: From<crate::Error>,
+  F: FnFut<(FormUrlEncoded<T>,), Result = RES>,
+  RES: ResFinalizer<E>,
+  T: DeserializeOwned,
+{
+  #[inline]
+  async fn auto(
+    &self,
+    auto_stream: &mut AutoStream<CA, SA>,
+    _: (u8, &[RouteMatch]),
+  ) -> Result<StatusCode, E> {
+    let rslt = deserialize_body(&auto_stream.req.rrd);
+    auto_stream.req.rrd.clear();
+    let Some(elem) = rslt else {
+      return Ok(StatusCode::BadRequest);
+    };
+    self.0.call((FormUrlEncoded(elem),)).await.finalize_response(&mut auto_stream.req)
+  }
+}
+
+impl<CA, E, F, RES, S, SA, T, const CLEAN: bool> Endpoint<CA, E, S, SA>
+  for FnFutWrapper<(StateGeneric<'_, CA, SA, ReqResBuffer, CLEAN>, FormUrlEncoded<T>), F>
+where
+  E: From<crate::Error>,
+  F: for<'any> FnFut<
+    (StateGeneric<'any, CA, SA, ReqResBuffer, CLEAN>, FormUrlEncoded<T>),
+    Result = RES,
+  >,
+  RES: ResFinalizer<E>,
+  T: DeserializeOwned,
+{
+  #[inline]
+  async fn auto(
+    &self,
+    auto_stream: &mut AutoStream<CA, SA>,
+    _: (u8, &[RouteMatch]),
+  ) -> Result<StatusCode, E> {
+    let Some(elem) = deserialize_body(&auto_stream.req.rrd) else {
+      auto_stream.req.rrd.clear();
+      return Ok(StatusCode::BadRequest);
+    };
+    self
+      .0
+      .call((
+        StateGeneric::new(
+          &mut auto_stream.conn_aux,
+          &mut auto_stream.stream_aux,
+          &mut auto_stream.req,
+        ),
+        FormUrlEncoded(elem),
+      ))
+      .await
+      .finalize_response(&mut auto_stream.req)
+  }
+}
+
+#[inline]
+fn deserialize_body<T>(rrb: &ReqResBuffer) -> Option<T>
+where
+  T: DeserializeOwned,
+{
+  deserialize_url_encoded(from_utf8_basic(&rrb.body).ok()?).ok()
+}
@@ -0,0 +1,320 @@
+use crate::{
+  http::{
+    server_framework::{Endpoint, ResFinalizer, RouteMatch, ServerFrameworkError, StateGeneric},
+    AutoStream, Header, Headers, KnownHeaderName, ReqResBuffer, StatusCode,
+  },
+  misc::{bytes_split1, from_utf8_basic, FnFut, FnFutWrapper, Vector},
+};
+use core::mem;
+
+const MAX_BOUNDARY_LEN: usize = 70;
+
+/// Parts of a `multipart/form-data` body.
+///
+/// * `MAX_PART_LEN`: Maximum length of the contents of each part.
+/// * `MAX_LEN`: Maximum length of the sum of the contents of all parts.
+///
+/// Malformed bodies are answered with `400 Bad Request` and bodies that exceed the above limits
+/// are answered with `413 Content Too Large`, in both cases without calling the endpoint. Should
+/// be used along [`crate::http::server_framework::form`].
+#[derive(Debug)]
+pub struct Multipart<
+  'body,
+  const MAX_PART_LEN: usize = { 1024 * 1024 },
+  const MAX_LEN: usize = { 4 * 1024 * 1024 },
+> {
+  /// All parts in the order they were received.
+  pub parts: Vector<MultipartPart<'body>>,
+}
+
+impl<'body, const MAX_PART_LEN: usize, const MAX_LEN: usize>
+  Multipart<'body, MAX_PART_LEN, MAX_LEN>
+{
+  /// Parses `body` using the boundary contained in the `Content-Type` header of `headers`.
+  #[inline]
+  pub fn parse(body: &'body [u8], headers: &Headers) -> crate::Result<Self> {
+    let boundary = headers
+      .get_by_name(KnownHeaderName::ContentType.into())
+      .and_then(|header| boundary(header.value))
+      .ok_or(invalid())?;
+    let mut delimiter = Vector::new();
+    let _ = delimiter.extend_from_copyable_slices([&b"\r\n--"[..], boundary])?;
+    let first_delimiter = delimiter.get(2..).unwrap_or_default();
+    let mut rest = if let Some(elem) = body.strip_prefix(first_delimiter) {
+      elem
+    } else {
+      let idx = find(body, &delimiter).ok_or(invalid())?;
+      body.get(idx.wrapping_add(delimiter.len())..).unwrap_or_default()
+    };
+    let mut len: usize = 0;
+    let mut parts = Vector::new();
+    loop {
+      rest = trim_transport_padding(rest);
+      if rest.starts_with(b"--") {
+        break;
+      }
+      rest = rest.strip_prefix(b"\r\n").ok_or(invalid())?;
+      let (headers_bytes, after_headers) = if let Some(elem) = rest.strip_prefix(b"\r\n") {
+        (&[][..], elem)
+      } else {
+        let idx = find(rest, b"\r\n\r\n").ok_or(invalid())?;
+        (rest.get(..idx).unwrap_or_default(), rest.get(idx.wrapping_add(4)..).unwrap_or_default())
+      };
+      let idx = find(after_headers, &delimiter).ok_or(invalid())?;
+      let contents = after_headers.get(..idx).unwrap_or_default();
+      len = len.wrapping_add(contents.len());
+      if contents.len() > MAX_PART_LEN || len > MAX_LEN {
+        return Err(ServerFrameworkError::MultipartIsTooLarge.into());
+      }
+      parts.push(MultipartPart::new(contents, headers_bytes)?)?;
+      rest = after_headers.get(idx.wrapping_add(delimiter.len())..).unwrap_or_default();
+    }
+    Ok(Self { parts })
+  }
+}
+
+impl<CA, E, F, RES, S, SA, const MAX_PART_LEN: usize, const MAX_LEN: usize> Endpoint<CA, E, S, SA>
+  for FnFutWrapper<(Multipart<'_, MAX_PART_LEN, MAX_LEN>,), F>
+where
+  E: From<crate::Error>,
+  F: for<'any> FnFut<(Multipart<'any, MAX_PART_LEN, MAX_LEN>,), Result = RES>,
+  RES: ResFinalizer<E>,
+{
+  #[inline]
+  async fn auto(
+    &self,
+    auto_stream: &mut AutoStream<CA, SA>,
+    _: (u8, &[RouteMatch]),
+  ) -> Result<StatusCode, E> {
+    let body = mem::take(&mut auto_stream.req.rrd.body);
+    let rslt = Multipart::parse(&body, &auto_stream.req.rrd.headers);
+    auto_stream.req.rrd.clear();
+    let multipart = match rslt {
+      Err(err) => return manage_err(err),
+      Ok(elem) => elem,
+    };
+    self.0.call((multipart,)).await.finalize_response(&mut auto_stream.req)
+  }
+}
+
+impl<CA, E, F, RES, S, SA, const CLEAN: bool, const MAX_PART_LEN: usize, const MAX_LEN: usize>
+  Endpoint<CA, E, S, SA>
+  for FnFutWrapper<
+    (StateGeneric<'_, CA, SA, ReqResBuffer, CLEAN>, Multipart<'_, MAX_PART_LEN, MAX_LEN>),
+    F,
+  >
+where
+  E: From<crate::Error>,
+  F: for<'any> FnFut<
+    (StateGeneric<'any, CA, SA, ReqResBuffer, CLEAN>, Multipart<'any, MAX_PART_LEN, MAX_LEN>),
+    Result = RES,
+  >,
+  RES: ResFinalizer<E>,
+{
+  #[inline]
+  async fn auto(
+    &self,
+    auto_stream: &mut AutoStream<CA, SA>,
+    _: (u8, &[RouteMatch]),
+  ) -> Result<StatusCode, E> {
+    let body = mem::take(&mut auto_stream.req.rrd.body);
+    let multipart = match Multipart::parse(&body, &auto_stream.req.rrd.headers) {
+      Err(err) => {
+        auto_stream.req.rrd.clear();
+        return manage_err(err);
+      }
+      Ok(elem) => elem,
+    };
+    self
+      .0
+      .call((
+        StateGeneric::new(
+          &mut auto_stream.conn_aux,
+          &mut auto_stream.stream_aux,
+          &mut auto_stream.req,
+        ),
+        multipart,
+      ))
+      .await
+      .finalize_response(&mut auto_stream.req)
+  }
+}
+
+/// Individual part of a `multipart/form-data` body.
+#[derive(Debug)]
+pub struct MultipartPart<'body> {
+  /// Raw contents
+  pub contents: &'body [u8],
+  /// `filename` parameter of the `Content-Disposition` header, if any.
+  pub filename: Option<&'body str>,
+  /// All headers, including `Content-Disposition`.
+  pub headers: Vector<Header<'body, &'body [u8]>>,
+  /// `name` parameter of the `Content-Disposition` header.
+  pub name: &'body str,
+}
+
+impl<'body> MultipartPart<'body> {
+  fn new(contents: &'body [u8], headers_bytes: &'body [u8]) -> crate::Result<Self> {
+    let mut filename = None;
+    let mut headers = Vector::new();
+    let mut name = None;
+    if !headers_bytes.is_empty() {
+      for line in lines(headers_bytes) {
+        let idx = line.iter().position(|el| *el == b':').ok_or(invalid())?;
+        let (header_name, header_value) = line.split_at(idx);
+        let header_name = from_utf8_basic(header_name.trim_ascii()).map_err(|_err| invalid())?;
+        let header_value = header_value.get(1..).unwrap_or_default().trim_ascii();
+        let content_disposition: &[u8] = KnownHeaderName::ContentDisposition.into();
+        if header_name.as_bytes().eq_ignore_ascii_case(content_disposition) {
+          let mut params = bytes_split1(header_value, b';');
+          if params.next().map(<[u8]>::trim_ascii) != Some(b"form-data") {
+            return Err(invalid());
+          }
+          for param in params {
+            let Some((param_name, param_value)) = param_name_and_value(param) else {
+              continue;
+            };
+            if param_name.eq_ignore_ascii_case(b"filename") {
+              filename = Some(param_value);
+            } else if param_name.eq_ignore_ascii_case(b"name") {
+              name = Some(param_value);
+            }
+          }
+        }
+        headers.push(Header {
+          is_sensitive: false,
+          is_trailer: false,
+          name: header_name,
+          value: header_value,
+        })?;
+      }
+    }
+    Ok(Self { contents, filename, headers, name: name.ok_or(invalid())? })
+  }
+}
+
+fn boundary(content_type: &[u8]) -> Option<&[u8]> {
+  let mut params = bytes_split1(content_type, b';');
+  if !params.next()?.trim_ascii().eq_ignore_ascii_case(b"multipart/form-data") {
+    return None;
+  }
+  let boundary = params.find_map(|param| {
+    let (name, value) = param_name_and_value(param)?;
+    name.eq_ignore_ascii_case(b"boundary").then_some(value.as_bytes())
+  })?;
+  (1..=MAX_BOUNDARY_LEN).contains(&boundary.len()).then_some(boundary)
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+  haystack.windows(needle.len()).position(|el| el == needle)
+}
+
+fn invalid() -> crate::Error {
+  ServerFrameworkError::InvalidMultipart.into()
+}
+
+fn lines(bytes: &[u8]) -> impl Iterator<Item = &[u8]> {
+  let mut rest = Some(bytes);
+  core::iter::from_fn(move || {
+    let local_rest = rest?;
+    if let Some(idx) = find(local_rest, b"\r\n") {
+      rest = local_rest.get(idx.wrapping_add(2)..);
+      local_rest.get(..idx)
+    } else {
+      rest = None;
+      Some(local_rest)
+    }
+  })
+}
+
+fn manage_err<E>(err: crate::Error) -> Result<StatusCode, E>
+where
+  E: From<crate::Error>,
+{
+  match err {
+    crate::Error::ServerFrameworkError(ServerFrameworkError::InvalidMultipart) => {
+      Ok(StatusCode::BadRequest)
+    }
+    crate::Error::ServerFrameworkError(ServerFrameworkError::MultipartIsTooLarge) => {
+      Ok(StatusCode::PayloadTooLarge)
+    }
+    _ => Err(err.into()),
+  }
+}
+
+/// Parameters like `name="value"` or `name=value`.
+fn param_name_and_value(param: &[u8]) -> Option<(&[u8], &str)> {
+  let idx = param.iter().position(|el| *el == b'=')?;
+  let (name, value) = param.split_at(idx);
+  let value = value.get(1..)?.trim_ascii();
+  let unquoted = value.strip_prefix(b"\"").and_then(|el| el.strip_suffix(b"\"")).unwrap_or(value);
+  Some((name.trim_ascii(), from_utf8_basic(unquoted).ok()?))
+}
+
+fn trim_transport_padding(bytes: &[u8]) -> &[u8] {
+  let idx = bytes.iter().position(|el| *el != b' ' && *el != b'\t').unwrap_or(bytes.len());
+  bytes.get(idx..).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::http::{
+    server_framework::{Multipart, ServerFrameworkError},
+    Header, Headers, KnownHeaderName,
+  };
+
+  #[test]
+  fn parses_parts() {
+    let body = b"preamble\r\n--abc\r\n\
+      content-disposition: form-data; name=\"title\"\r\n\r\nfoo\r\n\
+      --abc\r\n\
+      Content-Disposition: form-data; name=\"file\"; filename=\"a b.txt\"\r\n\
+      Content-Type: text/plain\r\n\r\nline\r\nline\r\n\
+      --abc--\r\nepilogue";
+    let headers = headers("multipart/form-data; boundary=\"abc\"");
+    let multipart = <Multipart<'_>>::parse(body, &headers).unwrap();
+    assert_eq!(multipart.parts.len(), 2);
+    assert_eq!(multipart.parts[0].contents, b"foo");
+    assert_eq!(multipart.parts[0].filename, None);
+    assert_eq!(multipart.parts[0].name, "title");
+    assert_eq!(multipart.parts[1].contents, b"line\r\nline");
+    assert_eq!(multipart.parts[1].filename, Some("a b.txt"));
+    assert_eq!(multipart.parts[1].headers[1].name, "Content-Type");
+    assert_eq!(multipart.parts[1].headers[1].value, b"text/plain");
+    assert_eq!(multipart.parts[1].name, "file");
+  }
+
+  #[test]
+  fn rejects_invalid_or_large_bodies() {
+    let body = b"--abc\r\ncontent-disposition: form-data; name=\"a\"\r\n\r\n12345\r\n--abc--";
+    let headers = headers("multipart/form-data; boundary=abc");
+    assert!(<Multipart<'_>>::parse(body, &headers).is_ok());
+    assert!(matches!(
+      <Multipart<'_, 4>>::parse(body, &headers),
+      Err(crate::Error::ServerFrameworkError(ServerFrameworkError::MultipartIsTooLarge))
+    ));
+    assert!(matches!(
+      <Multipart<'_, 8, 4>>::parse(body, &headers),
+      Err(crate::Error::ServerFrameworkError(ServerFrameworkError::MultipartIsTooLarge))
+    ));
+    assert!(matches!(
+      <Multipart<'_>>::parse(body, &self::headers("multipart/form-data")),
+      Err(crate::Error::ServerFrameworkError(ServerFrameworkError::InvalidMultipart))
+    ));
+    assert!(matches!(
+      <Multipart<'_>>::parse(&body[..body.len() - 7], &headers),
+      Err(crate::Error::ServerFrameworkError(ServerFrameworkError::InvalidMultipart))
+    ));
+  }
+
+  fn headers(content_type: &str) -> Headers {
+    let mut headers = Headers::new();
+    headers
+      .push_from_iter(Header::from_name_and_value(
+        KnownHeaderName::ContentType.into(),
+        [content_type.as_bytes()],
+      ))
+      .unwrap();
+    headers
+  }
+}
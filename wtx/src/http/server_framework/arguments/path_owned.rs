@@ -28,9 +28,9 @@ where
     auto_stream: &mut AutoStream<CA, SA>,
     path_defs: (u8, &[RouteMatch]),
   ) -> Result<StatusCode, E> {
-    auto_stream.req.rrd.clear();
     let path = manage_path(path_defs, &auto_stream.req.rrd.uri).map_err(From::from)?;
     let path_owned = PathOwned(P::from_str(path).map_err(Into::into)?);
+    auto_stream.req.rrd.clear();
     self.0.call((path_owned,)).await.finalize_response(&mut auto_stream.req)
   }
 }
@@ -66,3 +66,43 @@ where
       .finalize_response(&mut auto_stream.req)
   }
 }
+
+#[cfg(all(feature = "_async-tests", test))]
+mod tests {
+  use crate::{
+    http::{
+      server_framework::{Endpoint, PathOwned, RouteMatch},
+      AutoStream, Method, OperationMode, ReqResBuffer, Request, StatusCode,
+    },
+    misc::FnFutWrapper,
+  };
+  use core::net::{IpAddr, Ipv4Addr};
+
+  #[tokio::test]
+  async fn extracts_path_before_clearing_the_request() {
+    async fn endpoint(PathOwned(id): PathOwned<u32>) -> crate::Result<StatusCode> {
+      Ok(if id == 123 { StatusCode::Ok } else { StatusCode::NotFound })
+    }
+
+    let mut rrb = ReqResBuffer::empty();
+    rrb.uri.reset(format_args!("http://localhost/user/123")).unwrap();
+    rrb.body.extend_from_copyable_slice(b"body").unwrap();
+    let mut auto_stream = AutoStream {
+      conn_aux: (),
+      peer: IpAddr::V4(Ipv4Addr::LOCALHOST),
+      protocol: None,
+      req: Request::http2(Method::Get, rrb),
+      stream_aux: (),
+    };
+    let path_defs = [RouteMatch::new(0, OperationMode::Auto, "/user/{id}")];
+    let status_code = Endpoint::<(), crate::Error, (), ()>::auto(
+      &FnFutWrapper::from(endpoint),
+      &mut auto_stream,
+      (0, &path_defs),
+    )
+    .await
+    .unwrap();
+    assert_eq!(status_code, StatusCode::Ok);
+    assert!(auto_stream.req.rrd.body.is_empty());
+  }
+}
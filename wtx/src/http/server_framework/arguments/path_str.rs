@@ -28,7 +28,8 @@ where
     auto_stream: &mut AutoStream<CA, SA>,
     path_defs: (u8, &[RouteMatch]),
   ) -> Result<StatusCode, E> {
-    auto_stream.req.rrd.clear();
+    auto_stream.req.rrd.body.clear();
+    auto_stream.req.rrd.headers.clear();
     let path = manage_path(path_defs, &auto_stream.req.rrd.uri).map_err(From::from)?;
     self.0.call((PathStr(path),)).await.finalize_response(&mut auto_stream.req)
   }
@@ -67,3 +68,43 @@ where
       .finalize_response(&mut auto_stream.req)
   }
 }
+
+#[cfg(all(feature = "_async-tests", test))]
+mod tests {
+  use crate::{
+    http::{
+      server_framework::{Endpoint, PathStr, RouteMatch},
+      AutoStream, Method, OperationMode, ReqResBuffer, Request, StatusCode,
+    },
+    misc::FnFutWrapper,
+  };
+  use core::net::{IpAddr, Ipv4Addr};
+
+  #[tokio::test]
+  async fn extracts_path_before_clearing_the_request() {
+    async fn endpoint(PathStr(name): PathStr<'_>) -> crate::Result<StatusCode> {
+      Ok(if name == "foo" { StatusCode::Ok } else { StatusCode::NotFound })
+    }
+
+    let mut rrb = ReqResBuffer::empty();
+    rrb.uri.reset(format_args!("http://localhost/file/foo")).unwrap();
+    rrb.body.extend_from_copyable_slice(b"body").unwrap();
+    let mut auto_stream = AutoStream {
+      conn_aux: (),
+      peer: IpAddr::V4(Ipv4Addr::LOCALHOST),
+      protocol: None,
+      req: Request::http2(Method::Get, rrb),
+      stream_aux: (),
+    };
+    let path_defs = [RouteMatch::new(0, OperationMode::Auto, "/file/{name}")];
+    let status_code = Endpoint::<(), crate::Error, (), ()>::auto(
+      &FnFutWrapper::from(endpoint),
+      &mut auto_stream,
+      (0, &path_defs),
+    )
+    .await
+    .unwrap();
+    assert_eq!(status_code, StatusCode::Ok);
+    assert!(auto_stream.req.rrd.body.is_empty());
+  }
+}
@@ -0,0 +1,120 @@
+use crate::{
+  http::{
+    server_framework::{
+      arguments::{manage_query, url_encoded::deserialize_url_encoded},
+      Endpoint, ResFinalizer, RouteMatch, StateGeneric,
+    },
+    AutoStream, ReqResBuffer, StatusCode,
+  },
+  misc::{FnFut, FnFutWrapper},
+};
+use serde::de::DeserializeOwned;
+
+/// Percent-decoded URI query deserialized into an owned type.
+///
+/// Queries that can't be deserialized are answered with `400 Bad Request` without calling the
+/// endpoint.
+#[derive(Debug)]
+pub struct Query<T>(
+  /// Arbitrary type
+  pub T,
+);
+
+impl<CA, E, F, RES, S, SA, T> Endpoint<CA, E, S, SA> for FnFutWrapper<(Query<T>,), F>
+where
+  E: From<crate::Error>,
+  F: FnFut<(Query<T>,), Result = RES>,
+  RES: ResFinalizer<E>,
+  T: DeserializeOwned,
+{
+  #[inline]
+  async fn auto(
+    &self,
+    auto_stream: &mut AutoStream<CA, SA>,
+    _: (u8, &[RouteMatch]),
+  ) -> Result<StatusCode, E> {
+    let rslt = deserialize_url_encoded(manage_query(&auto_stream.req.rrd.uri));
+    auto_stream.req.rrd.clear();
+    let Ok(elem) = rslt else {
+      return Ok(StatusCode::BadRequest);
+    };
+    self.0.call((Query(elem),)).await.finalize_response(&mut auto_stream.req)
+  }
+}
+
+impl<CA, E, F, RES, S, SA, T, const CLEAN: bool> Endpoint<CA, E, S, SA>
+  for FnFutWrapper<(StateGeneric<'_, CA, SA, ReqResBuffer, CLEAN>, Query<T>), F>
+where
+  E: From<crate::Error>,
+  F: for<'any> FnFut<(StateGeneric<'any, CA, SA, ReqResBuffer, CLEAN>, Query<T>), Result = RES>,
+  RES: ResFinalizer<E>,
+  T: DeserializeOwned,
+{
+  #[inline]
+  async fn auto(
+    &self,
+    auto_stream: &mut AutoStream<CA, SA>,
+    _: (u8, &[RouteMatch]),
+  ) -> Result<StatusCode, E> {
+    let Ok(elem) = deserialize_url_encoded(manage_query(&auto_stream.req.rrd.uri)) else {
+      auto_stream.req.rrd.clear();
+      return Ok(StatusCode::BadRequest);
+    };
+    self
+      .0
+      .call((
+        StateGeneric::new(
+          &mut auto_stream.conn_aux,
+          &mut auto_stream.stream_aux,
+          &mut auto_stream.req,
+        ),
+        Query(elem),
+      ))
+      .await
+      .finalize_response(&mut auto_stream.req)
+  }
+}
+
+#[cfg(all(feature = "_async-tests", test))]
+mod tests {
+  use crate::{
+    http::{
+      server_framework::{Endpoint, Query},
+      AutoStream, Method, ReqResBuffer, Request, StatusCode,
+    },
+    misc::FnFutWrapper,
+  };
+  use core::net::{IpAddr, Ipv4Addr};
+
+  #[derive(serde::Deserialize)]
+  struct Page {
+    page: u32,
+  }
+
+  #[tokio::test]
+  async fn rejects_queries_that_cannot_be_deserialized() {
+    async fn endpoint(Query(elem): Query<Page>) -> crate::Result<StatusCode> {
+      Ok(if elem.page == 2 { StatusCode::Ok } else { StatusCode::NotFound })
+    }
+
+    for (query, expected) in [("page=2", StatusCode::Ok), ("page=two", StatusCode::BadRequest)] {
+      let mut rrb = ReqResBuffer::empty();
+      rrb.uri.reset(format_args!("http://localhost/items?{query}")).unwrap();
+      let mut auto_stream = AutoStream {
+        conn_aux: (),
+        peer: IpAddr::V4(Ipv4Addr::LOCALHOST),
+        protocol: None,
+        req: Request::http2(Method::Get, rrb),
+        stream_aux: (),
+      };
+      let status_code = Endpoint::<(), crate::Error, (), ()>::auto(
+        &FnFutWrapper::from(endpoint),
+        &mut auto_stream,
+        (0, &[]),
+      )
+      .await
+      .unwrap();
+      assert_eq!(status_code, expected);
+    }
+  }
+}
@@ -0,0 +1,71 @@
+use crate::{
+  http::{
+    server_framework::{
+      arguments::{manage_query, RouteMatch},
+      Endpoint, ResFinalizer, StateGeneric,
+    },
+    AutoStream, Headers, Request, StatusCode,
+  },
+  misc::{FnFut, FnFutWrapper, Vector},
+};
+
+/// String reference extracted from a URI query without the leading `?`.
+///
+/// Contents are not percent-decoded, see [`crate::http::PercentDecode`].
+#[derive(Debug)]
+pub struct QueryStr<'uri>(
+  /// Arbitrary type
+  pub &'uri str,
+);
+
+impl<CA, E, F, RES, S, SA> Endpoint<CA, E, S, SA> for FnFutWrapper<(QueryStr<'_>,), F>
+where
+  E: From<crate::Error>,
+  F: for<'any> FnFut<(QueryStr<'any>,), Result = RES>,
+  RES: ResFinalizer<E>,
+{
+  #[inline]
+  async fn auto(
+    &self,
+    auto_stream: &mut AutoStream<CA, SA>,
+    _: (u8, &[RouteMatch]),
+  ) -> Result<StatusCode, E> {
+    let (body, headers, uri) = auto_stream.req.rrd.parts_mut();
+    body.clear();
+    headers.clear();
+    self.0.call((QueryStr(manage_query(uri)),)).await.finalize_response(&mut auto_stream.req)
+  }
+}
+
+impl<CA, E, F, RES, S, SA, const CLEAN: bool> Endpoint<CA, E, S, SA>
+  for FnFutWrapper<
+    (StateGeneric<'_, CA, SA, (&mut Vector<u8>, &mut Headers), CLEAN>, QueryStr<'_>),
+    F,
+  >
+where
+  E: From<crate::Error>,
+  F: for<'any> FnFut<
+    (StateGeneric<'any, CA, SA, (&'any mut Vector<u8>, &'any mut Headers), CLEAN>, QueryStr<'any>),
+    Result = RES,
+  >,
+  RES: ResFinalizer<E>,
+{
+  #[inline]
+  async fn auto(
+    &self,
+    auto_stream: &mut AutoStream<CA, SA>,
+    _: (u8, &[RouteMatch]),
+  ) -> Result<StatusCode, E> {
+    let (body, headers, uri) = auto_stream.req.rrd.parts_mut();
+    let mut new_req =
+      Request::_new(auto_stream.req.method, (body, headers), auto_stream.req.version);
+    self
+      .0
+      .call((
+        StateGeneric::new(&mut auto_stream.conn_aux, &mut auto_stream.stream_aux, &mut new_req),
+        QueryStr(manage_query(uri)),
+      ))
+      .await
+      .finalize_response(&mut auto_stream.req)
+  }
+}
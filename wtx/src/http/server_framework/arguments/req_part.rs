@@ -0,0 +1,191 @@
+use crate::{
+  http::{
+    server_framework::{Endpoint, ResFinalizer, RouteMatch, ServerFrameworkError, StateGeneric},
+    AutoStream, Protocol, ReqResBuffer, Request, StatusCode,
+  },
+  misc::{FnFut, FnFutWrapper},
+};
+use core::net::IpAddr;
+
+/// Endpoint argument built from the headers of a request or from its connection.
+///
+/// Endpoints can declare up to three of these arguments, optionally preceded by a
+/// [`StateGeneric`]. Absent data is answered by [`ReqPart::missing`] and malformed data is
+/// answered with `400 Bad Request`, in both cases without calling the endpoint. Wrap an argument
+/// in an [`Option`] to accept requests where it is absent.
+pub trait ReqPart: Sized {
+  /// Fills the already cleared response of requests that don't contain the expected data.
+  /// Defaults to an empty `400 Bad Request`.
+  #[inline]
+  fn missing(_: &mut ReqResBuffer) -> crate::Result<StatusCode> {
+    Ok(StatusCode::BadRequest)
+  }
+
+  /// Returns `None` if `req` doesn't contain the expected data.
+  fn from_req_part(
+    peer: IpAddr,
+    protocol: Option<Protocol>,
+    req: &Request<ReqResBuffer>,
+  ) -> crate::Result<Option<Self>>;
+}
+
+impl<T> ReqPart for Option<T>
+where
+  T: ReqPart,
+{
+  #[inline]
+  fn from_req_part(
+    peer: IpAddr,
+    protocol: Option<Protocol>,
+    req: &Request<ReqResBuffer>,
+  ) -> crate::Result<Option<Self>> {
+    Ok(Some(T::from_req_part(peer, protocol, req)?))
+  }
+}
+
+macro_rules! impl_req_parts {
+  ($( [$($T:ident($N:tt))+] )+) => {
+    $(
+      impl<CA, E, F, RES, S, SA, $($T,)+> Endpoint<CA, E, S, SA> for FnFutWrapper<($($T,)+), F>
+      where
+        E: From<crate::Error>,
+        F: FnFut<($($T,)+), Result = RES>,
+        RES: ResFinalizer<E>,
+        $($T: ReqPart,)+
+      {
+        #[inline]
+        async fn auto(
+          &self,
+          auto_stream: &mut AutoStream<CA, SA>,
+          _: (u8, &[RouteMatch]),
+        ) -> Result<StatusCode, E> {
+          let args = ($(
+            match req_part::<E, $T>(auto_stream.peer, auto_stream.protocol, &mut auto_stream.req)? {
+              Ok(elem) => elem,
+              Err(status_code) => return Ok(status_code),
+            },
+          )+);
+          auto_stream.req.rrd.clear();
+          self.0.call(args).await.finalize_response(&mut auto_stream.req)
+        }
+      }
+
+      impl<CA, E, F, RES, S, SA, $($T,)+ const CLEAN: bool> Endpoint<CA, E, S, SA>
+        for FnFutWrapper<(StateGeneric<'_, CA, SA, ReqResBuffer, CLEAN>, $($T,)+), F>
+      where
+        E: From<crate::Error>,
+        F: for<'any> FnFut<
+          (StateGeneric<'any, CA, SA, ReqResBuffer, CLEAN>, $($T,)+),
+          Result = RES
+        >,
+        RES: ResFinalizer<E>,
+        $($T: ReqPart,)+
+      {
+        #[inline]
+        async fn auto(
+          &self,
+          auto_stream: &mut AutoStream<CA, SA>,
+          _: (u8, &[RouteMatch]),
+        ) -> Result<StatusCode, E> {
+          let args = ($(
+            match req_part::<E, $T>(auto_stream.peer, auto_stream.protocol, &mut auto_stream.req)? {
+              Ok(elem) => elem,
+              Err(status_code) => return Ok(status_code),
+            },
+          )+);
+          self
+            .0
+            .call((
+              StateGeneric::new(
+                &mut auto_stream.conn_aux,
+                &mut auto_stream.stream_aux,
+                &mut auto_stream.req,
+              ),
+              $(args.$N,)+
+            ))
+            .await
+            .finalize_response(&mut auto_stream.req)
+        }
+      }
+    )+
+  };
+}
+
+impl_req_parts! {
+  [A(0)]
+  [A(0) B(1)]
+  [A(0) B(1) C(2)]
+}
+
+#[inline]
+fn req_part<E, T>(
+  peer: IpAddr,
+  protocol: Option<Protocol>,
+  req: &mut Request<ReqResBuffer>,
+) -> Result<Result<T, StatusCode>, E>
+where
+  E: From<crate::Error>,
+  T: ReqPart,
+{
+  match T::from_req_part(peer, protocol, req) {
+    Ok(Some(elem)) => Ok(Ok(elem)),
+    Ok(None) => {
+      req.rrd.clear();
+      Ok(Err(T::missing(&mut req.rrd)?))
+    }
+    Err(crate::Error::ServerFrameworkError(ServerFrameworkError::InvalidHeader)) => {
+      req.rrd.clear();
+      Ok(Err(StatusCode::BadRequest))
+    }
+    Err(err) => Err(err.into()),
+  }
+}
+
+#[cfg(all(feature = "_async-tests", feature = "base64", feature = "nightly", test))]
+mod tests {
+  use crate::{
+    http::{
+      server_framework::{get, Authorization, ConnInfo, Router, ServerFrameworkBuilder},
+      KnownHeaderName, StatusCode,
+    },
+    misc::{simple_seed, Xorshift64},
+  };
+
+  #[tokio::test]
+  async fn req_parts() {
+    async fn endpoint(auth: Authorization, conn_info: ConnInfo) -> crate::Result<StatusCode> {
+      assert!(conn_info.peer.is_loopback());
+      Ok(if matches!(auth, Authorization::Bearer(token) if token == "abc") {
+        StatusCode::Ok
+      } else {
+        StatusCode::Forbidden
+      })
+    }
+
+    let router = Router::paths(crate::paths!(("/auth", get(endpoint)))).unwrap();
+    let client = ServerFrameworkBuilder::new(router)
+      .without_aux()
+      .test_client(Xorshift64::from(simple_seed()), |err: crate::Error| panic!("{err:?}"))
+      .await
+      .unwrap();
+    for (header, status_code) in [
+      (None, StatusCode::Unauthorized),
+      (Some("Bearer"), StatusCode::BadRequest),
+      (Some("Bearer xyz"), StatusCode::Forbidden),
+      (Some("Bearer abc"), StatusCode::Ok),
+    ] {
+      let mut builder = client.get("/auth");
+      if let Some(elem) = header {
+        builder = builder.header(KnownHeaderName::Authorization.into(), elem.as_bytes()).unwrap();
+      }
+      let res = builder.send().await.unwrap();
+      assert_eq!(res.status_code, status_code);
+      let www_authenticate = res.rrd.headers.get_by_name(KnownHeaderName::WwwAuthenticate.into());
+      assert_eq!(
+        www_authenticate.map(|el| el.value),
+        (status_code == StatusCode::Unauthorized)
+          .then_some(b"Basic realm=\"Restricted\", charset=\"UTF-8\", Bearer".as_slice())
+      );
+    }
+  }
+}
@@ -0,0 +1,371 @@
+#[cfg(feature = "base64")]
+use crate::http::{Header, StatusCode};
+use crate::{
+  http::{
+    server_framework::{ReqPart, ServerFrameworkError},
+    KnownHeaderName, Mime, Protocol, ReqResBuffer, Request, Version,
+  },
+  misc::{from_utf8_basic, str_split1, str_split_once1},
+};
+use alloc::string::String;
+#[cfg(feature = "base64")]
+use base64::{engine::general_purpose::STANDARD, Engine};
+use core::net::IpAddr;
+
+/// `Accept` header.
+#[derive(Debug)]
+pub struct Accept(
+  /// Raw value
+  pub String,
+);
+
+impl Accept {
+  /// If the most specific media range that matches `mime` has a non-zero quality.
+  #[inline]
+  pub fn accepts(&self, mime: Mime) -> bool {
+    let Some((ty, subtype)) = str_split_once1(mime.as_str(), b'/') else {
+      return false;
+    };
+    // Specificity and acceptance of the most specific range
+    let mut best: Option<(u8, bool)> = None;
+    for range in str_split1(&self.0, b',') {
+      let mut params = str_split1(range, b';');
+      let media_range = params.next().unwrap_or_default().trim();
+      let specificity = match str_split_once1(media_range, b'/') {
+        Some(("*", "*")) => 0,
+        Some((local_ty, "*")) if local_ty.eq_ignore_ascii_case(ty) => 1,
+        Some((local_ty, local_subtype))
+          if local_ty.eq_ignore_ascii_case(ty) && local_subtype.eq_ignore_ascii_case(subtype) =>
+        {
+          2
+        }
+        _ => continue,
+      };
+      if best.is_some_and(|(elem, _)| elem >= specificity) {
+        continue;
+      }
+      let has_zero_quality = params.any(|param| {
+        let Some((name, value)) = str_split_once1(param.trim(), b'=') else {
+          return false;
+        };
+        name.trim().eq_ignore_ascii_case("q") && value.trim().parse::<f32>().ok() == Some(0.0)
+      });
+      best = Some((specificity, !has_zero_quality));
+    }
+    best.is_some_and(|(_, is_accepted)| is_accepted)
+  }
+}
+
+impl ReqPart for Accept {
+  #[inline]
+  fn from_req_part(
+    _: IpAddr,
+    _: Option<Protocol>,
+    req: &Request<ReqResBuffer>,
+  ) -> crate::Result<Option<Self>> {
+    Ok(header_str(KnownHeaderName::Accept, req)?.map(|el| Self(el.into())))
+  }
+}
+
+/// `Authorization` header with the `Basic` or the `Bearer` scheme.
+///
+/// Absent headers are answered with `401 Unauthorized` and a `WWW-Authenticate` header that
+/// offers both schemes.
+#[cfg(feature = "base64")]
+#[derive(Debug)]
+pub enum Authorization {
+  /// Base64 decoded credentials
+  Basic {
+    /// Password
+    password: String,
+    /// User identifier
+    user: String,
+  },
+  /// Token
+  Bearer(String),
+}
+
+#[cfg(feature = "base64")]
+impl ReqPart for Authorization {
+  #[inline]
+  fn missing(rrb: &mut ReqResBuffer) -> crate::Result<StatusCode> {
+    rrb.headers.push_from_iter(Header::from_name_and_value(
+      KnownHeaderName::WwwAuthenticate.into(),
+      [b"Basic realm=\"Restricted\", charset=\"UTF-8\", Bearer".as_slice()],
+    ))?;
+    Ok(StatusCode::Unauthorized)
+  }
+
+  #[inline]
+  fn from_req_part(
+    _: IpAddr,
+    _: Option<Protocol>,
+    req: &Request<ReqResBuffer>,
+  ) -> crate::Result<Option<Self>> {
+    let Some(value) = header_str(KnownHeaderName::Authorization, req)? else {
+      return Ok(None);
+    };
+    let (scheme, credentials) = str_split_once1(value, b' ').ok_or_else(invalid)?;
+    let credentials = credentials.trim();
+    if scheme.eq_ignore_ascii_case("basic") {
+      let bytes = STANDARD.decode(credentials).map_err(|_err| invalid())?;
+      let decoded = from_utf8_basic(&bytes).map_err(|_err| invalid())?;
+      let (user, password) = str_split_once1(decoded, b':').ok_or_else(invalid)?;
+      Ok(Some(Self::Basic { password: password.into(), user: user.into() }))
+    } else if scheme.eq_ignore_ascii_case("bearer") && !credentials.is_empty() {
+      Ok(Some(Self::Bearer(credentials.into())))
+    } else {
+      Err(invalid())
+    }
+  }
+}
+
+/// Address of the remote peer and negotiated protocols.
+#[derive(Debug)]
+pub struct ConnInfo {
+  /// Remote peer address
+  pub peer: IpAddr,
+  /// See [Protocol].
+  pub protocol: Option<Protocol>,
+  /// HTTP version
+  pub version: Version,
+}
+
+impl ReqPart for ConnInfo {
+  #[inline]
+  fn from_req_part(
+    peer: IpAddr,
+    protocol: Option<Protocol>,
+    req: &Request<ReqResBuffer>,
+  ) -> crate::Result<Option<Self>> {
+    Ok(Some(Self { peer, protocol, version: req.version }))
+  }
+}
+
+/// `Content-Type` header.
+#[derive(Debug)]
+pub struct ContentType(
+  /// Raw value
+  pub String,
+);
+
+impl ContentType {
+  /// If the media type, without parameters, is equal to `mime`.
+  #[inline]
+  pub fn is(&self, mime: Mime) -> bool {
+    self.media_type().eq_ignore_ascii_case(mime.as_str())
+  }
+
+  /// Media type without parameters.
+  #[inline]
+  pub fn media_type(&self) -> &str {
+    str_split1(&self.0, b';').next().unwrap_or_default().trim()
+  }
+
+  /// Value of the parameter called `name`, if any. Quotes are removed.
+  #[inline]
+  pub fn param(&self, name: &str) -> Option<&str> {
+    str_split1(&self.0, b';').skip(1).find_map(|param| {
+      let (local_name, value) = str_split_once1(param.trim(), b'=')?;
+      if !local_name.trim().eq_ignore_ascii_case(name) {
+        return None;
+      }
+      let value = value.trim();
+      Some(value.strip_prefix('"').and_then(|el| el.strip_suffix('"')).unwrap_or(value))
+    })
+  }
+}
+
+impl ReqPart for ContentType {
+  #[inline]
+  fn from_req_part(
+    _: IpAddr,
+    _: Option<Protocol>,
+    req: &Request<ReqResBuffer>,
+  ) -> crate::Result<Option<Self>> {
+    Ok(header_str(KnownHeaderName::ContentType, req)?.map(|el| Self(el.into())))
+  }
+}
+
+/// `Cookie` header. Several headers, like the ones sent by HTTP/2 clients, are joined with `; `.
+#[derive(Debug)]
+pub struct Cookies(
+  /// Raw value
+  pub String,
+);
+
+impl Cookies {
+  /// Value of the first cookie called `name`, if any.
+  #[inline]
+  pub fn get(&self, name: &str) -> Option<&str> {
+    self.iter().find_map(|(local_name, value)| (local_name == name).then_some(value))
+  }
+
+  /// Name and value of all cookies.
+  #[inline]
+  pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+    str_split1(&self.0, b';').filter_map(|pair| {
+      let (name, value) = str_split_once1(pair.trim(), b'=')?;
+      Some((name.trim(), value.trim()))
+    })
+  }
+}
+
+impl ReqPart for Cookies {
+  #[inline]
+  fn from_req_part(
+    _: IpAddr,
+    _: Option<Protocol>,
+    req: &Request<ReqResBuffer>,
+  ) -> crate::Result<Option<Self>> {
+    let cookie_name = <&str>::from(KnownHeaderName::Cookie);
+    let mut rslt: Option<String> = None;
+    for header in req.rrd.headers.iter().filter(|el| el.name == cookie_name) {
+      let value = from_utf8_basic(header.value).map_err(|_err| invalid())?.trim();
+      match &mut rslt {
+        Some(elem) => {
+          elem.push_str("; ");
+          elem.push_str(value);
+        }
+        None => rslt = Some(value.into()),
+      }
+    }
+    Ok(rslt.map(Self))
+  }
+}
+
+/// `Host` header or the authority of the URI when the header is absent, which is the case of
+/// HTTP/2 requests.
+#[derive(Debug)]
+pub struct Host(
+  /// Host with an optional port
+  pub String,
+);
+
+impl ReqPart for Host {
+  #[inline]
+  fn from_req_part(
+    _: IpAddr,
+    _: Option<Protocol>,
+    req: &Request<ReqResBuffer>,
+  ) -> crate::Result<Option<Self>> {
+    if let Some(elem) = header_str(KnownHeaderName::Host, req)? {
+      return Ok(Some(Self(elem.into())));
+    }
+    let authority = req.rrd.uri.authority();
+    Ok(if authority.is_empty() { None } else { Some(Self(authority.into())) })
+  }
+}
+
+/// `User-Agent` header.
+#[derive(Debug)]
+pub struct UserAgent(
+  /// Raw value
+  pub String,
+);
+
+impl ReqPart for UserAgent {
+  #[inline]
+  fn from_req_part(
+    _: IpAddr,
+    _: Option<Protocol>,
+    req: &Request<ReqResBuffer>,
+  ) -> crate::Result<Option<Self>> {
+    Ok(header_str(KnownHeaderName::UserAgent, req)?.map(|el| Self(el.into())))
+  }
+}
+
+#[inline]
+fn header_str(name: KnownHeaderName, req: &Request<ReqResBuffer>) -> crate::Result<Option<&str>> {
+  let Some(header) = req.rrd.headers.get_by_name(name.into()) else {
+    return Ok(None);
+  };
+  Ok(Some(from_utf8_basic(header.value).map_err(|_err| invalid())?.trim()))
+}
+
+#[inline]
+fn invalid() -> crate::Error {
+  ServerFrameworkError::InvalidHeader.into()
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::http::{
+    server_framework::{Accept, ContentType, Cookies, ReqPart},
+    Header, KnownHeaderName, Method, Mime, ReqResBuffer, Request,
+  };
+  use alloc::vec::Vec;
+  use core::net::{IpAddr, Ipv4Addr};
+
+  #[test]
+  fn accept() {
+    let accept = parse::<Accept>(KnownHeaderName::Accept, "text/*, application/json;q=0").unwrap();
+    assert!(accept.accepts(Mime::Html));
+    assert!(!accept.accepts(Mime::Json));
+    assert!(!accept.accepts(Mime::Png));
+    let accept = parse::<Accept>(KnownHeaderName::Accept, "text/*, text/html;q=0").unwrap();
+    assert!(!accept.accepts(Mime::Html));
+    assert!(accept.accepts(Mime::Text));
+    let accept = parse::<Accept>(KnownHeaderName::Accept, "*/*;q=0, image/*").unwrap();
+    assert!(accept.accepts(Mime::Png));
+    assert!(!accept.accepts(Mime::Json));
+  }
+
+  #[cfg(feature = "base64")]
+  #[test]
+  fn authorization() {
+    use crate::http::server_framework::Authorization;
+
+    let basic = parse::<Authorization>(KnownHeaderName::Authorization, "Basic Zm9vOmJhcg==");
+    assert!(matches!(
+      basic,
+      Some(Authorization::Basic { password, user }) if password == "bar" && user == "foo"
+    ));
+    let bearer = parse::<Authorization>(KnownHeaderName::Authorization, "bearer abc");
+    assert!(matches!(bearer, Some(Authorization::Bearer(token)) if token == "abc"));
+  }
+
+  #[test]
+  fn content_type() {
+    let content_type = parse::<ContentType>(
+      KnownHeaderName::ContentType,
+      "multipart/form-data; boundary=\"abc\"; charset=utf-8",
+    )
+    .unwrap();
+    assert!(content_type.is(Mime::FormData));
+    assert_eq!(content_type.param("boundary"), Some("abc"));
+    assert_eq!(content_type.param("charset"), Some("utf-8"));
+    assert_eq!(content_type.param("name"), None);
+  }
+
+  #[test]
+  fn cookies() {
+    let cookies = parse::<Cookies>(KnownHeaderName::Cookie, "a=1; b=2").unwrap();
+    assert_eq!(cookies.get("b"), Some("2"));
+    assert_eq!(cookies.iter().collect::<Vec<_>>(), [("a", "1"), ("b", "2")]);
+    let cookies = parse_many::<Cookies>(KnownHeaderName::Cookie, &["a=1", "b=2; c=3"]).unwrap();
+    assert_eq!(cookies.iter().collect::<Vec<_>>(), [("a", "1"), ("b", "2"), ("c", "3")]);
+  }
+
+  fn parse<T>(name: KnownHeaderName, value: &str) -> Option<T>
+  where
+    T: ReqPart,
+  {
+    parse_many(name, &[value])
+  }
+
+  fn parse_many<T>(name: KnownHeaderName, values: &[&str]) -> Option<T>
+  where
+    T: ReqPart,
+  {
+    let mut rrb = ReqResBuffer::empty();
+    for value in values {
+      rrb
+        .headers
+        .push_from_iter(Header::from_name_and_value(name.into(), [value.as_bytes()]))
+        .unwrap();
+    }
+    let req = Request::http2(Method::Get, rrb);
+    T::from_req_part(IpAddr::V4(Ipv4Addr::LOCALHOST), None, &req).unwrap()
+  }
+}
@@ -0,0 +1,176 @@
+use crate::{
+  http::PercentDecode,
+  misc::{str_split1, str_split_once1, Vector},
+};
+use alloc::{borrow::Cow, string::String};
+use core::str::FromStr;
+use serde::{
+  de::{
+    value::{Error, MapDeserializer},
+    DeserializeOwned, Error as _, IntoDeserializer, Visitor,
+  },
+  forward_to_deserialize_any, Deserializer,
+};
+
+macro_rules! deserialize_parsed {
+  ($($method:ident => $visit:ident),* $(,)?) => {
+    $(
+      #[inline]
+      fn $method<V>(self, visitor: V) -> Result<V::Value, Error>
+      where
+        V: Visitor<'de>,
+      {
+        visitor.$visit(parse(&self.0)?)
+      }
+    )*
+  };
+}
+
+/// Deserializes `application/x-www-form-urlencoded` pairs like `a=1&b=2` into `T`.
+///
+/// Names and values are percent-decoded and `+` is treated as a space.
+#[inline]
+pub(crate) fn deserialize_url_encoded<T>(str: &str) -> Result<T, Error>
+where
+  T: DeserializeOwned,
+{
+  let mut pairs = Vector::new();
+  for pair in str_split1(str, b'&') {
+    if pair.is_empty() {
+      continue;
+    }
+    let (name, value) = str_split_once1(pair, b'=').unwrap_or((pair, ""));
+    pairs.push((decode(name)?, UrlEncodedValue(decode(value)?))).map_err(Error::custom)?;
+  }
+  T::deserialize(MapDeserializer::new(pairs.into_iter()))
+}
+
+fn decode(str: &str) -> Result<Cow<'_, str>, Error> {
+  let spaced = str.contains('+').then(|| str.replace('+', " "));
+  let input = spaced.as_deref().unwrap_or(str);
+  let mut vector = Vector::new();
+  if PercentDecode::new(input.as_bytes()).decode(&mut vector).map_err(Error::custom)? {
+    return String::from_utf8(vector.into()).map(Cow::Owned).map_err(Error::custom);
+  }
+  Ok(spaced.map_or(Cow::Borrowed(str), Cow::Owned))
+}
+
+fn parse<T>(str: &str) -> Result<T, Error>
+where
+  T: FromStr,
+{
+  str.parse().map_err(|_err| Error::custom(format_args!("`{str}` has an invalid format")))
+}
+
+struct UrlEncodedValue<'de>(Cow<'de, str>);
+
+impl<'de> Deserializer<'de> for UrlEncodedValue<'de> {
+  type Error = Error;
+
+  #[inline]
+  fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+  where
+    V: Visitor<'de>,
+  {
+    match self.0 {
+      Cow::Borrowed(elem) => visitor.visit_borrowed_str(elem),
+      Cow::Owned(elem) => visitor.visit_string(elem),
+    }
+  }
+
+  deserialize_parsed!(
+    deserialize_bool => visit_bool,
+    deserialize_f32 => visit_f32,
+    deserialize_f64 => visit_f64,
+    deserialize_i8 => visit_i8,
+    deserialize_i16 => visit_i16,
+    deserialize_i32 => visit_i32,
+    deserialize_i64 => visit_i64,
+    deserialize_u8 => visit_u8,
+    deserialize_u16 => visit_u16,
+    deserialize_u32 => visit_u32,
+    deserialize_u64 => visit_u64,
+  );
+
+  #[inline]
+  fn deserialize_enum<V>(
+    self,
+    _: &'static str,
+    _: &'static [&'static str],
+    visitor: V,
+  ) -> Result<V::Value, Error>
+  where
+    V: Visitor<'de>,
+  {
+    visitor.visit_enum(self.0.into_deserializer())
+  }
+
+  #[inline]
+  fn deserialize_newtype_struct<V>(self, _: &'static str, visitor: V) -> Result<V::Value, Error>
+  where
+    V: Visitor<'de>,
+  {
+    visitor.visit_newtype_struct(self)
+  }
+
+  #[inline]
+  fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+  where
+    V: Visitor<'de>,
+  {
+    visitor.visit_some(self)
+  }
+
+  forward_to_deserialize_any! {
+    char str string bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier
+    ignored_any i128 u128
+  }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for UrlEncodedValue<'de> {
+  type Deserializer = Self;
+
+  #[inline]
+  fn into_deserializer(self) -> Self::Deserializer {
+    self
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::http::server_framework::arguments::url_encoded::deserialize_url_encoded;
+  use alloc::string::String;
+
+  #[derive(Debug, PartialEq, serde::Deserialize)]
+  struct Foo {
+    name: String,
+    page: Option<u32>,
+    sort: Sort,
+  }
+
+  #[derive(Debug, PartialEq, serde::Deserialize)]
+  #[serde(rename_all = "lowercase")]
+  enum Sort {
+    Asc,
+    Desc,
+  }
+
+  #[test]
+  fn deserializes_decoded_pairs() {
+    assert_eq!(
+      deserialize_url_encoded::<Foo>("name=a+b%26c&page=2&sort=desc").unwrap(),
+      Foo { name: String::from("a b&c"), page: Some(2), sort: Sort::Desc }
+    );
+    assert_eq!(
+      deserialize_url_encoded::<Foo>("sort=asc&&name=").unwrap(),
+      Foo { name: String::new(), page: None, sort: Sort::Asc }
+    );
+  }
+
+  #[test]
+  fn rejects_invalid_pairs() {
+    assert!(deserialize_url_encoded::<Foo>("name=a&page=two&sort=asc").is_err());
+    assert!(deserialize_url_encoded::<Foo>("page=2&sort=asc").is_err());
+    assert!(deserialize_url_encoded::<Foo>("name=a&sort=up").is_err());
+  }
+}
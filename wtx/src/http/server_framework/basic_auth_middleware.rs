@@ -0,0 +1,181 @@
+use crate::{
+  http::{
+    server_framework::{Authorization, BasicAuthStore, Middleware, ReqPart},
+    Header, KnownHeaderName, ReqResBuffer, Request, Response, StatusCode,
+  },
+  misc::{password, LeaseMut},
+};
+use alloc::string::String;
+use core::{net::IpAddr, ops::ControlFlow};
+
+// Verified when the user doesn't exist so that the response time doesn't reveal valid names.
+const DUMMY_HASH: &str = "$argon2id$v=19$m=19456,t=2,p=1$AAAAAAAEICEAAAAQBAgGAQ$1gM4HoeoPt94fkK2h1C9ysopDU2Wm99CDyT6B9rD9lM";
+
+/// Verifies the credentials of the `Authorization` header with the `Basic` scheme against a
+/// [`BasicAuthStore`]. Authenticated users are written into the stream auxiliary.
+///
+/// Requests without valid credentials are answered with `401 Unauthorized` and a
+/// `WWW-Authenticate` header that contains `realm`.
+///
+/// Every request is verified with `Argon2id`, which intentionally consumes tens of milliseconds
+/// of CPU time. With the `tokio` feature the verification runs in
+/// `tokio::task::spawn_blocking`, otherwise it blocks the thread that polls the request. Clients
+/// should prefer exchanging credentials for a session or a token instead of sending them in
+/// every request.
+#[derive(Debug)]
+pub struct BasicAuthMiddleware<S> {
+  realm: &'static str,
+  store: S,
+}
+
+impl<S> BasicAuthMiddleware<S> {
+  /// New instance
+  #[inline]
+  pub const fn new(realm: &'static str, store: S) -> Self {
+    Self { realm, store }
+  }
+}
+
+impl<CA, E, S, SA> Middleware<CA, E, SA> for BasicAuthMiddleware<S>
+where
+  E: From<crate::Error>,
+  S: BasicAuthStore,
+  SA: LeaseMut<Option<S::User>>,
+{
+  type Aux = ();
+
+  #[inline]
+  fn aux(&self) -> Self::Aux {}
+
+  #[inline]
+  async fn req(
+    &self,
+    _: &mut CA,
+    _: &mut Self::Aux,
+    peer: IpAddr,
+    req: &mut Request<ReqResBuffer>,
+    stream_aux: &mut SA,
+  ) -> Result<ControlFlow<StatusCode, ()>, E> {
+    *stream_aux.lease_mut() = None;
+    let (password, user) = match Authorization::from_req_part(peer, None, req) {
+      Ok(Some(Authorization::Basic { password, user })) => (password, user),
+      Ok(_) | Err(crate::Error::ServerFrameworkError(_)) => {
+        return self.unauthorized(req).map_err(From::from)
+      }
+      Err(err) => return Err(err.into()),
+    };
+    let Some((elem, phc)) = self.store.user(&user).await? else {
+      let _ = verify(password, DUMMY_HASH.into()).await?;
+      return self.unauthorized(req).map_err(From::from);
+    };
+    if !verify(password, phc).await? {
+      return self.unauthorized(req).map_err(From::from);
+    }
+    *stream_aux.lease_mut() = Some(elem);
+    Ok(ControlFlow::Continue(()))
+  }
+
+  #[inline]
+  async fn res(
+    &self,
+    _: &mut CA,
+    _: &mut Self::Aux,
+    _: Response<&mut ReqResBuffer>,
+    _: &mut SA,
+  ) -> Result<ControlFlow<StatusCode, ()>, E> {
+    Ok(ControlFlow::Continue(()))
+  }
+}
+
+impl<S> BasicAuthMiddleware<S> {
+  #[inline]
+  fn unauthorized(
+    &self,
+    req: &mut Request<ReqResBuffer>,
+  ) -> crate::Result<ControlFlow<StatusCode, ()>> {
+    req.rrd.clear();
+    req.rrd.headers.push_from_fmt(Header::from_name_and_value(
+      KnownHeaderName::WwwAuthenticate.into(),
+      format_args!("Basic realm=\"{}\", charset=\"UTF-8\"", self.realm),
+    ))?;
+    Ok(ControlFlow::Break(StatusCode::Unauthorized))
+  }
+}
+
+#[inline]
+async fn verify(pwd: String, phc: String) -> crate::Result<bool> {
+  #[cfg(feature = "tokio")]
+  return tokio::task::spawn_blocking(move || password::verify(pwd.as_bytes(), &phc)).await?;
+  #[cfg(not(feature = "tokio"))]
+  return password::verify(pwd.as_bytes(), &phc);
+}
+
+#[cfg(all(feature = "_async-tests", test))]
+mod tests {
+  use crate::{
+    http::{
+      server_framework::{BasicAuthMiddleware, BasicAuthStore, Middleware},
+      Header, KnownHeaderName, Method, ReqResBuffer, Request, StatusCode,
+    },
+    misc::{password, Xorshift64},
+  };
+  use alloc::string::String;
+  use core::{
+    net::{IpAddr, Ipv4Addr},
+    ops::ControlFlow,
+  };
+
+  struct Store(String);
+
+  impl BasicAuthStore for Store {
+    type User = u32;
+
+    async fn user(&self, name: &str) -> crate::Result<Option<(Self::User, String)>> {
+      Ok((name == "foo").then(|| (1, self.0.clone())))
+    }
+  }
+
+  #[tokio::test]
+  async fn credentials() {
+    let phc = password::hash(b"bar", &mut Xorshift64::from(1)).unwrap();
+    let mw = BasicAuthMiddleware::new("wtx", Store(phc));
+    for (header, user) in [
+      (None, None),
+      (Some("Basic Zm9vOmJheg=="), None),
+      (Some("Basic YmF6OmJhcg=="), None),
+      (Some("Bearer abc"), None),
+      (Some("Basic Zm9vOmJhcg=="), Some(1)),
+    ] {
+      let mut rrb = ReqResBuffer::empty();
+      if let Some(elem) = header {
+        rrb
+          .headers
+          .push_from_iter(Header::from_name_and_value(
+            KnownHeaderName::Authorization.into(),
+            [elem.as_bytes()],
+          ))
+          .unwrap();
+      }
+      let mut req = Request::http2(Method::Get, rrb);
+      let mut stream_aux = None;
+      let rslt = Middleware::<(), crate::Error, _>::req(
+        &mw,
+        &mut (),
+        &mut (),
+        IpAddr::V4(Ipv4Addr::LOCALHOST),
+        &mut req,
+        &mut stream_aux,
+      )
+      .await
+      .unwrap();
+      assert_eq!(stream_aux, user);
+      if user.is_some() {
+        assert_eq!(rslt, ControlFlow::Continue(()));
+      } else {
+        assert_eq!(rslt, ControlFlow::Break(StatusCode::Unauthorized));
+        let value = req.rrd.headers.get_by_name(KnownHeaderName::WwwAuthenticate.into()).unwrap();
+        assert_eq!(value.value, b"Basic realm=\"wtx\", charset=\"UTF-8\"");
+      }
+    }
+  }
+}
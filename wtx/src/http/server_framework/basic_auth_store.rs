@@ -0,0 +1,25 @@
+use crate::misc::Arc;
+use alloc::string::String;
+use core::future::Future;
+
+/// Provides the credentials verified by [`crate::http::server_framework::BasicAuthMiddleware`].
+pub trait BasicAuthStore {
+  /// Authenticated user, which is written into the stream auxiliary.
+  type User;
+
+  /// Returns the user identified by `name` along with its password hash, which must be a PHC
+  /// string generated by [`crate::misc::password::hash`]. `None` means an unknown user.
+  fn user(&self, name: &str) -> impl Future<Output = crate::Result<Option<(Self::User, String)>>>;
+}
+
+impl<T> BasicAuthStore for Arc<T>
+where
+  T: BasicAuthStore,
+{
+  type User = T::User;
+
+  #[inline]
+  async fn user(&self, name: &str) -> crate::Result<Option<(Self::User, String)>> {
+    (**self).user(name).await
+  }
+}
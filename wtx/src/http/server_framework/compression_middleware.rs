@@ -0,0 +1,447 @@
+use crate::{
+  http::{
+    server_framework::Middleware, Header, Headers, KnownHeaderName, ReqResBuffer, Request,
+    Response, StatusCode,
+  },
+  misc::{bytes_split1, bytes_split_once1, Vector},
+};
+use core::{net::IpAddr, ops::ControlFlow};
+use flate2::{
+  read::{GzDecoder, GzEncoder, ZlibDecoder, ZlibEncoder},
+  Compression,
+};
+use std::io::Read;
+
+/// Compresses response bodies with `gzip` or `deflate` according to the `Accept-Encoding` header
+/// of the request as well as decompresses request bodies that contain a `Content-Encoding` header.
+///
+/// Responses that already have a `Content-Encoding` or a `Content-Range` header are left
+/// untouched, as well as responses whose `Content-Type` is already compressed, for example, most
+/// image, audio or video formats and archives.
+#[derive(Debug)]
+pub struct CompressionMiddleware {
+  level: u8,
+  max_decompressed_len: usize,
+  threshold: usize,
+}
+
+impl CompressionMiddleware {
+  /// * Compression level of 6.
+  /// * Decompressed request bodies can have at most 4 MiB.
+  /// * Only response bodies with 1024 bytes or more are compressed.
+  #[inline]
+  pub const fn new() -> Self {
+    Self { level: 6, max_decompressed_len: 4 * 1024 * 1024, threshold: 1024 }
+  }
+
+  /// A scale from 0 to 9 where 0 means "no compression" and 9 means "take as long as you'd like".
+  /// Values greater than 9 are treated as 9.
+  #[inline]
+  #[must_use]
+  pub fn level(mut self, elem: u8) -> Self {
+    self.level = elem.min(9);
+    self
+  }
+
+  /// Requests whose decompressed bodies are greater than `elem` are answered with
+  /// `413 Payload Too Large`.
+  #[inline]
+  #[must_use]
+  pub fn max_decompressed_len(mut self, elem: usize) -> Self {
+    self.max_decompressed_len = elem;
+    self
+  }
+
+  /// Response bodies with less than `elem` bytes are sent as is.
+  #[inline]
+  #[must_use]
+  pub fn threshold(mut self, elem: usize) -> Self {
+    self.threshold = elem;
+    self
+  }
+
+  #[inline]
+  fn compress(&self, body: &mut Vector<u8>, encoding: ContentEncoding) -> crate::Result<()> {
+    let level = Compression::new(self.level.into());
+    let mut vec = alloc::vec::Vec::with_capacity(body.len() / 2);
+    let _ = match encoding {
+      ContentEncoding::Deflate => ZlibEncoder::new(body.as_slice(), level).read_to_end(&mut vec)?,
+      ContentEncoding::Gzip => GzEncoder::new(body.as_slice(), level).read_to_end(&mut vec)?,
+    };
+    *body = Vector::from_vec(vec);
+    Ok(())
+  }
+
+  /// Returns `None` if the decompressed body is greater than the allowed maximum length.
+  #[inline]
+  fn decompress(
+    &self,
+    body: &mut Vector<u8>,
+    encoding: ContentEncoding,
+  ) -> crate::Result<Option<()>> {
+    let limit = u64::try_from(self.max_decompressed_len).unwrap_or(u64::MAX).saturating_add(1);
+    let mut vec = alloc::vec::Vec::new();
+    let _ = match encoding {
+      ContentEncoding::Deflate => {
+        ZlibDecoder::new(body.as_slice()).take(limit).read_to_end(&mut vec)?
+      }
+      ContentEncoding::Gzip => GzDecoder::new(body.as_slice()).take(limit).read_to_end(&mut vec)?,
+    };
+    if vec.len() > self.max_decompressed_len {
+      return Ok(None);
+    }
+    *body = Vector::from_vec(vec);
+    Ok(Some(()))
+  }
+}
+
+impl<CA, E, SA> Middleware<CA, E, SA> for CompressionMiddleware
+where
+  E: From<crate::Error>,
+{
+  type Aux = Option<ContentEncoding>;
+
+  #[inline]
+  fn aux(&self) -> Self::Aux {
+    None
+  }
+
+  #[inline]
+  async fn req(
+    &self,
+    _: &mut CA,
+    mw_aux: &mut Self::Aux,
+    _: IpAddr,
+    req: &mut Request<ReqResBuffer>,
+    _: &mut SA,
+  ) -> Result<ControlFlow<StatusCode, ()>, E> {
+    let [ae_opt, ce_opt] = req.rrd.headers.get_many_by_name([
+      KnownHeaderName::AcceptEncoding.into(),
+      KnownHeaderName::ContentEncoding.into(),
+    ]);
+    *mw_aux = ae_opt.and_then(|el| negotiate(el.value));
+    let Some(ce) = ce_opt else {
+      return Ok(ControlFlow::Continue(()));
+    };
+    let encoding = match ce.value.trim_ascii() {
+      elem if elem.eq_ignore_ascii_case(b"identity") => return Ok(ControlFlow::Continue(())),
+      elem if elem.eq_ignore_ascii_case(b"deflate") => ContentEncoding::Deflate,
+      elem if elem.eq_ignore_ascii_case(b"gzip") || elem.eq_ignore_ascii_case(b"x-gzip") => {
+        ContentEncoding::Gzip
+      }
+      _ => return Ok(ControlFlow::Break(StatusCode::UnsupportedMediaType)),
+    };
+    match self.decompress(&mut req.rrd.body, encoding) {
+      Err(_) => return Ok(ControlFlow::Break(StatusCode::BadRequest)),
+      Ok(None) => return Ok(ControlFlow::Break(StatusCode::PayloadTooLarge)),
+      Ok(Some(())) => {}
+    }
+    remove_content_headers(&mut req.rrd.headers)?;
+    Ok(ControlFlow::Continue(()))
+  }
+
+  #[inline]
+  async fn res(
+    &self,
+    _: &mut CA,
+    mw_aux: &mut Self::Aux,
+    res: Response<&mut ReqResBuffer>,
+    _: &mut SA,
+  ) -> Result<ControlFlow<StatusCode, ()>, E> {
+    if res.rrd.body.len() < self.threshold {
+      return Ok(ControlFlow::Continue(()));
+    }
+    let [ce_opt, cr_opt, ct_opt] = res.rrd.headers.get_many_by_name([
+      KnownHeaderName::ContentEncoding.into(),
+      KnownHeaderName::ContentRange.into(),
+      KnownHeaderName::ContentType.into(),
+    ]);
+    if ce_opt.is_some() || cr_opt.is_some() || ct_opt.is_some_and(|el| is_compressed(el.value)) {
+      return Ok(ControlFlow::Continue(()));
+    }
+    res.rrd.headers.push_from_iter(Header::from_name_and_value(
+      KnownHeaderName::Vary.into(),
+      [KnownHeaderName::AcceptEncoding.into()].map(str::as_bytes),
+    ))?;
+    let Some(encoding) = *mw_aux else {
+      return Ok(ControlFlow::Continue(()));
+    };
+    self.compress(&mut res.rrd.body, encoding)?;
+    res.rrd.headers.push_from_iter(Header::from_name_and_value(
+      KnownHeaderName::ContentEncoding.into(),
+      [encoding.as_str().as_bytes()],
+    ))?;
+    Ok(ControlFlow::Continue(()))
+  }
+}
+
+impl Default for CompressionMiddleware {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// Content coding negotiated with the client.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ContentEncoding {
+  /// zlib
+  Deflate,
+  /// gzip
+  Gzip,
+}
+
+impl ContentEncoding {
+  #[inline]
+  fn as_str(self) -> &'static str {
+    match self {
+      Self::Deflate => "deflate",
+      Self::Gzip => "gzip",
+    }
+  }
+}
+
+/// Media types whose contents are usually already compressed.
+fn is_compressed(content_type: &[u8]) -> bool {
+  const TYPES: &[&[u8]] = &[
+    b"application/gzip",
+    b"application/pdf",
+    b"application/vnd.rar",
+    b"application/x-7z-compressed",
+    b"application/x-bzip2",
+    b"application/x-gzip",
+    b"application/x-rar-compressed",
+    b"application/x-xz",
+    b"application/zip",
+    b"application/zstd",
+    b"font/woff",
+    b"font/woff2",
+  ];
+  const PREFIXES: &[&[u8]] = &[b"audio/", b"image/", b"video/"];
+  let media_type = bytes_split1(content_type, b';').next().unwrap_or_default().trim_ascii();
+  let has_prefix = |prefix: &[u8]| {
+    media_type.get(..prefix.len()).is_some_and(|el| el.eq_ignore_ascii_case(prefix))
+  };
+  if media_type.eq_ignore_ascii_case(b"image/svg+xml") {
+    return false;
+  }
+  PREFIXES.iter().any(|el| has_prefix(el))
+    || TYPES.iter().any(|el| media_type.eq_ignore_ascii_case(el))
+}
+
+/// Picks the encoding with the highest quality value. `gzip` is preferred in case of ties.
+fn negotiate(accept_encoding: &[u8]) -> Option<ContentEncoding> {
+  let mut deflate = None;
+  let mut gzip = None;
+  let mut wildcard = None;
+  for elem in bytes_split1(accept_encoding, b',') {
+    let (coding, params) = bytes_split_once1(elem, b';').unwrap_or((elem, &[]));
+    let quality = quality(params);
+    match coding.trim_ascii() {
+      b"*" => wildcard = Some(quality),
+      local if local.eq_ignore_ascii_case(b"deflate") => deflate = Some(quality),
+      local if local.eq_ignore_ascii_case(b"gzip") || local.eq_ignore_ascii_case(b"x-gzip") => {
+        gzip = Some(quality);
+      }
+      _ => {}
+    }
+  }
+  let gzip_quality = gzip.or(wildcard).unwrap_or_default();
+  let deflate_quality = deflate.or(wildcard).unwrap_or_default();
+  if gzip_quality == 0 && deflate_quality == 0 {
+    return None;
+  }
+  Some(if deflate_quality > gzip_quality {
+    ContentEncoding::Deflate
+  } else {
+    ContentEncoding::Gzip
+  })
+}
+
+/// Quality value multiplied by 1000. Absent or invalid values are treated as 1.
+fn quality(params: &[u8]) -> u16 {
+  let Some(value) = params.trim_ascii().strip_prefix(b"q=") else {
+    return 1000;
+  };
+  let (integer, fraction) = bytes_split_once1(value, b'.').unwrap_or((value, &[]));
+  match integer {
+    b"0" => {
+      let mut rslt: u16 = 0;
+      let mut factor: u16 = 100;
+      for byte in fraction.iter().take(3) {
+        if !byte.is_ascii_digit() {
+          return 1000;
+        }
+        rslt = rslt.wrapping_add(u16::from(byte.wrapping_sub(b'0')).wrapping_mul(factor));
+        factor /= 10;
+      }
+      rslt
+    }
+    _ => 1000,
+  }
+}
+
+/// Decompressed bodies no longer match the original `Content-Encoding` and `Content-Length`.
+fn remove_content_headers(headers: &mut Headers) -> crate::Result<()> {
+  let content_encoding: &str = KnownHeaderName::ContentEncoding.into();
+  let content_length: &str = KnownHeaderName::ContentLength.into();
+  let mut new_headers = Headers::new();
+  for header in headers.iter() {
+    if header.name.eq_ignore_ascii_case(content_encoding)
+      || header.name.eq_ignore_ascii_case(content_length)
+    {
+      continue;
+    }
+    new_headers.push_from_iter(Header {
+      is_sensitive: header.is_sensitive,
+      is_trailer: header.is_trailer,
+      name: header.name,
+      value: [header.value],
+    })?;
+  }
+  *headers = new_headers;
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  #[cfg(feature = "_async-tests")]
+  use crate::http::{
+    server_framework::Middleware, Header, KnownHeaderName, Method, Mime, ReqResBuffer, Request,
+    Response, StatusCode,
+  };
+  use crate::{
+    http::server_framework::compression_middleware::{
+      is_compressed, negotiate, CompressionMiddleware, ContentEncoding,
+    },
+    misc::Vector,
+  };
+  #[cfg(feature = "_async-tests")]
+  use core::{
+    net::{IpAddr, Ipv4Addr},
+    ops::ControlFlow,
+  };
+
+  #[test]
+  fn detects_compressed_types() {
+    assert!(is_compressed(b"image/png"));
+    assert!(is_compressed(b"Video/MP4; codecs=avc1"));
+    assert!(is_compressed(b"application/zip"));
+    assert!(is_compressed(b"font/woff2"));
+    assert!(!is_compressed(b"image/svg+xml"));
+    assert!(!is_compressed(b"text/html; charset=utf-8"));
+    assert!(!is_compressed(b"application/json"));
+  }
+
+  #[cfg(feature = "_async-tests")]
+  #[tokio::test]
+  async fn middleware() {
+    let cm = CompressionMiddleware::new().threshold(16);
+    let body = [b'a'; 64];
+
+    let mut rrb = ReqResBuffer::empty();
+    push(&mut rrb, KnownHeaderName::AcceptEncoding, "deflate;q=0.5, gzip");
+    let (req_cf, res_cf, rrb) = exchange(&cm, rrb, &body, Some(Mime::Text)).await;
+    assert_eq!((req_cf, res_cf), (ControlFlow::Continue(()), ControlFlow::Continue(())));
+    assert_eq!(header(&rrb, KnownHeaderName::ContentEncoding), Some(&b"gzip"[..]));
+    assert_eq!(header(&rrb, KnownHeaderName::Vary), Some(&b"accept-encoding"[..]));
+    let mut decompressed = rrb.body.clone();
+    assert_eq!(cm.decompress(&mut decompressed, ContentEncoding::Gzip).unwrap(), Some(()));
+    assert_eq!(decompressed.as_slice(), &body);
+
+    let mut rrb = ReqResBuffer::empty();
+    push(&mut rrb, KnownHeaderName::AcceptEncoding, "gzip");
+    let (_, _, rrb) = exchange(&cm, rrb, &body, Some(Mime::Png)).await;
+    assert_eq!(header(&rrb, KnownHeaderName::ContentEncoding), None);
+    assert_eq!(rrb.body.as_slice(), &body);
+
+    let mut rrb = ReqResBuffer::empty();
+    push(&mut rrb, KnownHeaderName::AcceptEncoding, "gzip");
+    let (_, _, rrb) = exchange(&cm, rrb, &body[..8], Some(Mime::Text)).await;
+    assert_eq!(header(&rrb, KnownHeaderName::ContentEncoding), None);
+
+    let mut rrb = ReqResBuffer::empty();
+    let mut compressed = Vector::from_slice(b"payload").unwrap();
+    cm.compress(&mut compressed, ContentEncoding::Deflate).unwrap();
+    rrb.body.extend_from_copyable_slice(&compressed).unwrap();
+    push(&mut rrb, KnownHeaderName::ContentEncoding, "deflate");
+    let mut req = Request::http2(Method::Post, rrb);
+    let mut aux = Middleware::<(), crate::Error, ()>::aux(&cm);
+    let peer = IpAddr::V4(Ipv4Addr::LOCALHOST);
+    let cf =
+      Middleware::<(), crate::Error, ()>::req(&cm, &mut (), &mut aux, peer, &mut req, &mut ())
+        .await
+        .unwrap();
+    assert_eq!(cf, ControlFlow::Continue(()));
+    assert_eq!(req.rrd.body.as_slice(), b"payload");
+    assert_eq!(header(&req.rrd, KnownHeaderName::ContentEncoding), None);
+
+    let mut rrb = ReqResBuffer::empty();
+    push(&mut rrb, KnownHeaderName::ContentEncoding, "br");
+    let (req_cf, _, _) = exchange(&cm, rrb, &body, None).await;
+    assert_eq!(req_cf, ControlFlow::Break(StatusCode::UnsupportedMediaType));
+  }
+
+  #[cfg(feature = "_async-tests")]
+  async fn exchange(
+    cm: &CompressionMiddleware,
+    rrb: ReqResBuffer,
+    body: &[u8],
+    mime: Option<Mime>,
+  ) -> (ControlFlow<StatusCode, ()>, ControlFlow<StatusCode, ()>, ReqResBuffer) {
+    let mut req = Request::http2(Method::Get, rrb);
+    let mut aux = Middleware::<(), crate::Error, ()>::aux(cm);
+    let peer = IpAddr::V4(Ipv4Addr::LOCALHOST);
+    let req_cf =
+      Middleware::<(), crate::Error, ()>::req(cm, &mut (), &mut aux, peer, &mut req, &mut ())
+        .await
+        .unwrap();
+    req.rrd.clear();
+    req.rrd.body.extend_from_copyable_slice(body).unwrap();
+    if let Some(elem) = mime {
+      push(&mut req.rrd, KnownHeaderName::ContentType, elem.as_str());
+    }
+    let res = Response::http2(&mut req.rrd, StatusCode::Ok);
+    let res_cf =
+      Middleware::<(), crate::Error, ()>::res(cm, &mut (), &mut aux, res, &mut ()).await.unwrap();
+    (req_cf, res_cf, req.rrd)
+  }
+
+  #[cfg(feature = "_async-tests")]
+  fn header(rrb: &ReqResBuffer, name: KnownHeaderName) -> Option<&[u8]> {
+    rrb.headers.get_by_name(name.into()).map(|el| el.value)
+  }
+
+  #[cfg(feature = "_async-tests")]
+  fn push(rrb: &mut ReqResBuffer, name: KnownHeaderName, value: &str) {
+    rrb
+      .headers
+      .push_from_iter(Header::from_name_and_value(name.into(), [value.as_bytes()]))
+      .unwrap();
+  }
+
+  #[test]
+  fn negotiates_encodings() {
+    assert_eq!(negotiate(b"gzip, deflate, br"), Some(ContentEncoding::Gzip));
+    assert_eq!(negotiate(b"gzip;q=0.5, deflate"), Some(ContentEncoding::Deflate));
+    assert_eq!(negotiate(b"deflate;q=0.9, *;q=0.1"), Some(ContentEncoding::Deflate));
+    assert_eq!(negotiate(b"*"), Some(ContentEncoding::Gzip));
+    assert_eq!(negotiate(b"gzip;q=0, deflate;q=0.000"), None);
+    assert_eq!(negotiate(b"br, identity"), None);
+  }
+
+  #[test]
+  fn round_trips() {
+    let cm = CompressionMiddleware::new().max_decompressed_len(64);
+    for encoding in [ContentEncoding::Deflate, ContentEncoding::Gzip] {
+      let mut body = Vector::from_slice(&[7; 64]).unwrap();
+      cm.compress(&mut body, encoding).unwrap();
+      assert!(body.len() < 64);
+      assert_eq!(cm.decompress(&mut body, encoding).unwrap(), Some(()));
+      assert_eq!(body.as_slice(), &[7; 64]);
+      let mut large_body = Vector::from_slice(&[7; 65]).unwrap();
+      cm.compress(&mut large_body, encoding).unwrap();
+      assert_eq!(cm.decompress(&mut large_body, encoding).unwrap(), None);
+    }
+  }
+}
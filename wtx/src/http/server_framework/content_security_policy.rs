@@ -0,0 +1,258 @@
+use crate::{
+  http::server_framework::ServerFrameworkError,
+  misc::{ArrayString, Vector},
+};
+use core::fmt::{Display, Formatter, Write};
+
+/// Typed builder of the `Content-Security-Policy` header.
+///
+/// ```rust
+/// use wtx::http::server_framework::{ContentSecurityPolicy, CspDirective, CspSource};
+/// let csp = ContentSecurityPolicy::new()
+///   .directive(CspDirective::DefaultSrc, [CspSource::SelfOrigin])
+///   .unwrap()
+///   .directive(CspDirective::ScriptSrc, [CspSource::SelfOrigin, CspSource::Nonce])
+///   .unwrap();
+/// let mut value = String::new();
+/// csp.write_with_nonce(&mut value, Some("abc")).unwrap();
+/// assert_eq!(value, "default-src 'self'; script-src 'self' 'nonce-abc'");
+/// ```
+#[derive(Debug)]
+pub struct ContentSecurityPolicy {
+  directives: Vector<(CspDirective, Vector<CspSource>)>,
+  upgrade_insecure_requests: bool,
+}
+
+impl ContentSecurityPolicy {
+  /// Policy without directives.
+  #[inline]
+  pub const fn new() -> Self {
+    Self { directives: Vector::new(), upgrade_insecure_requests: false }
+  }
+
+  /// `default-src 'self'; base-uri 'self'; frame-ancestors 'none'; object-src 'none'`
+  #[inline]
+  pub(crate) fn strict() -> Self {
+    let self_origin = || Vector::from_vec(alloc::vec![CspSource::SelfOrigin]);
+    Self {
+      directives: Vector::from_vec(alloc::vec![
+        (CspDirective::DefaultSrc, self_origin()),
+        (CspDirective::BaseUri, self_origin()),
+        (CspDirective::FrameAncestors, Vector::new()),
+        (CspDirective::ObjectSrc, Vector::new()),
+      ]),
+      upgrade_insecure_requests: false,
+    }
+  }
+
+  /// Adds `directive` with the given `sources`. An empty set of sources is rendered as
+  /// `'none'`, which is also the only source that can't be combined with others.
+  #[inline]
+  pub fn directive(
+    mut self,
+    directive: CspDirective,
+    sources: impl IntoIterator<Item = CspSource>,
+  ) -> crate::Result<Self> {
+    let mut vector = Vector::new();
+    vector.extend_from_iter(sources)?;
+    if vector.len() > 1 && vector.contains(&CspSource::None) {
+      return Err(ServerFrameworkError::InvalidCspSources.into());
+    }
+    self.directives.push((directive, vector))?;
+    Ok(self)
+  }
+
+  /// If the policy has at least one [`CspSource::Nonce`].
+  #[inline]
+  pub fn has_nonce(&self) -> bool {
+    self.directives.iter().any(|(_, sources)| sources.contains(&CspSource::Nonce))
+  }
+
+  /// Instructs browsers to treat insecure URLs as if they were secure.
+  #[inline]
+  #[must_use]
+  pub fn upgrade_insecure_requests(mut self, elem: bool) -> Self {
+    self.upgrade_insecure_requests = elem;
+    self
+  }
+
+  /// Writes the policy into `buffer`. [`CspSource::Nonce`] entries are ignored if `nonce` is
+  /// `None`, as such, directives that only have nonces can't be rendered and return an error.
+  #[inline]
+  pub fn write_with_nonce<W>(&self, buffer: &mut W, nonce: Option<&str>) -> core::fmt::Result
+  where
+    W: Write,
+  {
+    let mut sep = "";
+    for (directive, sources) in self.directives.iter() {
+      if nonce.is_none()
+        && !sources.is_empty()
+        && sources.iter().all(|source| *source == CspSource::Nonce)
+      {
+        return Err(core::fmt::Error);
+      }
+      buffer.write_fmt(format_args!("{sep}{}", directive.strings().custom[0]))?;
+      sep = "; ";
+      let mut has_source = false;
+      for source in sources.iter() {
+        match (source, nonce) {
+          (CspSource::Nonce, Some(elem)) => buffer.write_fmt(format_args!(" 'nonce-{elem}'"))?,
+          (CspSource::Nonce, None) => continue,
+          _ => buffer.write_fmt(format_args!(" {}", source.as_str()))?,
+        }
+        has_source = true;
+      }
+      if !has_source {
+        buffer.write_str(" 'none'")?;
+      }
+    }
+    if self.upgrade_insecure_requests {
+      buffer.write_fmt(format_args!("{sep}upgrade-insecure-requests"))?;
+    }
+    Ok(())
+  }
+}
+
+impl Default for ContentSecurityPolicy {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// Renders a policy with the nonce of the current request.
+pub(crate) struct CspWithNonce<'any>(
+  pub(crate) &'any ContentSecurityPolicy,
+  pub(crate) Option<&'any str>,
+);
+
+impl Display for CspWithNonce<'_> {
+  #[inline]
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    self.0.write_with_nonce(f, self.1)
+  }
+}
+
+/// Random value generated for each request by
+/// [`crate::http::server_framework::SecurityHeadersMiddleware`] that allows specific inline
+/// scripts or styles, for example, `<script nonce="...">`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CspNonce(pub(crate) ArrayString<24>);
+
+impl CspNonce {
+  /// Base64 representation
+  #[inline]
+  pub fn as_str(&self) -> &str {
+    self.0.as_str()
+  }
+}
+
+_create_enum! {
+  /// Fetch directives and other directives of a Content Security Policy.
+  #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+  pub enum CspDirective<u8> {
+    /// Restricts the URLs that can be used in the `base` element.
+    BaseUri = (0, "base-uri"),
+    /// Restricts the URLs that can be loaded by scripts.
+    ConnectSrc = (1, "connect-src"),
+    /// Fallback of other fetch directives.
+    DefaultSrc = (2, "default-src"),
+    /// Sources of fonts.
+    FontSrc = (3, "font-src"),
+    /// Restricts the URLs that can be used as the target of form submissions.
+    FormAction = (4, "form-action"),
+    /// Parents that may embed a page.
+    FrameAncestors = (5, "frame-ancestors"),
+    /// Sources of nested browsing contexts.
+    FrameSrc = (6, "frame-src"),
+    /// Sources of images.
+    ImgSrc = (7, "img-src"),
+    /// Sources of audio and video.
+    MediaSrc = (8, "media-src"),
+    /// Sources of plugins.
+    ObjectSrc = (9, "object-src"),
+    /// Sources of scripts.
+    ScriptSrc = (10, "script-src"),
+    /// Sources of stylesheets.
+    StyleSrc = (11, "style-src"),
+    /// Sources of workers.
+    WorkerSrc = (12, "worker-src"),
+  }
+}
+
+/// Source of a [`CspDirective`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CspSource {
+  /// Host or scheme, for example, `https://example.com`.
+  Custom(&'static str),
+  /// `data:` URLs.
+  Data,
+  /// Any URL with the `https` scheme.
+  Https,
+  /// `'nonce-*'` with the nonce of the current request.
+  Nonce,
+  /// `'none'`
+  None,
+  /// `'self'`
+  SelfOrigin,
+  /// `'strict-dynamic'`
+  StrictDynamic,
+  /// `'unsafe-eval'`
+  UnsafeEval,
+  /// `'unsafe-inline'`
+  UnsafeInline,
+}
+
+impl CspSource {
+  #[inline]
+  const fn as_str(self) -> &'static str {
+    match self {
+      Self::Custom(elem) => elem,
+      Self::Data => "data:",
+      Self::Https => "https:",
+      Self::Nonce => "'nonce-'",
+      Self::None => "'none'",
+      Self::SelfOrigin => "'self'",
+      Self::StrictDynamic => "'strict-dynamic'",
+      Self::UnsafeEval => "'unsafe-eval'",
+      Self::UnsafeInline => "'unsafe-inline'",
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::http::server_framework::{
+    ContentSecurityPolicy, CspDirective, CspSource, ServerFrameworkError,
+  };
+  use alloc::string::String;
+
+  #[test]
+  fn none_is_exclusive() {
+    assert!(matches!(
+      ContentSecurityPolicy::new()
+        .directive(CspDirective::ImgSrc, [CspSource::None, CspSource::Data]),
+      Err(crate::Error::ServerFrameworkError(ServerFrameworkError::InvalidCspSources))
+    ));
+    let csp =
+      ContentSecurityPolicy::new().directive(CspDirective::ImgSrc, [CspSource::None]).unwrap();
+    let mut value = String::new();
+    csp.write_with_nonce(&mut value, None).unwrap();
+    assert_eq!(value, "img-src 'none'");
+  }
+
+  #[test]
+  fn nonce_only_directives_without_nonce() {
+    let csp = ContentSecurityPolicy::new()
+      .directive(CspDirective::DefaultSrc, [CspSource::SelfOrigin, CspSource::Nonce])
+      .unwrap();
+    let mut value = String::new();
+    csp.write_with_nonce(&mut value, None).unwrap();
+    assert_eq!(value, "default-src 'self'");
+    let csp = csp.directive(CspDirective::ScriptSrc, [CspSource::Nonce]).unwrap();
+    let mut value = String::new();
+    csp.write_with_nonce(&mut value, Some("abc")).unwrap();
+    assert_eq!(value, "default-src 'self' 'nonce-abc'; script-src 'nonce-abc'");
+    assert!(csp.write_with_nonce(&mut String::new(), None).is_err());
+  }
+}
@@ -6,7 +6,7 @@ use crate::{
   },
   misc::{bytes_split1, ArrayVector, Intersperse, Vector},
 };
-use core::ops::ControlFlow;
+use core::{net::IpAddr, ops::ControlFlow};
 use hashbrown::HashSet;
 
 const MAX_HEADEARS: usize = 8;
@@ -405,6 +405,7 @@ where
     &self,
     _: &mut CA,
     mw_aux: &mut Self::Aux,
+    _: IpAddr,
     req: &mut Request<ReqResBuffer>,
     _: &mut SA,
   ) -> Result<ControlFlow<StatusCode, ()>, E> {
@@ -0,0 +1,46 @@
+use crate::misc::bytes_split1;
+
+/// Compares the entity tags of `value` with `etag`.
+///
+/// If `is_weak` is true, then the weak comparison of RFC 9110 is used and tags match when their
+/// opaque parts are equal. Otherwise, both tags must be strong and equal.
+#[inline]
+pub(crate) fn matches_etag(value: &[u8], etag: &[u8], is_weak: bool) -> bool {
+  let (etag_is_weak, etag_opaque) = match etag.strip_prefix(b"W/") {
+    Some(elem) => (true, elem),
+    None => (false, etag),
+  };
+  bytes_split1(value, b',').any(|elem| {
+    let trimmed = elem.trim_ascii();
+    if trimmed == b"*" {
+      return true;
+    }
+    match trimmed.strip_prefix(b"W/") {
+      Some(tag) => is_weak && tag == etag_opaque,
+      None => trimmed == etag_opaque && (is_weak || !etag_is_weak),
+    }
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::http::server_framework::entity_tag::matches_etag;
+
+  #[test]
+  fn strong_and_weak_comparisons() {
+    for (value, etag, weak, strong) in [
+      (b"\"1\"".as_slice(), b"\"1\"".as_slice(), true, true),
+      (b"W/\"1\"", b"\"1\"", true, false),
+      (b"\"1\"", b"W/\"1\"", true, false),
+      (b"W/\"1\"", b"W/\"1\"", true, false),
+      (b"\"1\"", b"\"2\"", false, false),
+      (b"W/\"2\"", b"W/\"1\"", false, false),
+      (b"\"0\", W/\"1\"", b"\"1\"", true, false),
+      (b"\"0\" , \"1\"", b"\"1\"", true, true),
+      (b"*", b"W/\"1\"", true, true),
+    ] {
+      assert_eq!(matches_etag(value, etag, true), weak);
+      assert_eq!(matches_etag(value, etag, false), strong);
+    }
+  }
+}
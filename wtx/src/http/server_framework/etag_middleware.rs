@@ -0,0 +1,236 @@
+use crate::{
+  http::{
+    server_framework::{entity_tag::matches_etag, Middleware},
+    CacheControl, Header, KnownHeaderName, Method, ReqResBuffer, Request, Response, StatusCode,
+  },
+  misc::{bytes_split1, ArrayString, Vector},
+};
+use core::{fmt::Write, net::IpAddr, ops::ControlFlow};
+
+/// Adds strong `ETag` headers to successful responses of `GET` or `HEAD` requests and answers
+/// `If-None-Match` headers that match the tag with `304 Not Modified` and an empty body.
+///
+/// Tags are derived from the final bytes of the body, which means that endpoints are still
+/// executed. What is saved is the transfer of the body. Tags already provided by endpoints are
+/// kept as is and responses with a `no-store` directive are left untouched.
+///
+/// Must be placed after middlewares that modify response bodies, like
+/// [`crate::http::server_framework::CompressionMiddleware`].
+#[derive(Debug)]
+pub struct EtagMiddleware {
+  cache_control: Option<CacheControl>,
+}
+
+impl EtagMiddleware {
+  /// Doesn't add `Cache-Control` headers.
+  #[inline]
+  pub const fn new() -> Self {
+    Self { cache_control: None }
+  }
+
+  /// Policy of responses that don't have a `Cache-Control` header.
+  #[inline]
+  #[must_use]
+  pub const fn cache_control(mut self, elem: CacheControl) -> Self {
+    self.cache_control = Some(elem);
+    self
+  }
+}
+
+impl Default for EtagMiddleware {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<CA, E, SA> Middleware<CA, E, SA> for EtagMiddleware
+where
+  E: From<crate::Error>,
+{
+  /// If the request is a `GET` or `HEAD` request and the contents of its `If-None-Match` header.
+  type Aux = (bool, Vector<u8>);
+
+  #[inline]
+  fn aux(&self) -> Self::Aux {
+    (false, Vector::new())
+  }
+
+  #[inline]
+  async fn req(
+    &self,
+    _: &mut CA,
+    mw_aux: &mut Self::Aux,
+    _: IpAddr,
+    req: &mut Request<ReqResBuffer>,
+    _: &mut SA,
+  ) -> Result<ControlFlow<StatusCode, ()>, E> {
+    mw_aux.0 = matches!(req.method, Method::Get | Method::Head);
+    if mw_aux.0 {
+      if let Some(header) = req.rrd.headers.get_by_name(KnownHeaderName::IfNoneMatch.into()) {
+        mw_aux.1.extend_from_copyable_slice(header.value).map_err(crate::Error::from)?;
+      }
+    }
+    Ok(ControlFlow::Continue(()))
+  }
+
+  #[inline]
+  async fn res(
+    &self,
+    _: &mut CA,
+    mw_aux: &mut Self::Aux,
+    res: Response<&mut ReqResBuffer>,
+    _: &mut SA,
+  ) -> Result<ControlFlow<StatusCode, ()>, E> {
+    if !mw_aux.0 || res.status_code != StatusCode::Ok {
+      return Ok(ControlFlow::Continue(()));
+    }
+    let [cc_opt, etag_opt] = res
+      .rrd
+      .headers
+      .get_many_by_name([KnownHeaderName::CacheControl.into(), KnownHeaderName::Etag.into()]);
+    let is_no_store = match cc_opt {
+      Some(header) => bytes_split1(header.value, b',')
+        .any(|elem| elem.trim_ascii().eq_ignore_ascii_case(b"no-store")),
+      None => self.cache_control.is_some_and(|el| el.is_no_store()),
+    };
+    let has_cc = cc_opt.is_some();
+    let has_if_none_match = !mw_aux.1.is_empty();
+    let prev_is_match =
+      etag_opt.map(|el| has_if_none_match && matches_etag(&mw_aux.1, el.value, true));
+    if let (false, Some(elem)) = (has_cc, &self.cache_control) {
+      elem.push_header(&mut res.rrd.headers)?;
+    }
+    if is_no_store {
+      return Ok(ControlFlow::Continue(()));
+    }
+    let is_match = if let Some(elem) = prev_is_match {
+      elem
+    } else {
+      let mut etag = ArrayString::<40>::new();
+      let len = res.rrd.body.len();
+      let hash = fnv1a(&res.rrd.body);
+      etag.write_fmt(format_args!("\"{len:x}-{hash:016x}\"")).map_err(crate::Error::from)?;
+      res.rrd.headers.push_from_iter(Header::from_name_and_value(
+        KnownHeaderName::Etag.into(),
+        [etag.as_bytes()],
+      ))?;
+      has_if_none_match && matches_etag(&mw_aux.1, etag.as_bytes(), true)
+    };
+    if is_match {
+      res.rrd.body.clear();
+      return Ok(ControlFlow::Break(StatusCode::NotModified));
+    }
+    Ok(ControlFlow::Continue(()))
+  }
+}
+
+// 64-bit FNV-1a
+#[inline]
+fn fnv1a(bytes: &[u8]) -> u64 {
+  bytes.iter().fold(0xcbf2_9ce4_8422_2325, |acc, byte| {
+    (acc ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+  })
+}
+
+#[cfg(all(feature = "_async-tests", test))]
+mod tests {
+  use crate::{
+    http::{
+      server_framework::{EtagMiddleware, Middleware},
+      CacheControl, Header, KnownHeaderName, Method, ReqResBuffer, Request, Response, StatusCode,
+    },
+    misc::from_utf8_basic,
+  };
+  use core::{
+    net::{IpAddr, Ipv4Addr},
+    ops::ControlFlow,
+  };
+
+  #[tokio::test]
+  async fn conditional_requests() {
+    let mw = EtagMiddleware::new().cache_control(CacheControl::new().no_cache(true));
+    let etag = exchange(&mw, Method::Get, None, ControlFlow::Continue(())).await.unwrap();
+    let _ =
+      exchange(&mw, Method::Get, Some(&etag), ControlFlow::Break(StatusCode::NotModified)).await;
+    let weak = alloc::format!("W/{etag}");
+    let _ =
+      exchange(&mw, Method::Get, Some(&weak), ControlFlow::Break(StatusCode::NotModified)).await;
+    let _ = exchange(&mw, Method::Get, Some("\"other\""), ControlFlow::Continue(())).await;
+    assert_eq!(exchange(&mw, Method::Post, Some(&etag), ControlFlow::Continue(())).await, None);
+  }
+
+  #[cfg(all(feature = "matchit", feature = "nightly"))]
+  #[tokio::test]
+  async fn head_requests_of_methods() {
+    use crate::{
+      http::server_framework::{get, methods, Router, ServerFrameworkBuilder, State},
+      misc::{simple_seed, Xorshift64},
+    };
+
+    async fn item(state: State<'_, (), (), ReqResBuffer>) -> crate::Result<StatusCode> {
+      state.req.rrd.clear();
+      state.req.rrd.body.extend_from_copyable_slice(b"{\"id\":1}")?;
+      Ok(StatusCode::Ok)
+    }
+
+    let router =
+      Router::new(crate::paths!(("/item", methods((get(item),)))), EtagMiddleware::new()).unwrap();
+    let client = ServerFrameworkBuilder::new(router)
+      .without_aux()
+      .test_client(Xorshift64::from(simple_seed()), |err: crate::Error| panic!("{err:?}"))
+      .await
+      .unwrap();
+    let get_res = client.get("/item").send().await.unwrap();
+    let get_etag = get_res.rrd.headers.get_by_name(KnownHeaderName::Etag.into()).unwrap().value;
+    let head_res = client.req(Method::Head, "/item").send().await.unwrap();
+    assert_eq!(head_res.status_code, StatusCode::Ok);
+    assert!(head_res.rrd.body.is_empty());
+    let head_etag = head_res.rrd.headers.get_by_name(KnownHeaderName::Etag.into()).unwrap().value;
+    assert_eq!(head_etag, get_etag);
+    let res = client
+      .req(Method::Head, "/item")
+      .header(KnownHeaderName::IfNoneMatch.into(), get_etag)
+      .unwrap()
+      .send()
+      .await
+      .unwrap();
+    assert_eq!(res.status_code, StatusCode::NotModified);
+  }
+
+  async fn exchange(
+    mw: &EtagMiddleware,
+    method: Method,
+    if_none_match: Option<&str>,
+    expected: ControlFlow<StatusCode, ()>,
+  ) -> Option<alloc::string::String> {
+    let mut rrb = ReqResBuffer::empty();
+    if let Some(elem) = if_none_match {
+      rrb
+        .headers
+        .push_from_iter(Header::from_name_and_value(
+          KnownHeaderName::IfNoneMatch.into(),
+          [elem.as_bytes()],
+        ))
+        .unwrap();
+    }
+    let mut req = Request::http2(method, rrb);
+    let mut aux = Middleware::<(), crate::Error, ()>::aux(mw);
+    let peer = IpAddr::V4(Ipv4Addr::LOCALHOST);
+    let _ = Middleware::<(), crate::Error, ()>::req(mw, &mut (), &mut aux, peer, &mut req, &mut ())
+      .await
+      .unwrap();
+    req.rrd.clear();
+    req.rrd.body.extend_from_copyable_slice(b"{\"id\":1}").unwrap();
+    let res = Response::http2(&mut req.rrd, StatusCode::Ok);
+    let rslt =
+      Middleware::<(), crate::Error, ()>::res(mw, &mut (), &mut aux, res, &mut ()).await.unwrap();
+    assert_eq!(rslt, expected);
+    assert_eq!(req.rrd.body.is_empty(), expected.is_break());
+    let etag = req.rrd.headers.get_by_name(KnownHeaderName::Etag.into());
+    if let Some(header) = req.rrd.headers.get_by_name(KnownHeaderName::CacheControl.into()) {
+      assert_eq!(header.value, b"no-cache");
+    }
+    etag.map(|el| from_utf8_basic(el.value).unwrap().into())
+  }
+}
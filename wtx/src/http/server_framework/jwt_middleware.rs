@@ -0,0 +1,480 @@
+use crate::{
+  http::{
+    server_framework::{Authorization, Middleware, ReqPart, ServerFrameworkError},
+    Header, KnownHeaderName, ReqResBuffer, Request, Response, StatusCode,
+  },
+  misc::{str_rsplit_once1, str_split1, GenericTime, LeaseMut, Vector},
+};
+use alloc::{string::String, vec::Vec};
+#[cfg(all(feature = "aws-lc-rs", not(feature = "ring")))]
+use aws_lc_rs::signature;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use core::{marker::PhantomData, net::IpAddr, ops::ControlFlow, time::Duration};
+use hmac::{Hmac, Mac};
+#[cfg(feature = "ring")]
+use ring::signature;
+use serde::{de::DeserializeOwned, Deserialize, Deserializer};
+use sha2::{Sha256, Sha384, Sha512};
+
+/// Signature algorithms of JSON Web Tokens.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum JwtAlgorithm {
+  /// ECDSA using P-256 and SHA-256
+  #[cfg(any(feature = "aws-lc-rs", feature = "ring"))]
+  Es256,
+  /// HMAC using SHA-256
+  Hs256,
+  /// HMAC using SHA-384
+  Hs384,
+  /// HMAC using SHA-512
+  Hs512,
+  /// RSASSA-PKCS1-v1_5 using SHA-256
+  #[cfg(any(feature = "aws-lc-rs", feature = "ring"))]
+  Rs256,
+}
+
+impl JwtAlgorithm {
+  /// Value of the `alg` header parameter.
+  #[inline]
+  pub const fn name(self) -> &'static str {
+    match self {
+      #[cfg(any(feature = "aws-lc-rs", feature = "ring"))]
+      Self::Es256 => "ES256",
+      Self::Hs256 => "HS256",
+      Self::Hs384 => "HS384",
+      Self::Hs512 => "HS512",
+      #[cfg(any(feature = "aws-lc-rs", feature = "ring"))]
+      Self::Rs256 => "RS256",
+    }
+  }
+}
+
+/// Validates `Bearer` tokens of the `Authorization` header and writes the decoded claims of type
+/// `C` into the stream auxiliary, which is available to endpoints through their state.
+///
+/// Only tokens signed with the configured algorithm are accepted, which prevents algorithm
+/// confusion attacks. `exp` and `nbf` are always verified when present while `aud` and `iss`
+/// are only verified when the corresponding expected values are set.
+///
+/// Missing or invalid tokens are answered with `401 Unauthorized` and a `WWW-Authenticate` header.
+#[derive(Debug)]
+pub struct JwtMiddleware<C> {
+  algorithm: JwtAlgorithm,
+  audience: Option<String>,
+  issuer: Option<String>,
+  key: Vector<u8>,
+  leeway: Duration,
+  phantom: PhantomData<fn() -> C>,
+}
+
+impl<C> JwtMiddleware<C>
+where
+  C: DeserializeOwned,
+{
+  /// `key` is the shared secret of HMAC algorithms, the DER-encoded `RSAPublicKey` of `RS256` or
+  /// the uncompressed SEC1 point of `ES256`.
+  #[inline]
+  pub fn new(algorithm: JwtAlgorithm, key: &[u8]) -> crate::Result<Self> {
+    let mut vector = Vector::new();
+    vector.extend_from_copyable_slice(key)?;
+    Ok(Self {
+      algorithm,
+      audience: None,
+      issuer: None,
+      key: vector,
+      leeway: Duration::ZERO,
+      phantom: PhantomData,
+    })
+  }
+
+  /// Tokens must contain `elem` in their `aud` claim.
+  #[inline]
+  #[must_use]
+  pub fn audience(mut self, elem: impl Into<String>) -> Self {
+    self.audience = Some(elem.into());
+    self
+  }
+
+  /// Tokens must have `elem` as their `iss` claim.
+  #[inline]
+  #[must_use]
+  pub fn issuer(mut self, elem: impl Into<String>) -> Self {
+    self.issuer = Some(elem.into());
+    self
+  }
+
+  /// Tolerated clock skew when verifying `exp` and `nbf`.
+  #[inline]
+  #[must_use]
+  pub fn leeway(mut self, elem: Duration) -> Self {
+    self.leeway = elem;
+    self
+  }
+
+  /// Verifies the signature and the registered claims of `token` at the instant `now`, which is
+  /// the elapsed time since the UNIX epoch.
+  #[inline]
+  pub fn verify(&self, token: &str, now: Duration) -> crate::Result<C> {
+    let (message, signature) =
+      str_rsplit_once1(token, b'.').ok_or(ServerFrameworkError::InvalidJwt)?;
+    let mut iter = str_split1(message, b'.');
+    let (Some(header), Some(payload), None) = (iter.next(), iter.next(), iter.next()) else {
+      return Err(ServerFrameworkError::InvalidJwt.into());
+    };
+    let jwt_header: JwtHeader =
+      serde_json::from_slice(&decode(header)?).map_err(|_err| ServerFrameworkError::InvalidJwt)?;
+    if jwt_header.alg != self.algorithm.name() {
+      return Err(ServerFrameworkError::InvalidJwt.into());
+    }
+    self.verify_signature(message.as_bytes(), &decode(signature)?)?;
+    let payload = decode(payload)?;
+    let registered: RegisteredClaims =
+      serde_json::from_slice(&payload).map_err(|_err| ServerFrameworkError::InvalidJwt)?;
+    let now_secs = now.as_secs();
+    let leeway_secs = self.leeway.as_secs();
+    if let Some(exp) = registered.exp {
+      if now_secs >= exp.saturating_add(leeway_secs) {
+        return Err(ServerFrameworkError::ExpiredJwt.into());
+      }
+    }
+    if let Some(nbf) = registered.nbf {
+      if now_secs.saturating_add(leeway_secs) < nbf {
+        return Err(ServerFrameworkError::PrematureJwt.into());
+      }
+    }
+    if let Some(audience) = &self.audience {
+      let has_audience = match registered.aud {
+        Some(Audience::Many(elems)) => elems.iter().any(|elem| elem == audience),
+        Some(Audience::One(elem)) => elem == *audience,
+        None => false,
+      };
+      if !has_audience {
+        return Err(ServerFrameworkError::InvalidJwtAudience.into());
+      }
+    }
+    if let Some(issuer) = &self.issuer {
+      if registered.iss.as_ref() != Some(issuer) {
+        return Err(ServerFrameworkError::InvalidJwtIssuer.into());
+      }
+    }
+    Ok(serde_json::from_slice(&payload).map_err(|_err| ServerFrameworkError::InvalidJwt)?)
+  }
+
+  #[inline]
+  fn verify_signature(&self, message: &[u8], signature: &[u8]) -> crate::Result<()> {
+    let is_valid = match self.algorithm {
+      #[cfg(any(feature = "aws-lc-rs", feature = "ring"))]
+      JwtAlgorithm::Es256 => {
+        signature::UnparsedPublicKey::new(&signature::ECDSA_P256_SHA256_FIXED, self.key.as_slice())
+          .verify(message, signature)
+          .is_ok()
+      }
+      JwtAlgorithm::Hs256 => verify_hmac::<Hmac<Sha256>>(&self.key, message, signature),
+      JwtAlgorithm::Hs384 => verify_hmac::<Hmac<Sha384>>(&self.key, message, signature),
+      JwtAlgorithm::Hs512 => verify_hmac::<Hmac<Sha512>>(&self.key, message, signature),
+      #[cfg(any(feature = "aws-lc-rs", feature = "ring"))]
+      JwtAlgorithm::Rs256 => signature::UnparsedPublicKey::new(
+        &signature::RSA_PKCS1_2048_8192_SHA256,
+        self.key.as_slice(),
+      )
+      .verify(message, signature)
+      .is_ok(),
+    };
+    if !is_valid {
+      return Err(ServerFrameworkError::InvalidJwtSignature.into());
+    }
+    Ok(())
+  }
+}
+
+impl<C, CA, E, SA> Middleware<CA, E, SA> for JwtMiddleware<C>
+where
+  C: DeserializeOwned,
+  E: From<crate::Error>,
+  SA: LeaseMut<Option<C>>,
+{
+  type Aux = ();
+
+  #[inline]
+  fn aux(&self) -> Self::Aux {}
+
+  #[inline]
+  async fn req(
+    &self,
+    _: &mut CA,
+    _: &mut Self::Aux,
+    peer: IpAddr,
+    req: &mut Request<ReqResBuffer>,
+    stream_aux: &mut SA,
+  ) -> Result<ControlFlow<StatusCode, ()>, E> {
+    *stream_aux.lease_mut() = None;
+    let rslt = match Authorization::from_req_part(peer, None, req) {
+      Ok(Some(Authorization::Bearer(token))) => self.verify(&token, GenericTime::timestamp()?),
+      Ok(None) => return unauthorized(req, "Bearer").map_err(From::from),
+      Ok(Some(Authorization::Basic { .. })) => Err(ServerFrameworkError::InvalidJwt.into()),
+      Err(err) => Err(err),
+    };
+    match rslt {
+      Ok(claims) => {
+        *stream_aux.lease_mut() = Some(claims);
+        Ok(ControlFlow::Continue(()))
+      }
+      Err(crate::Error::ServerFrameworkError(_)) => {
+        unauthorized(req, "Bearer error=\"invalid_token\"").map_err(From::from)
+      }
+      Err(err) => Err(err.into()),
+    }
+  }
+
+  #[inline]
+  async fn res(
+    &self,
+    _: &mut CA,
+    _: &mut Self::Aux,
+    _: Response<&mut ReqResBuffer>,
+    _: &mut SA,
+  ) -> Result<ControlFlow<StatusCode, ()>, E> {
+    Ok(ControlFlow::Continue(()))
+  }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Audience {
+  Many(Vec<String>),
+  One(String),
+}
+
+#[derive(Deserialize)]
+struct JwtHeader {
+  alg: String,
+}
+
+#[derive(Deserialize)]
+struct RegisteredClaims {
+  aud: Option<Audience>,
+  #[serde(default, deserialize_with = "numeric_date")]
+  exp: Option<u64>,
+  iss: Option<String>,
+  #[serde(default, deserialize_with = "numeric_date")]
+  nbf: Option<u64>,
+}
+
+#[inline]
+fn decode(str: &str) -> crate::Result<Vec<u8>> {
+  Ok(URL_SAFE_NO_PAD.decode(str).map_err(|_err| ServerFrameworkError::InvalidJwt)?)
+}
+
+/// `NumericDate` values can have fractional seconds, which are discarded.
+#[expect(clippy::as_conversions, reason = "float to integer conversions saturate")]
+#[expect(clippy::cast_possible_truncation, reason = "fractional seconds are discarded")]
+#[expect(clippy::cast_sign_loss, reason = "negative values saturate to zero")]
+#[inline]
+fn numeric_date<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  Ok(Option::<f64>::deserialize(deserializer)?.map(|elem| elem as u64))
+}
+
+#[inline]
+fn unauthorized(
+  req: &mut Request<ReqResBuffer>,
+  challenge: &str,
+) -> crate::Result<ControlFlow<StatusCode, ()>> {
+  req.rrd.clear();
+  req.rrd.headers.push_from_iter(Header::from_name_and_value(
+    KnownHeaderName::WwwAuthenticate.into(),
+    [challenge.as_bytes()],
+  ))?;
+  Ok(ControlFlow::Break(StatusCode::Unauthorized))
+}
+
+#[inline]
+fn verify_hmac<M>(key: &[u8], message: &[u8], signature: &[u8]) -> bool
+where
+  M: Mac + hmac::digest::KeyInit,
+{
+  let Ok(mut mac) = <M as hmac::digest::KeyInit>::new_from_slice(key) else {
+    return false;
+  };
+  mac.update(message);
+  mac.verify_slice(signature).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::http::server_framework::{JwtAlgorithm, JwtMiddleware, ServerFrameworkError};
+  use alloc::{format, string::String, vec::Vec};
+  #[cfg(feature = "ring")]
+  use base64::engine::general_purpose::STANDARD;
+  use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+  use core::time::Duration;
+  use hmac::{Hmac, Mac};
+  use sha2::Sha256;
+
+  const KEY: &[u8] = b"secret";
+
+  #[derive(Debug, PartialEq, serde::Deserialize)]
+  struct Claims {
+    sub: String,
+  }
+
+  #[cfg(feature = "ring")]
+  #[test]
+  fn verify_es256() {
+    use ring::{
+      rand::SystemRandom,
+      signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_FIXED_SIGNING},
+    };
+
+    let rng = SystemRandom::new();
+    let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng).unwrap();
+    let key_pair =
+      EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, pkcs8.as_ref(), &rng).unwrap();
+    let jwt =
+      JwtMiddleware::<Claims>::new(JwtAlgorithm::Es256, key_pair.public_key().as_ref()).unwrap();
+    let token = token("ES256", r#"{"sub":"foo"}"#, |message| {
+      key_pair.sign(&rng, message).unwrap().as_ref().into()
+    });
+    assert_eq!(jwt.verify(&token, Duration::ZERO).unwrap().sub, "foo");
+    let hs256 = hs256_token("ES256", r#"{"sub":"foo"}"#, KEY);
+    assert!(matches!(
+      jwt.verify(&hs256, Duration::ZERO),
+      Err(crate::Error::ServerFrameworkError(ServerFrameworkError::InvalidJwtSignature))
+    ));
+  }
+
+  #[cfg(feature = "ring")]
+  #[test]
+  fn verify_rs256() {
+    use ring::{
+      rand::SystemRandom,
+      signature::{RsaKeyPair, RSA_PKCS1_SHA256},
+    };
+
+    let pem = include_str!("../../../../.certs/key.pem");
+    let der = STANDARD
+      .decode(pem.lines().filter(|line| !line.starts_with("-----")).collect::<String>())
+      .unwrap();
+    let key_pair = RsaKeyPair::from_pkcs8(&der).unwrap();
+    let jwt =
+      JwtMiddleware::<Claims>::new(JwtAlgorithm::Rs256, key_pair.public().as_ref()).unwrap();
+    let token = token("RS256", r#"{"sub":"foo"}"#, |message| {
+      let mut signature = alloc::vec![0; key_pair.public().modulus_len()];
+      key_pair.sign(&RSA_PKCS1_SHA256, &SystemRandom::new(), message, &mut signature).unwrap();
+      signature
+    });
+    assert_eq!(jwt.verify(&token, Duration::ZERO).unwrap().sub, "foo");
+    let tampered = format!("{}x", token.get(..token.len().wrapping_sub(1)).unwrap());
+    assert!(jwt.verify(&tampered, Duration::ZERO).is_err());
+  }
+
+  #[test]
+  fn verify() {
+    let jwt = JwtMiddleware::<Claims>::new(JwtAlgorithm::Hs256, KEY)
+      .unwrap()
+      .audience("api")
+      .issuer("wtx")
+      .leeway(Duration::from_secs(5));
+    let now = Duration::from_secs(100);
+    let claims = r#"{"aud":["api"],"exp":150,"iss":"wtx","nbf":90,"sub":"foo"}"#;
+    assert_eq!(jwt.verify(&hs256_token("HS256", claims, KEY), now).unwrap().sub, "foo");
+    let claims = r#"{"aud":"api","exp":150.5,"iss":"wtx","nbf":105.9,"sub":"foo"}"#;
+    assert_eq!(jwt.verify(&hs256_token("HS256", claims, KEY), now).unwrap().sub, "foo");
+    for (alg, claims, key, err) in [
+      ("HS256", r#"{"aud":"api","exp":94,"iss":"wtx","sub":"foo"}"#, KEY, "ExpiredJwt"),
+      ("HS256", r#"{"aud":"api","exp":94.9,"iss":"wtx","sub":"foo"}"#, KEY, "ExpiredJwt"),
+      ("HS256", r#"{"aud":"api","iss":"wtx","nbf":106,"sub":"foo"}"#, KEY, "PrematureJwt"),
+      ("HS256", r#"{"aud":"api","iss":"wtx","nbf":106.1,"sub":"foo"}"#, KEY, "PrematureJwt"),
+      ("HS256", r#"{"aud":"api","exp":"150","iss":"wtx","sub":"foo"}"#, KEY, "InvalidJwt"),
+      ("HS256", r#"{"aud":"web","iss":"wtx","sub":"foo"}"#, KEY, "InvalidJwtAudience"),
+      ("HS256", r#"{"aud":"api","sub":"foo"}"#, KEY, "InvalidJwtIssuer"),
+      ("HS256", r#"{"aud":"api","iss":"wtx","sub":"foo"}"#, b"other", "InvalidJwtSignature"),
+      ("HS384", r#"{"aud":"api","iss":"wtx","sub":"foo"}"#, KEY, "InvalidJwt"),
+      ("none", r#"{"aud":"api","iss":"wtx","sub":"foo"}"#, KEY, "InvalidJwt"),
+    ] {
+      let Err(crate::Error::ServerFrameworkError(local_err)) =
+        jwt.verify(&hs256_token(alg, claims, key), now)
+      else {
+        panic!();
+      };
+      assert_eq!(format!("{local_err:?}"), err);
+    }
+    assert!(matches!(
+      jwt.verify("abc", now),
+      Err(crate::Error::ServerFrameworkError(ServerFrameworkError::InvalidJwt))
+    ));
+  }
+
+  #[cfg(feature = "_async-tests")]
+  #[tokio::test]
+  async fn unauthorized_requests() {
+    use crate::http::{
+      server_framework::Middleware, Header, KnownHeaderName, Method, ReqResBuffer, Request,
+      StatusCode,
+    };
+    use core::{
+      net::{IpAddr, Ipv4Addr},
+      ops::ControlFlow,
+    };
+
+    let jwt = JwtMiddleware::<Claims>::new(JwtAlgorithm::Hs256, KEY).unwrap();
+    let valid = format!("Bearer {}", hs256_token("HS256", r#"{"sub":"foo"}"#, KEY));
+    let invalid = format!("Bearer {}", hs256_token("HS256", r#"{"sub":"foo"}"#, b"other"));
+    for (header, challenge) in [
+      (None, Some(b"Bearer".as_slice())),
+      (Some("Basic Zm9vOmJhcg=="), Some(br#"Bearer error="invalid_token""#.as_slice())),
+      (Some(invalid.as_str()), Some(br#"Bearer error="invalid_token""#.as_slice())),
+      (Some(valid.as_str()), None),
+    ] {
+      let mut req = Request::http2(Method::Get, ReqResBuffer::empty());
+      if let Some(elem) = header {
+        req
+          .rrd
+          .headers
+          .push_from_iter(Header::from_name_and_value(
+            KnownHeaderName::Authorization.into(),
+            [elem.as_bytes()],
+          ))
+          .unwrap();
+      }
+      let mut sa = None;
+      let peer = IpAddr::V4(Ipv4Addr::LOCALHOST);
+      let cf = Middleware::<(), crate::Error, Option<Claims>>::req(
+        &jwt,
+        &mut (),
+        &mut (),
+        peer,
+        &mut req,
+        &mut sa,
+      )
+      .await
+      .unwrap();
+      let www_authenticate = req.rrd.headers.get_by_name(KnownHeaderName::WwwAuthenticate.into());
+      assert_eq!(www_authenticate.map(|el| el.value), challenge);
+      if challenge.is_some() {
+        assert_eq!(cf, ControlFlow::Break(StatusCode::Unauthorized));
+        assert_eq!(sa, None);
+      } else {
+        assert_eq!(cf, ControlFlow::Continue(()));
+        assert_eq!(sa, Some(Claims { sub: "foo".into() }));
+      }
+    }
+  }
+
+  fn hs256_token(alg: &str, claims: &str, key: &[u8]) -> String {
+    token(alg, claims, |message| {
+      let mut mac = Hmac::<Sha256>::new_from_slice(key).unwrap();
+      mac.update(message);
+      mac.finalize().into_bytes().as_slice().into()
+    })
+  }
+
+  fn token(alg: &str, claims: &str, sign: impl FnOnce(&[u8]) -> Vec<u8>) -> String {
+    let header = URL_SAFE_NO_PAD.encode(format!(r#"{{"alg":"{alg}","typ":"JWT"}}"#));
+    let payload = URL_SAFE_NO_PAD.encode(claims);
+    let signature = URL_SAFE_NO_PAD.encode(sign(format!("{header}.{payload}").as_bytes()));
+    format!("{header}.{payload}.{signature}")
+  }
+}
@@ -0,0 +1,37 @@
+use crate::{
+  http::{ReqResBuffer, Response},
+  http2::{Http2Buffer, Http2Data, ServerStream},
+  misc::{LeaseMut, Lock, RefCounter, StreamWriter},
+};
+use core::future::Future;
+
+/// Streams of manual endpoints that can be answered by the framework itself, e.g., when a
+/// middleware halts a request before reaching its endpoint.
+pub trait ManualStreamSender {
+  /// Sends `res` and closes the stream.
+  fn send_manual_res(
+    &mut self,
+    res: Response<&mut ReqResBuffer>,
+  ) -> impl Future<Output = crate::Result<()>>;
+}
+
+impl ManualStreamSender for () {
+  #[inline]
+  async fn send_manual_res(&mut self, _: Response<&mut ReqResBuffer>) -> crate::Result<()> {
+    Ok(())
+  }
+}
+
+impl<HB, HD, SW> ManualStreamSender for ServerStream<HD>
+where
+  HB: LeaseMut<Http2Buffer>,
+  HD: RefCounter,
+  HD::Item: Lock<Resource = Http2Data<HB, SW, false>>,
+  SW: StreamWriter,
+{
+  #[inline]
+  async fn send_manual_res(&mut self, res: Response<&mut ReqResBuffer>) -> crate::Result<()> {
+    let _ = self.send_res(res).await?;
+    Ok(())
+  }
+}
@@ -1,36 +1,124 @@
+pub(crate) mod delete;
+pub(crate) mod dispatch;
+pub(crate) mod form;
 pub(crate) mod get;
 pub(crate) mod json;
+pub(crate) mod patch;
 pub(crate) mod post;
+pub(crate) mod put;
+pub(crate) mod sse;
+pub(crate) mod streaming;
 pub(crate) mod web_socket;
 
-use crate::http::{Headers, HttpError, KnownHeaderName, Method, Mime};
+use crate::{
+  http::{
+    server_framework::{methods::dispatch::_answer_unknown_method, ManualStreamSender},
+    AutoStream, Headers, HttpError, KnownHeaderName, ManualStream, Method, Mime, Response,
+    StatusCode,
+  },
+  misc::bytes_split1,
+};
 
+/// Answers automatic streams whose method isn't `expected` with `405 Method Not Allowed`.
 #[inline]
-fn check_method<E>(expected: Method, received: Method) -> Result<(), E>
+fn check_auto_method<CA, SA>(
+  auto_stream: &mut AutoStream<CA, SA>,
+  expected: Method,
+) -> crate::Result<Option<StatusCode>> {
+  let method = auto_stream.req.method;
+  if method == expected {
+    return Ok(None);
+  }
+  Ok(Some(_answer_unknown_method(false, method, &[expected], &mut auto_stream.req.rrd)?))
+}
+
+/// Answers manual streams whose method isn't `expected` with `405 Method Not Allowed`. Returns
+/// `true` if a response was sent.
+#[inline]
+async fn check_manual_method<CA, S, SA>(
+  manual_stream: &mut ManualStream<CA, S, SA>,
+  expected: Method,
+) -> crate::Result<bool>
 where
-  E: From<crate::Error>,
+  S: ManualStreamSender,
 {
-  if expected != received {
-    return Err(E::from(crate::Error::from(HttpError::UnexpectedHttpMethod { expected })));
+  let method = manual_stream.req.method;
+  if method == expected {
+    return Ok(false);
   }
-  Ok(())
+  let rrd = &mut manual_stream.req.rrd;
+  let status_code = _answer_unknown_method(false, method, &[expected], rrd)?;
+  let version = manual_stream.req.version;
+  manual_stream.stream.send_manual_res(Response { rrd, status_code, version }).await?;
+  Ok(true)
 }
 
 #[inline]
-fn check_json<E>(headers: &Headers, method: Method) -> Result<(), E>
+fn check_json<E>(headers: &Headers) -> Result<(), E>
 where
   E: From<crate::Error>,
 {
-  if headers
-    .get_by_name(KnownHeaderName::ContentType.into())
-    .map_or(true, |el| el.value == Mime::Json.as_str().as_bytes())
-  {
+  check_mime(headers, &[Mime::Json])
+}
+
+/// The media type of `Content-Type` must be one of `mimes`, parameters are ignored.
+#[inline]
+fn check_mime<E>(headers: &Headers, mimes: &[Mime]) -> Result<(), E>
+where
+  E: From<crate::Error>,
+{
+  let has_mime = headers.get_by_name(KnownHeaderName::ContentType.into()).is_some_and(|header| {
+    let media_type = bytes_split1(header.value, b';').next().unwrap_or_default().trim_ascii();
+    mimes.iter().any(|mime| media_type.eq_ignore_ascii_case(mime.as_str().as_bytes()))
+  });
+  if !has_mime {
     return Err(E::from(crate::Error::from(HttpError::UnexpectedContentType)));
   }
-  if method != Method::Post {
-    return Err(E::from(crate::Error::from(HttpError::UnexpectedHttpMethod {
-      expected: Method::Post,
-    })));
-  }
   Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+  use crate::http::{
+    server_framework::methods::{check_json, check_mime},
+    Header, Headers, HttpError, KnownHeaderName, Mime,
+  };
+
+  #[test]
+  fn check_json_requires_a_json_media_type() {
+    assert!(check_json::<crate::Error>(&headers(Some("application/json"))).is_ok());
+    assert!(check_json::<crate::Error>(&headers(Some("Application/JSON; charset=utf-8"))).is_ok());
+    assert!(matches!(
+      check_json::<crate::Error>(&headers(None)),
+      Err(crate::Error::HttpError(HttpError::UnexpectedContentType))
+    ));
+    assert!(matches!(
+      check_json::<crate::Error>(&headers(Some("text/plain"))),
+      Err(crate::Error::HttpError(HttpError::UnexpectedContentType))
+    ));
+  }
+
+  #[test]
+  fn check_mime_accepts_any_of_the_given_types() {
+    let mimes = &[Mime::FormUrlEncoded, Mime::FormData];
+    let form_data = headers(Some("multipart/form-data; boundary=abc"));
+    assert!(check_mime::<crate::Error>(&form_data, mimes).is_ok());
+    let url_encoded = headers(Some("application/x-www-form-urlencoded"));
+    assert!(check_mime::<crate::Error>(&url_encoded, mimes).is_ok());
+    let json = headers(Some("application/json"));
+    assert!(check_mime::<crate::Error>(&json, mimes).is_err());
+  }
+
+  fn headers(content_type: Option<&str>) -> Headers {
+    let mut headers = Headers::new();
+    if let Some(elem) = content_type {
+      headers
+        .push_from_iter(Header::from_name_and_value(
+          KnownHeaderName::ContentType.into(),
+          [elem.as_bytes()],
+        ))
+        .unwrap();
+    }
+    headers
+  }
+}
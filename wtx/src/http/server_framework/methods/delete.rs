@@ -0,0 +1,88 @@
+use crate::{
+  http::{
+    server_framework::{
+      methods::{check_auto_method, check_manual_method, dispatch::MethodEndpoint},
+      Endpoint, EndpointNode, ManualStreamSender, RouteMatch,
+    },
+    AutoStream, ManualStream, Method, OperationMode, StatusCode,
+  },
+  misc::{ArrayVector, FnFut, Vector},
+};
+
+/// Requires a request of type `DELETE`.
+///
+/// Requests of other methods are answered with `405 Method Not Allowed`.
+#[derive(Debug)]
+pub struct Delete<T>(
+  /// Arbitrary type
+  pub T,
+);
+
+/// Creates a new [`Delete`] instance.
+#[inline]
+pub fn delete<A, T>(ty: T) -> Delete<T::Wrapper>
+where
+  T: FnFut<A>,
+{
+  Delete(ty.into_wrapper())
+}
+
+impl<CA, E, S, SA, T> Endpoint<CA, E, S, SA> for Delete<T>
+where
+  E: From<crate::Error>,
+  S: ManualStreamSender,
+  T: Endpoint<CA, E, S, SA>,
+{
+  const OM: OperationMode = T::OM;
+
+  #[inline]
+  async fn auto(
+    &self,
+    auto_stream: &mut AutoStream<CA, SA>,
+    path_defs: (u8, &[RouteMatch]),
+  ) -> Result<StatusCode, E> {
+    if let Some(status_code) = check_auto_method(auto_stream, Method::Delete)? {
+      return Ok(status_code);
+    }
+    self.0.auto(auto_stream, path_defs).await
+  }
+
+  #[inline]
+  async fn manual(
+    &self,
+    mut manual_stream: ManualStream<CA, S, SA>,
+    path_defs: (u8, &[RouteMatch]),
+  ) -> Result<(), E> {
+    if check_manual_method(&mut manual_stream, Method::Delete).await? {
+      return Ok(());
+    }
+    self.0.manual(manual_stream, path_defs).await
+  }
+}
+
+impl<CA, E, S, SA, T> EndpointNode<CA, E, S, SA> for Delete<T>
+where
+  E: From<crate::Error>,
+  S: ManualStreamSender,
+  T: Endpoint<CA, E, S, SA>,
+{
+  const IS_ROUTER: bool = false;
+
+  #[inline]
+  fn paths_indices(
+    &self,
+    _: ArrayVector<RouteMatch, 4>,
+    _: &mut Vector<ArrayVector<RouteMatch, 4>>,
+  ) -> crate::Result<()> {
+    Ok(())
+  }
+}
+
+impl<CA, E, S, SA, T> MethodEndpoint<CA, E, S, SA> for Delete<T>
+where
+  E: From<crate::Error>,
+  S: ManualStreamSender,
+  T: Endpoint<CA, E, S, SA>,
+{
+  const METHOD: Method = Method::Delete;
+}
@@ -0,0 +1,172 @@
+use crate::{
+  http::{
+    server_framework::{Endpoint, EndpointNode, RouteMatch},
+    Header, KnownHeaderName, Method, OperationMode, ReqResBuffer, StatusCode,
+  },
+  misc::{ArrayString, ArrayVector, Vector},
+};
+
+/// Endpoint wrapper that is only called for requests of [`MethodEndpoint::METHOD`].
+pub trait MethodEndpoint<CA, E, S, SA>: Endpoint<CA, E, S, SA>
+where
+  E: From<crate::Error>,
+{
+  /// Expected method
+  const METHOD: Method;
+}
+
+/// Dispatches requests of the same path to one of the inner [`MethodEndpoint`]s according to
+/// the received method.
+///
+/// * `HEAD` is answered by the `GET` endpoint without the response body, which is only possible
+///   in automatic streams.
+/// * `OPTIONS` is answered with `204 No Content` and an `Allow` header.
+/// * Any other unknown method is answered with `405 Method Not Allowed` and an `Allow` header.
+///
+/// All inner endpoints must share the same [`OperationMode`], otherwise the evaluation of
+/// [`Endpoint::OM`] fails at compile time.
+#[derive(Debug)]
+pub struct Methods<T>(
+  /// Tuple of [`MethodEndpoint`]s
+  pub T,
+);
+
+/// Creates a new [`Methods`] instance.
+#[inline]
+pub fn methods<T>(endpoints: T) -> Methods<T> {
+  Methods(endpoints)
+}
+
+impl<CA, E, S, SA, T> EndpointNode<CA, E, S, SA> for Methods<T>
+where
+  E: From<crate::Error>,
+  Methods<T>: Endpoint<CA, E, S, SA>,
+{
+  const IS_ROUTER: bool = false;
+
+  #[inline]
+  fn paths_indices(
+    &self,
+    _: ArrayVector<RouteMatch, 4>,
+    _: &mut Vector<ArrayVector<RouteMatch, 4>>,
+  ) -> crate::Result<()> {
+    Ok(())
+  }
+}
+
+/// Fills `rrb` with the response of a method that isn't handled by any endpoint.
+#[inline]
+pub(crate) fn _answer_unknown_method(
+  has_head: bool,
+  method: Method,
+  methods: &[Method],
+  rrb: &mut ReqResBuffer,
+) -> crate::Result<StatusCode> {
+  let mut allow = ArrayString::<64>::new();
+  for elem in methods {
+    if allow.len() > 0 {
+      allow.push_str(", ")?;
+    }
+    allow.push_str(elem.strings().custom[0])?;
+    if has_head && *elem == Method::Get {
+      allow.push_str(", HEAD")?;
+    }
+  }
+  if allow.len() > 0 {
+    allow.push_str(", ")?;
+  }
+  allow.push_str("OPTIONS")?;
+  rrb.clear();
+  rrb.headers.push_from_iter(Header::from_name_and_value(
+    KnownHeaderName::Allow.into(),
+    [allow.as_str().as_bytes()],
+  ))?;
+  Ok(if method == Method::Options { StatusCode::NoContent } else { StatusCode::MethodNotAllowed })
+}
+
+/// Operation mode shared by all elements, if any.
+#[inline]
+pub(crate) const fn _methods_om(oms: &[OperationMode]) -> OperationMode {
+  let [first, rest @ ..] = oms else {
+    return OperationMode::Auto;
+  };
+  let mut local = rest;
+  while let [elem, tail @ ..] = local {
+    if !matches!((first, elem), (OperationMode::Auto, OperationMode::Auto))
+      && !matches!((first, elem), (OperationMode::Manual, OperationMode::Manual))
+    {
+      panic!("all endpoints of `Methods` must have the same operation mode");
+    }
+    local = tail;
+  }
+  *first
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::http::{server_framework::methods::dispatch::_methods_om, OperationMode};
+
+  #[test]
+  fn methods_om_requires_equal_modes() {
+    const AUTO: OperationMode = _methods_om(&[OperationMode::Auto, OperationMode::Auto]);
+    const MANUAL: OperationMode = _methods_om(&[OperationMode::Manual, OperationMode::Manual]);
+    assert!(matches!((AUTO, MANUAL), (OperationMode::Auto, OperationMode::Manual)));
+  }
+
+  #[should_panic]
+  #[test]
+  fn methods_om_rejects_mixed_modes() {
+    let _om = _methods_om(&[OperationMode::Auto, OperationMode::Manual]);
+  }
+}
+
+#[cfg(all(feature = "_async-tests", feature = "nightly", test))]
+mod async_tests {
+  use crate::{
+    http::{
+      server_framework::{get, methods, post, Router, ServerFrameworkBuilder, StateClean},
+      KnownHeaderName, Method, ReqResBuffer, StatusCode,
+    },
+    misc::{simple_seed, Xorshift64},
+  };
+
+  #[tokio::test]
+  async fn dispatch() {
+    async fn one(state: StateClean<'_, (), (), ReqResBuffer>) -> crate::Result<StatusCode> {
+      state.req.rrd.body.extend_from_copyable_slice(b"get")?;
+      Ok(StatusCode::Ok)
+    }
+
+    async fn two(_: StateClean<'_, (), (), ReqResBuffer>) -> crate::Result<StatusCode> {
+      Ok(StatusCode::Created)
+    }
+
+    let router = Router::paths(crate::paths!(
+      ("/item", methods((get(one), post(two)))),
+      ("/single", get(one))
+    ))
+    .unwrap();
+    let client = ServerFrameworkBuilder::new(router)
+      .without_aux()
+      .test_client(Xorshift64::from(simple_seed()), |err: crate::Error| panic!("{err:?}"))
+      .await
+      .unwrap();
+    let allow = Some(b"GET, HEAD, POST, OPTIONS".as_slice());
+    let single_allow = Some(b"GET, OPTIONS".as_slice());
+    for (path, method, status_code, allow, body) in [
+      ("/item", Method::Get, StatusCode::Ok, None, b"get".as_slice()),
+      ("/item", Method::Head, StatusCode::Ok, None, b"".as_slice()),
+      ("/item", Method::Post, StatusCode::Created, None, b"".as_slice()),
+      ("/item", Method::Options, StatusCode::NoContent, allow, b"".as_slice()),
+      ("/item", Method::Delete, StatusCode::MethodNotAllowed, allow, b"".as_slice()),
+      ("/single", Method::Get, StatusCode::Ok, None, b"get".as_slice()),
+      ("/single", Method::Post, StatusCode::MethodNotAllowed, single_allow, b"".as_slice()),
+    ] {
+      let res = client.req(method, path).send().await.unwrap();
+      assert_eq!(res.status_code, status_code);
+      let header = res.rrd.headers.get_by_name(KnownHeaderName::Allow.into());
+      assert_eq!(header.map(|el| el.value), allow);
+      assert_eq!(res.rrd.body.as_slice(), body);
+    }
+  }
+}
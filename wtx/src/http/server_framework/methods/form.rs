@@ -0,0 +1,92 @@
+use crate::{
+  http::{
+    server_framework::{
+      methods::{check_auto_method, check_manual_method, check_mime, dispatch::MethodEndpoint},
+      Endpoint, EndpointNode, ManualStreamSender, RouteMatch,
+    },
+    AutoStream, ManualStream, Method, Mime, OperationMode, StatusCode,
+  },
+  misc::{ArrayVector, FnFut, Vector},
+};
+
+const MIMES: &[Mime] = &[Mime::FormUrlEncoded, Mime::FormData];
+
+/// Requires a request of type `POST` with an URL-encoded or a multipart form MIME.
+///
+/// Requests of other methods are answered with `405 Method Not Allowed`.
+#[derive(Debug)]
+pub struct Form<T>(
+  /// Arbitrary type
+  pub T,
+);
+
+/// Creates a new [`Form`] instance.
+#[inline]
+pub fn form<A, T>(ty: T) -> Form<T::Wrapper>
+where
+  T: FnFut<A>,
+{
+  Form(ty.into_wrapper())
+}
+
+impl<CA, E, S, SA, T> Endpoint<CA, E, S, SA> for Form<T>
+where
+  E: From<crate::Error>,
+  S: ManualStreamSender,
+  T: Endpoint<CA, E, S, SA>,
+{
+  const OM: OperationMode = T::OM;
+
+  #[inline]
+  async fn auto(
+    &self,
+    auto_stream: &mut AutoStream<CA, SA>,
+    path_defs: (u8, &[RouteMatch]),
+  ) -> Result<StatusCode, E> {
+    if let Some(status_code) = check_auto_method(auto_stream, Method::Post)? {
+      return Ok(status_code);
+    }
+    check_mime(&auto_stream.req.rrd.headers, MIMES)?;
+    self.0.auto(auto_stream, path_defs).await
+  }
+
+  #[inline]
+  async fn manual(
+    &self,
+    mut manual_stream: ManualStream<CA, S, SA>,
+    path_defs: (u8, &[RouteMatch]),
+  ) -> Result<(), E> {
+    if check_manual_method(&mut manual_stream, Method::Post).await? {
+      return Ok(());
+    }
+    check_mime(&manual_stream.req.rrd.headers, MIMES)?;
+    self.0.manual(manual_stream, path_defs).await
+  }
+}
+
+impl<CA, E, S, SA, T> EndpointNode<CA, E, S, SA> for Form<T>
+where
+  E: From<crate::Error>,
+  S: ManualStreamSender,
+  T: Endpoint<CA, E, S, SA>,
+{
+  const IS_ROUTER: bool = false;
+
+  #[inline]
+  fn paths_indices(
+    &self,
+    _: ArrayVector<RouteMatch, 4>,
+    _: &mut Vector<ArrayVector<RouteMatch, 4>>,
+  ) -> crate::Result<()> {
+    Ok(())
+  }
+}
+
+impl<CA, E, S, SA, T> MethodEndpoint<CA, E, S, SA> for Form<T>
+where
+  E: From<crate::Error>,
+  S: ManualStreamSender,
+  T: Endpoint<CA, E, S, SA>,
+{
+  const METHOD: Method = Method::Post;
+}
@@ -1,12 +1,17 @@
 use crate::{
   http::{
-    server_framework::{methods::check_method, Endpoint, EndpointNode, RouteMatch},
+    server_framework::{
+      methods::{check_auto_method, check_manual_method, dispatch::MethodEndpoint},
+      Endpoint, EndpointNode, ManualStreamSender, RouteMatch,
+    },
     AutoStream, ManualStream, Method, OperationMode, StatusCode,
   },
   misc::{ArrayVector, FnFut, Vector},
 };
 
 /// Requires a request of type `POST`.
+///
+/// Requests of other methods are answered with `405 Method Not Allowed`.
 #[derive(Debug)]
 pub struct Post<T>(
   /// Arbitrary type
@@ -25,6 +30,7 @@ where
 impl<CA, E, S, SA, T> Endpoint<CA, E, S, SA> for Post<T>
 where
   E: From<crate::Error>,
+  S: ManualStreamSender,
   T: Endpoint<CA, E, S, SA>,
 {
   const OM: OperationMode = T::OM;
@@ -35,17 +41,21 @@ where
     auto_stream: &mut AutoStream<CA, SA>,
     path_defs: (u8, &[RouteMatch]),
   ) -> Result<StatusCode, E> {
-    check_method(Method::Post, auto_stream.req.method)?;
+    if let Some(status_code) = check_auto_method(auto_stream, Method::Post)? {
+      return Ok(status_code);
+    }
     self.0.auto(auto_stream, path_defs).await
   }
 
   #[inline]
   async fn manual(
     &self,
-    manual_stream: ManualStream<CA, S, SA>,
+    mut manual_stream: ManualStream<CA, S, SA>,
     path_defs: (u8, &[RouteMatch]),
   ) -> Result<(), E> {
-    check_method(Method::Post, manual_stream.req.method)?;
+    if check_manual_method(&mut manual_stream, Method::Post).await? {
+      return Ok(());
+    }
     self.0.manual(manual_stream, path_defs).await
   }
 }
@@ -53,6 +63,7 @@ where
 impl<CA, E, S, SA, T> EndpointNode<CA, E, S, SA> for Post<T>
 where
   E: From<crate::Error>,
+  S: ManualStreamSender,
   T: Endpoint<CA, E, S, SA>,
 {
   const IS_ROUTER: bool = false;
@@ -66,3 +77,12 @@ where
     Ok(())
   }
 }
+
+impl<CA, E, S, SA, T> MethodEndpoint<CA, E, S, SA> for Post<T>
+where
+  E: From<crate::Error>,
+  S: ManualStreamSender,
+  T: Endpoint<CA, E, S, SA>,
+{
+  const METHOD: Method = Method::Post;
+}
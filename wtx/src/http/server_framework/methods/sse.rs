@@ -0,0 +1,314 @@
+use crate::{
+  http::{
+    server_framework::{Endpoint, EndpointNode, RouteMatch, ServerFrameworkError},
+    Header, KnownHeaderName, ManualStream, Method, Mime, OperationMode, StatusCode,
+  },
+  http2::{Http2Buffer, Http2Data, Http2SendStatus, SendDataMode, ServerStream},
+  misc::{
+    _select, sleep, str_split1, ArrayVector, Either, FnFut, LeaseMut, Lock, RefCounter,
+    StreamWriter, Vector,
+  },
+};
+use core::{fmt::Write, time::Duration};
+
+/// Requires a request of type `GET` that is answered with a stream of Server-Sent Events.
+///
+/// The handler receives a [`ManualStream`] whose stream is a [`SseSender`]. Comments are
+/// periodically sent to keep the connection alive and the handler is dropped as soon as the
+/// client disconnects.
+#[derive(Debug)]
+pub struct Sse<T> {
+  handler: T,
+  keep_alive: Duration,
+}
+
+impl<T> Sse<T> {
+  /// Interval between keep-alive comments. Defaults to 15 seconds.
+  #[inline]
+  #[must_use]
+  pub fn keep_alive(mut self, elem: Duration) -> Self {
+    self.keep_alive = elem;
+    self
+  }
+}
+
+/// Creates a new [`Sse`] instance.
+#[inline]
+pub fn sse<T>(handler: T) -> Sse<T> {
+  Sse { handler, keep_alive: Duration::from_secs(15) }
+}
+
+impl<CA, E, HB, HD, SA, SW, T> Endpoint<CA, E, ServerStream<HD>, SA> for Sse<T>
+where
+  E: From<crate::Error>,
+  HB: LeaseMut<Http2Buffer>,
+  HD: RefCounter,
+  HD::Item: Lock<Resource = Http2Data<HB, SW, false>>,
+  SW: StreamWriter,
+  T: FnFut<(ManualStream<CA, SseSender<HD>, SA>,), Result = Result<(), E>>,
+{
+  const OM: OperationMode = OperationMode::Manual;
+
+  #[inline]
+  async fn manual(
+    &self,
+    mut manual_stream: ManualStream<CA, ServerStream<HD>, SA>,
+    _: (u8, &[RouteMatch]),
+  ) -> Result<(), E> {
+    let rrd = &mut manual_stream.req.rrd;
+    rrd.headers.clear();
+    if manual_stream.req.method != Method::Get {
+      rrd.headers.push_from_iter(Header::from_name_and_value(
+        KnownHeaderName::Allow.into(),
+        [b"GET".as_slice()],
+      ))?;
+      let mut common = manual_stream.stream.common();
+      let _ = common.send_headers(&rrd.headers, true, StatusCode::MethodNotAllowed).await?;
+      return Ok(());
+    }
+    rrd.headers.push_from_iter_many([
+      Header::from_name_and_value(
+        KnownHeaderName::CacheControl.into(),
+        [b"no-cache".as_slice()].into_iter(),
+      ),
+      Header::from_name_and_value(
+        KnownHeaderName::ContentType.into(),
+        [Mime::EventStream.as_str().as_bytes()].into_iter(),
+      ),
+    ])?;
+    let mut common = manual_stream.stream.common();
+    if common.send_headers(&rrd.headers, false, StatusCode::Ok).await?.is_closed() {
+      return Ok(());
+    }
+    rrd.headers.clear();
+    let mut keep_alive_stream = manual_stream.stream.clone();
+    let handler_fut = self.handler.call((ManualStream {
+      conn_aux: manual_stream.conn_aux,
+      peer: manual_stream.peer,
+      protocol: manual_stream.protocol,
+      req: manual_stream.req,
+      stream: SseSender { buffer: Vector::new(), stream: manual_stream.stream },
+      stream_aux: manual_stream.stream_aux,
+    },));
+    let keep_alive_fut = async {
+      loop {
+        sleep(self.keep_alive).await?;
+        let data = SendDataMode::scattered_data_frames(b":\n\n".as_slice());
+        if keep_alive_stream.common().send_data(data, false).await?.is_closed() {
+          return crate::Result::Ok(());
+        }
+      }
+    };
+    match _select(handler_fut, keep_alive_fut).await {
+      Either::Left(rslt) => {
+        rslt?;
+        let data = SendDataMode::scattered_data_frames([].as_slice());
+        let _ = keep_alive_stream.common().send_data(data, true).await?;
+      }
+      Either::Right(rslt) => rslt?,
+    }
+    Ok(())
+  }
+}
+
+impl<CA, E, HB, HD, SA, SW, T> EndpointNode<CA, E, ServerStream<HD>, SA> for Sse<T>
+where
+  E: From<crate::Error>,
+  HB: LeaseMut<Http2Buffer>,
+  HD: RefCounter,
+  HD::Item: Lock<Resource = Http2Data<HB, SW, false>>,
+  SW: StreamWriter,
+  T: FnFut<(ManualStream<CA, SseSender<HD>, SA>,), Result = Result<(), E>>,
+{
+  const IS_ROUTER: bool = false;
+
+  #[inline]
+  fn paths_indices(
+    &self,
+    _: ArrayVector<RouteMatch, 4>,
+    _: &mut Vector<ArrayVector<RouteMatch, 4>>,
+  ) -> crate::Result<()> {
+    Ok(())
+  }
+}
+
+/// A single Server-Sent Event.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SseEvent<'any> {
+  data: &'any str,
+  event: Option<&'any str>,
+  id: Option<&'any str>,
+  retry: Option<Duration>,
+}
+
+impl<'any> SseEvent<'any> {
+  /// Event with the given `data`. Line breaks are sent as multiple `data` fields.
+  #[inline]
+  pub const fn new(data: &'any str) -> Self {
+    Self { data, event: None, id: None, retry: None }
+  }
+
+  /// Event type. Can't contain line breaks.
+  #[inline]
+  #[must_use]
+  pub const fn event(mut self, elem: &'any str) -> Self {
+    self.event = Some(elem);
+    self
+  }
+
+  /// Last event ID that is sent back by clients when reconnecting. Can't contain line breaks or
+  /// null characters.
+  #[inline]
+  #[must_use]
+  pub const fn id(mut self, elem: &'any str) -> Self {
+    self.id = Some(elem);
+    self
+  }
+
+  /// Reconnection time used by clients.
+  #[inline]
+  #[must_use]
+  pub const fn retry(mut self, elem: Duration) -> Self {
+    self.retry = Some(elem);
+    self
+  }
+
+  #[inline]
+  fn encode(&self, buffer: &mut Vector<u8>) -> crate::Result<()> {
+    if let Some(elem) = self.event {
+      check_field(elem, false)?;
+      buffer.write_fmt(format_args!("event: {elem}\n"))?;
+    }
+    if let Some(elem) = self.id {
+      check_field(elem, true)?;
+      buffer.write_fmt(format_args!("id: {elem}\n"))?;
+    }
+    if let Some(elem) = self.retry {
+      buffer.write_fmt(format_args!("retry: {}\n", elem.as_millis()))?;
+    }
+    for line in str_split1(self.data, b'\n') {
+      let line = line.strip_suffix('\r').unwrap_or(line);
+      for sub_line in str_split1(line, b'\r') {
+        buffer.write_fmt(format_args!("data: {sub_line}\n"))?;
+      }
+    }
+    buffer.push(b'\n')?;
+    Ok(())
+  }
+}
+
+/// Sends Server-Sent Events to the client.
+#[derive(Debug)]
+pub struct SseSender<HD> {
+  buffer: Vector<u8>,
+  stream: ServerStream<HD>,
+}
+
+impl<HB, HD, SW> SseSender<HD>
+where
+  HB: LeaseMut<Http2Buffer>,
+  HD: RefCounter,
+  HD::Item: Lock<Resource = Http2Data<HB, SW, false>>,
+  SW: StreamWriter,
+{
+  /// Sends a comment, which is ignored by clients. Can't contain line breaks.
+  #[inline]
+  pub async fn comment(&mut self, comment: &str) -> crate::Result<Http2SendStatus> {
+    check_field(comment, false)?;
+    self.buffer.clear();
+    self.buffer.write_fmt(format_args!(": {comment}\n\n"))?;
+    self.send_buffer().await
+  }
+
+  /// Sends `event`. The returned status indicates whether the client is still connected.
+  #[inline]
+  pub async fn send(&mut self, event: &SseEvent<'_>) -> crate::Result<Http2SendStatus> {
+    self.buffer.clear();
+    event.encode(&mut self.buffer)?;
+    self.send_buffer().await
+  }
+
+  #[inline]
+  async fn send_buffer(&mut self) -> crate::Result<Http2SendStatus> {
+    let data = SendDataMode::scattered_data_frames(self.buffer.as_slice());
+    self.stream.common().send_data(data, false).await
+  }
+}
+
+#[inline]
+fn check_field(field: &str, is_id: bool) -> crate::Result<()> {
+  if field.bytes().any(|byte| byte == b'\n' || byte == b'\r' || (is_id && byte == 0)) {
+    return Err(ServerFrameworkError::InvalidSseField.into());
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{http::server_framework::SseEvent, misc::Vector};
+  use core::time::Duration;
+
+  #[test]
+  fn encode() {
+    let mut buffer = Vector::new();
+    SseEvent::new("first\r\nsecond\rthird\n")
+      .event("update")
+      .id("1")
+      .retry(Duration::from_secs(3))
+      .encode(&mut buffer)
+      .unwrap();
+    assert_eq!(
+      buffer.as_slice(),
+      b"event: update\nid: 1\nretry: 3000\ndata: first\ndata: second\ndata: third\ndata: \n\n"
+    );
+    buffer.clear();
+    assert!(SseEvent::new("").id("a\nb").encode(&mut buffer).is_err());
+  }
+}
+
+#[cfg(all(feature = "_async-tests", feature = "nightly", test))]
+mod async_tests {
+  use crate::{
+    http::{
+      server_framework::{sse, Router, ServerFrameworkBuilder, SseEvent, SseSender},
+      ManualStream, StatusCode,
+    },
+    http2::{Http2Buffer, Http2DataTokio},
+    misc::{simple_seed, Xorshift64},
+  };
+  use core::time::Duration;
+  use tokio::io::{DuplexStream, WriteHalf};
+
+  #[tokio::test]
+  async fn events() {
+    async fn handler(
+      mut manual_stream: ManualStream<
+        (),
+        SseSender<Http2DataTokio<Http2Buffer, WriteHalf<DuplexStream>, false>>,
+        (),
+      >,
+    ) -> crate::Result<()> {
+      let _ = manual_stream.stream.send(&SseEvent::new("a").id("1")).await?;
+      tokio::time::sleep(Duration::from_millis(150)).await;
+      let _ = manual_stream.stream.send(&SseEvent::new("b")).await?;
+      Ok(())
+    }
+
+    let router = Router::paths(crate::paths!((
+      "/events",
+      sse(handler).keep_alive(Duration::from_millis(100))
+    )))
+    .unwrap();
+    let client = ServerFrameworkBuilder::new(router)
+      .without_aux()
+      .test_client(Xorshift64::from(simple_seed()), |err: crate::Error| panic!("{err:?}"))
+      .await
+      .unwrap();
+    let res = tokio::time::timeout(Duration::from_secs(2), client.get("/events").send())
+      .await
+      .unwrap()
+      .unwrap();
+    assert_eq!(res.status_code, StatusCode::Ok);
+    assert_eq!(res.rrd.body.as_slice(), b"id: 1\ndata: a\n\n:\n\ndata: b\n\n");
+  }
+}
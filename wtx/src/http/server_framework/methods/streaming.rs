@@ -0,0 +1,235 @@
+use crate::{
+  http::{
+    server_framework::{Endpoint, EndpointNode, RouteMatch},
+    Headers, ManualStream, OperationMode, StatusCode,
+  },
+  http2::{Http2Buffer, Http2Data, Http2RecvStatus, Http2SendStatus, SendDataMode, ServerStream},
+  misc::{ArrayVector, FnFut, LeaseMut, Lock, RefCounter, StreamWriter, Vector},
+};
+use core::mem;
+
+/// Gives the handler an asynchronous reader of the request body and an asynchronous writer of
+/// the response body, which means that neither of them have to fit in memory.
+///
+/// Request bodies aren't limited by the maximum body length of the connection. The remote peer
+/// is instead only allowed to send more data after the handler consumes what was previously
+/// received, which means that at most the initial window length is buffered. Middlewares of
+/// routers are still applied to the request headers.
+#[derive(Debug)]
+pub struct Streaming<T>(
+  /// Arbitrary type
+  pub T,
+);
+
+/// Creates a new [`Streaming`] instance.
+#[inline]
+pub fn streaming<T>(handler: T) -> Streaming<T> {
+  Streaming(handler)
+}
+
+impl<CA, E, HB, HD, SA, SW, T> Endpoint<CA, E, ServerStream<HD>, SA> for Streaming<T>
+where
+  E: From<crate::Error>,
+  HB: LeaseMut<Http2Buffer>,
+  HD: RefCounter,
+  HD::Item: Lock<Resource = Http2Data<HB, SW, false>>,
+  SW: StreamWriter,
+  T: FnFut<(ManualStream<CA, BodyStream<HD>, SA>,), Result = Result<(), E>>,
+{
+  const OM: OperationMode = OperationMode::Manual;
+
+  #[inline]
+  async fn manual(
+    &self,
+    mut manual_stream: ManualStream<CA, ServerStream<HD>, SA>,
+    _: (u8, &[RouteMatch]),
+  ) -> Result<(), E> {
+    manual_stream.stream.common().enable_body_streaming().await?;
+    let buffered = mem::take(&mut manual_stream.req.rrd.body);
+    let reader = BodyReader { buffered, is_finished: false, stream: manual_stream.stream.clone() };
+    let writer = BodyWriter { stream: manual_stream.stream };
+    self
+      .0
+      .call((ManualStream {
+        conn_aux: manual_stream.conn_aux,
+        peer: manual_stream.peer,
+        protocol: manual_stream.protocol,
+        req: manual_stream.req,
+        stream: BodyStream { reader, writer },
+        stream_aux: manual_stream.stream_aux,
+      },))
+      .await
+  }
+}
+
+impl<CA, E, HB, HD, SA, SW, T> EndpointNode<CA, E, ServerStream<HD>, SA> for Streaming<T>
+where
+  E: From<crate::Error>,
+  HB: LeaseMut<Http2Buffer>,
+  HD: RefCounter,
+  HD::Item: Lock<Resource = Http2Data<HB, SW, false>>,
+  SW: StreamWriter,
+  T: FnFut<(ManualStream<CA, BodyStream<HD>, SA>,), Result = Result<(), E>>,
+{
+  const IS_ROUTER: bool = false;
+
+  #[inline]
+  fn paths_indices(
+    &self,
+    _: ArrayVector<RouteMatch, 4>,
+    _: &mut Vector<ArrayVector<RouteMatch, 4>>,
+  ) -> crate::Result<()> {
+    Ok(())
+  }
+}
+
+/// Request body reader and response body writer of a [`Streaming`] endpoint.
+#[derive(Debug)]
+pub struct BodyStream<HD> {
+  /// See [`BodyReader`].
+  pub reader: BodyReader<HD>,
+  /// See [`BodyWriter`].
+  pub writer: BodyWriter<HD>,
+}
+
+/// Reads the request body in chunks.
+#[derive(Debug)]
+pub struct BodyReader<HD> {
+  buffered: Vector<u8>,
+  is_finished: bool,
+  stream: ServerStream<HD>,
+}
+
+impl<HB, HD, SW> BodyReader<HD>
+where
+  HB: LeaseMut<Http2Buffer>,
+  HD: RefCounter,
+  HD::Item: Lock<Resource = Http2Data<HB, SW, false>>,
+  SW: StreamWriter,
+{
+  /// Returns the next chunk of data or `None` if the whole body was already received.
+  ///
+  /// An error is returned if the stream or the connection is closed before the end of the body.
+  #[inline]
+  pub async fn read(&mut self) -> crate::Result<Option<Vector<u8>>> {
+    if !self.buffered.is_empty() {
+      return Ok(Some(mem::take(&mut self.buffered)));
+    }
+    if self.is_finished {
+      return Ok(None);
+    }
+    match self.stream.common().recv_data().await? {
+      Http2RecvStatus::ClosedConnection | Http2RecvStatus::ClosedStream => {
+        self.is_finished = true;
+        Err(crate::Error::ClosedConnection)
+      }
+      Http2RecvStatus::Eos(elem) => {
+        self.is_finished = true;
+        Ok(if elem.is_empty() { None } else { Some(elem) })
+      }
+      Http2RecvStatus::Ongoing(elem) => Ok(Some(elem)),
+    }
+  }
+}
+
+/// Writes the response in parts. Headers must be sent first and the stream must be closed with
+/// [`BodyWriter::finish`].
+#[derive(Debug)]
+pub struct BodyWriter<HD> {
+  stream: ServerStream<HD>,
+}
+
+impl<HB, HD, SW> BodyWriter<HD>
+where
+  HB: LeaseMut<Http2Buffer>,
+  HD: RefCounter,
+  HD::Item: Lock<Resource = Http2Data<HB, SW, false>>,
+  SW: StreamWriter,
+{
+  /// Closes the stream with optional trailers.
+  #[inline]
+  pub async fn finish(&mut self, trailers: &Headers) -> crate::Result<Http2SendStatus> {
+    if trailers.headers_len() == 0 {
+      let data = SendDataMode::scattered_data_frames([].as_slice());
+      return self.stream.common().send_data(data, true).await;
+    }
+    self.stream.common().send_trailers(trailers).await
+  }
+
+  /// Sends the status code and the headers of the response.
+  #[inline]
+  pub async fn send_headers(
+    &mut self,
+    headers: &Headers,
+    status_code: StatusCode,
+  ) -> crate::Result<Http2SendStatus> {
+    self.stream.common().send_headers(headers, false, status_code).await
+  }
+
+  /// Sends `data` as one or more DATA frames. Waits while the window of the remote peer is
+  /// exhausted.
+  #[inline]
+  pub async fn write(&mut self, data: &[u8]) -> crate::Result<Http2SendStatus> {
+    if data.is_empty() {
+      return Ok(Http2SendStatus::Ok);
+    }
+    self.stream.common().send_data(SendDataMode::scattered_data_frames(data), false).await
+  }
+}
+
+#[cfg(all(feature = "_async-tests", feature = "nightly", test))]
+mod tests {
+  use crate::{
+    http::{
+      server_framework::{streaming, BodyStream, Router, ServerFrameworkBuilder},
+      Headers, ManualStream, StatusCode,
+    },
+    http2::{Http2Buffer, Http2DataTokio},
+    misc::{simple_seed, Vector, Xorshift64},
+  };
+  use core::time::Duration;
+  use tokio::io::{DuplexStream, WriteHalf};
+
+  #[tokio::test]
+  async fn bypasses_max_body_len() {
+    async fn handler(
+      mut manual_stream: ManualStream<
+        (),
+        BodyStream<Http2DataTokio<Http2Buffer, WriteHalf<DuplexStream>, false>>,
+        (),
+      >,
+    ) -> crate::Result<()> {
+      let BodyStream { reader, writer } = &mut manual_stream.stream;
+      let mut body = Vector::new();
+      while let Some(chunk) = reader.read().await? {
+        body.extend_from_copyable_slice(&chunk)?;
+      }
+      let _ = writer.send_headers(&Headers::new(), StatusCode::Ok).await?;
+      for chunk in body.chunks(64 * 1024) {
+        let _ = writer.write(chunk).await?;
+      }
+      let _ = writer.finish(&Headers::new()).await?;
+      Ok(())
+    }
+
+    let router = Router::paths(crate::paths!(("/echo", streaming(handler)))).unwrap();
+    let client = ServerFrameworkBuilder::new(router)
+      .initial_window_len(16 * 1024)
+      .max_body_len(64 * 1024)
+      .without_aux()
+      .test_client(Xorshift64::from(simple_seed()), |err: crate::Error| panic!("{err:?}"))
+      .await
+      .unwrap();
+    // Greater than the limit of the server but smaller than the default limit of the client
+    let body: alloc::vec::Vec<u8> = (0..120_000u32).map(|idx| idx.to_be_bytes()[3]).collect();
+    let res = tokio::time::timeout(
+      Duration::from_secs(5),
+      client.post("/echo").body(&body).unwrap().send(),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    assert_eq!(res.status_code, StatusCode::Ok);
+    assert_eq!(res.rrd.body.as_slice(), body.as_slice());
+  }
+}
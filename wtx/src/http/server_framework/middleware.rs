@@ -1,7 +1,11 @@
 use crate::http::{ReqResBuffer, Request, Response, StatusCode};
-use core::{future::Future, ops::ControlFlow};
+use core::{future::Future, net::IpAddr, ops::ControlFlow};
 
 /// Request middleware
+///
+/// Middlewares of a tuple are called in sequence. If the request hook of an element halts the
+/// request, then the response hooks of the preceding elements are still called with the halted
+/// response.
 pub trait Middleware<CA, E, SA>
 where
   E: From<crate::Error>,
@@ -12,11 +16,12 @@ where
   /// Auxiliary structure
   fn aux(&self) -> Self::Aux;
 
-  /// Modifies or halts requests.
+  /// Modifies or halts requests sent by `peer`.
   fn req(
     &self,
     conn_aux: &mut CA,
     mw_aux: &mut Self::Aux,
+    peer: IpAddr,
     req: &mut Request<ReqResBuffer>,
     stream_aux: &mut SA,
   ) -> impl Future<Output = Result<ControlFlow<StatusCode, ()>, E>>;
@@ -29,4 +34,84 @@ where
     res: Response<&mut ReqResBuffer>,
     stream_aux: &mut SA,
   ) -> impl Future<Output = Result<ControlFlow<StatusCode, ()>, E>>;
+
+  /// Span that instruments the endpoint of a request. Called after the request hooks.
+  #[cfg(feature = "tracing")]
+  #[inline]
+  fn span(&self, _: &Self::Aux) -> Option<tracing::Span> {
+    None
+  }
+}
+
+#[cfg(all(feature = "_async-tests", test))]
+pub(crate) mod tests {
+  use crate::http::{
+    server_framework::{CorsMiddleware, Middleware},
+    KnownHeaderName, Method, ReqResBuffer, Request, Response, StatusCode,
+  };
+  use core::{
+    net::{IpAddr, Ipv4Addr},
+    ops::ControlFlow,
+  };
+
+  /// Halts every request with the inner status code.
+  pub(crate) struct Halt(pub(crate) StatusCode);
+
+  impl<CA, SA> Middleware<CA, crate::Error, SA> for Halt {
+    type Aux = ();
+
+    fn aux(&self) -> Self::Aux {}
+
+    async fn req(
+      &self,
+      _: &mut CA,
+      _: &mut Self::Aux,
+      _: IpAddr,
+      _: &mut Request<ReqResBuffer>,
+      _: &mut SA,
+    ) -> crate::Result<ControlFlow<StatusCode, ()>> {
+      Ok(ControlFlow::Break(self.0))
+    }
+
+    async fn res(
+      &self,
+      _: &mut CA,
+      _: &mut Self::Aux,
+      _: Response<&mut ReqResBuffer>,
+      _: &mut SA,
+    ) -> crate::Result<ControlFlow<StatusCode, ()>> {
+      Ok(ControlFlow::Continue(()))
+    }
+  }
+
+  #[tokio::test]
+  async fn halted_tuples_call_preceding_responses() {
+    let mws = (CorsMiddleware::permissive(), Halt(StatusCode::Forbidden));
+    let mut req = Request::http2(Method::Get, ReqResBuffer::empty());
+    let mut aux = Middleware::<(), crate::Error, ()>::aux(&mws);
+    let peer = IpAddr::V4(Ipv4Addr::LOCALHOST);
+    let cf =
+      Middleware::<(), crate::Error, ()>::req(&mws, &mut (), &mut aux, peer, &mut req, &mut ())
+        .await
+        .unwrap();
+    assert_eq!(cf, ControlFlow::Break(StatusCode::Forbidden));
+    let name = KnownHeaderName::AccessControlAllowOrigin.into();
+    assert_eq!(req.rrd.headers.get_by_name(name).unwrap().value, b"*");
+  }
+
+  #[tokio::test]
+  async fn halted_tuples_skip_subsequent_elements() {
+    let mws = (Halt(StatusCode::Forbidden), CorsMiddleware::permissive());
+    let mut req = Request::http2(Method::Get, ReqResBuffer::empty());
+    let mut aux = Middleware::<(), crate::Error, ()>::aux(&mws);
+    let peer = IpAddr::V4(Ipv4Addr::LOCALHOST);
+    let cf =
+      Middleware::<(), crate::Error, ()>::req(&mws, &mut (), &mut aux, peer, &mut req, &mut ())
+        .await
+        .unwrap();
+    assert_eq!(cf, ControlFlow::Break(StatusCode::Forbidden));
+    assert_eq!(aux.1, None);
+    let name = KnownHeaderName::AccessControlAllowOrigin.into();
+    assert!(req.rrd.headers.get_by_name(name).is_none());
+  }
 }
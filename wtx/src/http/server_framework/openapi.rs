@@ -0,0 +1,523 @@
+use crate::{
+  http::{
+    server_framework::{
+      Delete, Endpoint, EndpointNode, Form, Get, Json, MethodEndpoint, Patch, Post, Put,
+      RouteMatch, Router, Scoped, Sse, Streaming, WebSocket,
+    },
+    AutoStream, ManualStream, Method, Mime, OperationMode, StatusCode,
+  },
+  misc::{str_split1, ArrayVector, Vector},
+};
+use alloc::{
+  string::{String, ToString},
+  vec::Vec,
+};
+use serde_json::{json, Map, Value};
+
+/// Generates OpenAPI 3.1 documents from the routes of a [`Router`].
+///
+/// Every route and method wrapper is listed along with the parameters of its path. Summaries,
+/// descriptions, tags, request bodies and responses can be attached to individual operations
+/// through [`documented`].
+///
+/// Because documents are built from the same structures that serve requests, a test that
+/// compares the output with a checked-in file detects any divergence.
+#[derive(Debug)]
+pub struct OpenApi {
+  description: Option<&'static str>,
+  servers: Vector<&'static str>,
+  title: &'static str,
+  version: &'static str,
+}
+
+impl OpenApi {
+  /// `title` and `version` of the API.
+  #[inline]
+  pub const fn new(title: &'static str, version: &'static str) -> Self {
+    Self { description: None, servers: Vector::new(), title, version }
+  }
+
+  /// Description of the API.
+  #[inline]
+  #[must_use]
+  pub fn description(mut self, elem: &'static str) -> Self {
+    self.description = Some(elem);
+    self
+  }
+
+  /// Adds the base URL of a server.
+  #[inline]
+  pub fn server(mut self, url: &'static str) -> crate::Result<Self> {
+    self.servers.push(url)?;
+    Ok(self)
+  }
+
+  /// JSON document of all operations of `node`.
+  #[inline]
+  pub fn document<T>(&self, node: &T) -> crate::Result<Value>
+  where
+    T: OpenApiNode,
+  {
+    let mut paths = Map::new();
+    node.openapi(&mut String::new(), None, &mut paths)?;
+    let mut info = Map::new();
+    let _ = info.insert("title".into(), self.title.into());
+    let _ = info.insert("version".into(), self.version.into());
+    if let Some(elem) = self.description {
+      let _ = info.insert("description".into(), elem.into());
+    }
+    let mut document = Map::new();
+    let _ = document.insert("openapi".into(), "3.1.0".into());
+    let _ = document.insert("info".into(), info.into());
+    if !self.servers.is_empty() {
+      let servers = self.servers.iter().map(|elem| json!({ "url": elem })).collect();
+      let _ = document.insert("servers".into(), Value::Array(servers));
+    }
+    let _ = document.insert("paths".into(), paths.into());
+    Ok(document.into())
+  }
+}
+
+/// Metadata of a single operation.
+#[derive(Debug, Default)]
+pub struct OpenApiOperation {
+  description: Option<&'static str>,
+  operation_id: Option<&'static str>,
+  request_body: Option<(Mime, &'static str)>,
+  responses: Vector<(StatusCode, &'static str, Option<(Mime, &'static str)>)>,
+  summary: Option<&'static str>,
+  tags: Vector<&'static str>,
+}
+
+impl OpenApiOperation {
+  /// Operation without metadata.
+  #[inline]
+  pub const fn new() -> Self {
+    Self {
+      description: None,
+      operation_id: None,
+      request_body: None,
+      responses: Vector::new(),
+      summary: None,
+      tags: Vector::new(),
+    }
+  }
+
+  /// Detailed explanation.
+  #[inline]
+  #[must_use]
+  pub fn description(mut self, elem: &'static str) -> Self {
+    self.description = Some(elem);
+    self
+  }
+
+  /// Unique identifier.
+  #[inline]
+  #[must_use]
+  pub fn operation_id(mut self, elem: &'static str) -> Self {
+    self.operation_id = Some(elem);
+    self
+  }
+
+  /// Request body of type `mime` described by the JSON Schema `schema`.
+  #[inline]
+  #[must_use]
+  pub fn request_body(mut self, mime: Mime, schema: &'static str) -> Self {
+    self.request_body = Some((mime, schema));
+    self
+  }
+
+  /// Possible response with an optional body of type `Mime` described by a JSON Schema.
+  #[inline]
+  pub fn response(
+    mut self,
+    status_code: StatusCode,
+    description: &'static str,
+    body: Option<(Mime, &'static str)>,
+  ) -> crate::Result<Self> {
+    self.responses.push((status_code, description, body))?;
+    Ok(self)
+  }
+
+  /// Short summary.
+  #[inline]
+  #[must_use]
+  pub fn summary(mut self, elem: &'static str) -> Self {
+    self.summary = Some(elem);
+    self
+  }
+
+  /// Adds a tag used to group operations.
+  #[inline]
+  pub fn tag(mut self, elem: &'static str) -> crate::Result<Self> {
+    self.tags.push(elem)?;
+    Ok(self)
+  }
+
+  #[inline]
+  fn to_json(&self, path: &str) -> crate::Result<Value> {
+    let mut rslt = Map::new();
+    if let Some(elem) = self.operation_id {
+      let _ = rslt.insert("operationId".into(), elem.into());
+    }
+    if let Some(elem) = self.summary {
+      let _ = rslt.insert("summary".into(), elem.into());
+    }
+    if let Some(elem) = self.description {
+      let _ = rslt.insert("description".into(), elem.into());
+    }
+    if !self.tags.is_empty() {
+      let _ = rslt.insert("tags".into(), self.tags.iter().copied().collect());
+    }
+    let parameters: Vec<Value> = path_params(path)
+      .map(|name| json!({ "in": "path", "name": name, "required": true, "schema": { "type": "string" } }))
+      .collect();
+    if !parameters.is_empty() {
+      let _ = rslt.insert("parameters".into(), parameters.into());
+    }
+    if let Some((mime, schema)) = self.request_body {
+      let _ = rslt.insert(
+        "requestBody".into(),
+        json!({ "content": { mime.as_str(): { "schema": serde_json::from_str::<Value>(schema)? } }, "required": true }),
+      );
+    }
+    if !self.responses.is_empty() {
+      let mut responses = Map::new();
+      for (status_code, description, body) in self.responses.iter() {
+        let mut response = Map::new();
+        let _ = response.insert("description".into(), (*description).into());
+        if let Some((mime, schema)) = body {
+          let content =
+            json!({ mime.as_str(): { "schema": serde_json::from_str::<Value>(schema)? } });
+          let _ = response.insert("content".into(), content);
+        }
+        let _ = responses.insert(u16::from(*status_code).to_string(), response.into());
+      }
+      let _ = rslt.insert("responses".into(), responses.into());
+    }
+    Ok(rslt.into())
+  }
+}
+
+/// Attaches an [`OpenApiOperation`] to the method wrapper `en`.
+#[derive(Debug)]
+pub struct Documented<T> {
+  en: T,
+  op: OpenApiOperation,
+}
+
+/// Creates a new [`Documented`] instance.
+#[inline]
+pub fn documented<T>(en: T, op: OpenApiOperation) -> Documented<T> {
+  Documented { en, op }
+}
+
+impl<CA, E, S, SA, T> Endpoint<CA, E, S, SA> for Documented<T>
+where
+  E: From<crate::Error>,
+  T: Endpoint<CA, E, S, SA>,
+{
+  const OM: OperationMode = T::OM;
+
+  #[inline]
+  async fn auto(
+    &self,
+    auto_stream: &mut AutoStream<CA, SA>,
+    path_defs: (u8, &[RouteMatch]),
+  ) -> Result<StatusCode, E> {
+    self.en.auto(auto_stream, path_defs).await
+  }
+
+  #[inline]
+  async fn manual(
+    &self,
+    manual_stream: ManualStream<CA, S, SA>,
+    path_defs: (u8, &[RouteMatch]),
+  ) -> Result<(), E> {
+    self.en.manual(manual_stream, path_defs).await
+  }
+}
+
+impl<CA, E, S, SA, T> EndpointNode<CA, E, S, SA> for Documented<T>
+where
+  E: From<crate::Error>,
+  T: EndpointNode<CA, E, S, SA>,
+{
+  const IS_ROUTER: bool = T::IS_ROUTER;
+
+  #[inline]
+  fn paths_indices(
+    &self,
+    prev: ArrayVector<RouteMatch, 4>,
+    vec: &mut Vector<ArrayVector<RouteMatch, 4>>,
+  ) -> crate::Result<()> {
+    self.en.paths_indices(prev, vec)
+  }
+}
+
+impl<CA, E, S, SA, T> MethodEndpoint<CA, E, S, SA> for Documented<T>
+where
+  E: From<crate::Error>,
+  T: MethodEndpoint<CA, E, S, SA>,
+{
+  const METHOD: Method = T::METHOD;
+}
+
+/// Node that contributes operations to an [`OpenApi`] document.
+pub trait OpenApiNode {
+  /// Inserts into `paths` the operations located at `path` or at any of its sub-paths.
+  /// Method wrappers use `op` when available.
+  fn openapi(
+    &self,
+    path: &mut String,
+    op: Option<&OpenApiOperation>,
+    paths: &mut Map<String, Value>,
+  ) -> crate::Result<()>;
+}
+
+impl<T> OpenApiNode for &T
+where
+  T: OpenApiNode,
+{
+  #[inline]
+  fn openapi(
+    &self,
+    path: &mut String,
+    op: Option<&OpenApiOperation>,
+    paths: &mut Map<String, Value>,
+  ) -> crate::Result<()> {
+    (*self).openapi(path, op, paths)
+  }
+}
+
+impl<T> OpenApiNode for Documented<T>
+where
+  T: OpenApiNode,
+{
+  #[inline]
+  fn openapi(
+    &self,
+    path: &mut String,
+    _: Option<&OpenApiOperation>,
+    paths: &mut Map<String, Value>,
+  ) -> crate::Result<()> {
+    self.en.openapi(path, Some(&self.op), paths)
+  }
+}
+
+impl<CA, E, EN, M, S, SA> OpenApiNode for Router<CA, E, EN, M, S, SA>
+where
+  EN: OpenApiNode,
+{
+  #[inline]
+  fn openapi(
+    &self,
+    path: &mut String,
+    op: Option<&OpenApiOperation>,
+    paths: &mut Map<String, Value>,
+  ) -> crate::Result<()> {
+    self.en.openapi(path, op, paths)
+  }
+}
+
+impl<EN, M> OpenApiNode for Scoped<EN, M>
+where
+  EN: OpenApiNode,
+{
+  #[inline]
+  fn openapi(
+    &self,
+    path: &mut String,
+    op: Option<&OpenApiOperation>,
+    paths: &mut Map<String, Value>,
+  ) -> crate::Result<()> {
+    self.en.openapi(path, op, paths)
+  }
+}
+
+/// Operations of [`Streaming`] aren't listed because any method is accepted.
+impl<T> OpenApiNode for Streaming<T> {
+  #[inline]
+  fn openapi(
+    &self,
+    _: &mut String,
+    _: Option<&OpenApiOperation>,
+    _: &mut Map<String, Value>,
+  ) -> crate::Result<()> {
+    Ok(())
+  }
+}
+
+macro_rules! impl_method_wrappers {
+  ($($ty:ident => $method:expr),+ $(,)?) => {
+    $(
+      impl<T> OpenApiNode for $ty<T> {
+        #[inline]
+        fn openapi(
+          &self,
+          path: &mut String,
+          op: Option<&OpenApiOperation>,
+          paths: &mut Map<String, Value>,
+        ) -> crate::Result<()> {
+          _push_operation($method, path, op, paths)
+        }
+      }
+    )+
+  };
+}
+
+impl_method_wrappers!(
+  Delete => Method::Delete,
+  Form => Method::Post,
+  Get => Method::Get,
+  Json => Method::Post,
+  Patch => Method::Patch,
+  Post => Method::Post,
+  Put => Method::Put,
+  Sse => Method::Get,
+  WebSocket => Method::Get,
+);
+
+#[cfg(feature = "static-files")]
+impl OpenApiNode for crate::http::server_framework::StaticFiles {
+  #[inline]
+  fn openapi(
+    &self,
+    path: &mut String,
+    op: Option<&OpenApiOperation>,
+    paths: &mut Map<String, Value>,
+  ) -> crate::Result<()> {
+    _push_operation(Method::Get, path, op, paths)
+  }
+}
+
+/// Inserts an operation of `method` located at `path`. Catch-all parameters like `{*name}` are
+/// written as `{name}`.
+#[inline]
+pub(crate) fn _push_operation(
+  method: Method,
+  path: &str,
+  op: Option<&OpenApiOperation>,
+  paths: &mut Map<String, Value>,
+) -> crate::Result<()> {
+  let operation = match op {
+    Some(elem) => elem.to_json(path)?,
+    None => OpenApiOperation::new().to_json(path)?,
+  };
+  let mut template = String::new();
+  if path.is_empty() {
+    template.push('/');
+  } else {
+    template.reserve(path.len());
+    for (idx, segment) in str_split1(path, b'/').enumerate() {
+      if idx > 0 {
+        template.push('/');
+      }
+      if let Some(elem) = segment.strip_prefix("{*") {
+        template.push('{');
+        template.push_str(elem);
+      } else {
+        template.push_str(segment);
+      }
+    }
+  }
+  let entry = paths.entry(template).or_insert_with(|| Map::new().into());
+  if let Value::Object(map) = entry {
+    let _ = map.insert(method.strings().custom[1].into(), operation);
+  }
+  Ok(())
+}
+
+#[inline]
+fn path_params(path: &str) -> impl Iterator<Item = &str> {
+  str_split1(path, b'/').filter_map(|segment| {
+    let name = segment.strip_prefix('{')?.strip_suffix('}')?;
+    Some(name.strip_prefix('*').unwrap_or(name))
+  })
+}
+
+#[cfg(all(feature = "matchit", test))]
+mod tests {
+  use crate::http::{
+    server_framework::{
+      documented, get, methods, post, scoped, OpenApi, OpenApiOperation, PathOwned, Router,
+      StateClean,
+    },
+    Mime, ReqResBuffer, StatusCode,
+  };
+  use serde_json::json;
+
+  #[test]
+  fn document() {
+    async fn list(_: StateClean<'_, (), (), ReqResBuffer>) -> crate::Result<StatusCode> {
+      Ok(StatusCode::Ok)
+    }
+
+    async fn user(_: PathOwned<u32>) -> crate::Result<StatusCode> {
+      Ok(StatusCode::Ok)
+    }
+
+    let create = OpenApiOperation::new()
+      .summary("Creates an user")
+      .tag("users")
+      .unwrap()
+      .request_body(Mime::Json, r#"{"type":"object"}"#)
+      .response(StatusCode::Created, "Created", None)
+      .unwrap();
+    let router = Router::<(), crate::Error, _, (), (), ()>::paths(crate::paths!(
+      ("/health", get(list)),
+      (
+        "/users",
+        scoped(
+          Router::<(), crate::Error, _, (), (), ()>::paths(crate::paths!(
+            ("/", methods((get(list), documented(post(list), create)))),
+            ("/files/{*path}", get(list)),
+            ("/{id}", get(user)),
+          ))
+          .unwrap(),
+          ()
+        )
+      ),
+    ))
+    .unwrap();
+    let doc =
+      OpenApi::new("API", "1.0.0").server("https://localhost").unwrap().document(&router).unwrap();
+    assert_eq!(
+      doc,
+      json!({
+        "info": { "title": "API", "version": "1.0.0" },
+        "openapi": "3.1.0",
+        "paths": {
+          "/health": { "get": {} },
+          "/users/": {
+            "get": {},
+            "post": {
+              "requestBody": {
+                "content": { "application/json": { "schema": { "type": "object" } } },
+                "required": true
+              },
+              "responses": { "201": { "description": "Created" } },
+              "summary": "Creates an user",
+              "tags": ["users"]
+            }
+          },
+          "/users/files/{path}": {
+            "get": {
+              "parameters": [
+                { "in": "path", "name": "path", "required": true, "schema": { "type": "string" } }
+              ]
+            }
+          },
+          "/users/{id}": {
+            "get": {
+              "parameters": [
+                { "in": "path", "name": "id", "required": true, "schema": { "type": "string" } }
+              ]
+            }
+          }
+        },
+        "servers": [{ "url": "https://localhost" }]
+      })
+    );
+  }
+}
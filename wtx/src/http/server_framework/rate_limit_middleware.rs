@@ -0,0 +1,261 @@
+use crate::{
+  http::{
+    server_framework::{MemoryRateLimitStore, Middleware, RateLimitStore},
+    Header, KnownHeaderName, ReqResBuffer, Request, Response, StatusCode,
+  },
+  misc::Vector,
+};
+use core::{net::IpAddr, ops::ControlFlow, time::Duration};
+use std::time::Instant;
+
+/// Allows at most `burst` requests per `period`. Used requests are continuously replenished, in
+/// other words, a new request is allowed every `period / burst`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RateLimit {
+  burst: u32,
+  period: Duration,
+}
+
+impl RateLimit {
+  /// A `burst` of zero is treated as one.
+  #[inline]
+  pub fn new(burst: u32, period: Duration) -> Self {
+    Self { burst: burst.max(1), period }
+  }
+
+  /// Maximum number of requests in a single period
+  #[inline]
+  pub fn burst(&self) -> u32 {
+    self.burst
+  }
+
+  /// Interval that replenishes all requests
+  #[inline]
+  pub fn period(&self) -> Duration {
+    self.period
+  }
+
+  /// Generic cell rate algorithm, which is equivalent to a token bucket that only stores the
+  /// theoretical arrival time (`tat`) of the next request. New buckets should start with `now`.
+  ///
+  /// Returns `None` and updates `tat` if the request is allowed, otherwise, returns the amount of
+  /// time the client should wait before trying again.
+  #[inline]
+  pub fn consume(&self, tat: &mut Duration, now: Duration) -> Option<Duration> {
+    let interval = self.period / self.burst;
+    let new_tat = (*tat).max(now).saturating_add(interval);
+    let allowed_at = now.saturating_add(self.period);
+    if new_tat > allowed_at {
+      return Some(new_tat.saturating_sub(allowed_at));
+    }
+    *tat = new_tat;
+    None
+  }
+}
+
+/// Limits the number of requests of each peer. Rejected requests receive a
+/// `429 Too Many Requests` response with a `Retry-After` header.
+///
+/// Limits of routes are applied to all paths that start with the given prefix, where the longest
+/// prefix wins. Prefixes are matched on whole path segments, for example, `/api` matches `/api`
+/// and `/api/users` but not `/apix`. Paths that don't match any route share the same default
+/// limit.
+///
+/// Buckets are refilled according to a monotonic clock, which means that adjustments of the system
+/// time don't affect limits.
+#[derive(Debug)]
+pub struct RateLimitMiddleware<S> {
+  default: RateLimit,
+  peers: Vector<(IpAddr, Option<RateLimit>)>,
+  routes: Vector<(&'static str, RateLimit)>,
+  start: Instant,
+  store: S,
+}
+
+impl RateLimitMiddleware<MemoryRateLimitStore> {
+  /// Instance backed by a [`MemoryRateLimitStore`].
+  #[inline]
+  pub fn new(default: RateLimit) -> Self {
+    Self::with_store(default, MemoryRateLimitStore::new())
+  }
+}
+
+impl<S> RateLimitMiddleware<S> {
+  /// Instance backed by a custom store.
+  #[inline]
+  pub fn with_store(default: RateLimit, store: S) -> Self {
+    Self { default, peers: Vector::new(), routes: Vector::new(), start: Instant::now(), store }
+  }
+
+  /// Overrides the limits of `peer`. `None` means that `peer` is never limited.
+  #[inline]
+  pub fn peer(mut self, peer: IpAddr, limit: Option<RateLimit>) -> crate::Result<Self> {
+    self.peers.push((peer, limit))?;
+    Ok(self)
+  }
+
+  /// Applies `limit` to all paths that start with the segments of `prefix`.
+  #[inline]
+  pub fn route(mut self, prefix: &'static str, limit: RateLimit) -> crate::Result<Self> {
+    self.routes.push((prefix, limit))?;
+    Ok(self)
+  }
+
+  #[inline]
+  fn params(&self, path: &str, peer: IpAddr) -> Option<(&'static str, RateLimit)> {
+    let (key, mut limit) = self
+      .routes
+      .iter()
+      .filter(|(prefix, _)| has_segment_prefix(path, prefix))
+      .max_by_key(|(prefix, _)| prefix.len())
+      .copied()
+      .unwrap_or(("", self.default));
+    if let Some((_, peer_limit)) = self.peers.iter().find(|(ip, _)| *ip == peer) {
+      limit = (*peer_limit)?;
+    }
+    Some((key, limit))
+  }
+}
+
+impl<CA, E, S, SA> Middleware<CA, E, SA> for RateLimitMiddleware<S>
+where
+  E: From<crate::Error>,
+  S: RateLimitStore,
+{
+  type Aux = ();
+
+  #[inline]
+  fn aux(&self) -> Self::Aux {}
+
+  #[inline]
+  async fn req(
+    &self,
+    _: &mut CA,
+    _: &mut Self::Aux,
+    peer: IpAddr,
+    req: &mut Request<ReqResBuffer>,
+    _: &mut SA,
+  ) -> Result<ControlFlow<StatusCode, ()>, E> {
+    let Some((key, limit)) = self.params(req.rrd.uri.path(), peer) else {
+      return Ok(ControlFlow::Continue(()));
+    };
+    let now = self.start.elapsed();
+    let Some(retry_after) = self.store.hit(key, &limit, now, peer).await? else {
+      return Ok(ControlFlow::Continue(()));
+    };
+    let secs = retry_after.as_secs().wrapping_add(u64::from(retry_after.subsec_nanos() > 0));
+    req.rrd.body.clear();
+    req.rrd.headers.clear();
+    req.rrd.headers.push_from_fmt(Header::from_name_and_value(
+      KnownHeaderName::RetryAfter.into(),
+      format_args!("{secs}"),
+    ))?;
+    Ok(ControlFlow::Break(StatusCode::TooManyRequests))
+  }
+
+  #[inline]
+  async fn res(
+    &self,
+    _: &mut CA,
+    _: &mut Self::Aux,
+    _: Response<&mut ReqResBuffer>,
+    _: &mut SA,
+  ) -> Result<ControlFlow<StatusCode, ()>, E> {
+    Ok(ControlFlow::Continue(()))
+  }
+}
+
+fn has_segment_prefix(path: &str, prefix: &str) -> bool {
+  let Some(rest) = path.strip_prefix(prefix) else {
+    return false;
+  };
+  rest.is_empty() || rest.starts_with('/') || prefix.ends_with('/')
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::http::server_framework::{MemoryRateLimitStore, RateLimit, RateLimitMiddleware};
+  use core::{
+    net::{IpAddr, Ipv4Addr},
+    time::Duration,
+  };
+
+  const LOCALHOST: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+  const OTHER: IpAddr = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+
+  #[test]
+  fn consume() {
+    let limit = RateLimit::new(2, Duration::from_secs(10));
+    let mut tat = Duration::ZERO;
+    assert_eq!(limit.consume(&mut tat, Duration::ZERO), None);
+    assert_eq!(limit.consume(&mut tat, Duration::ZERO), None);
+    assert_eq!(limit.consume(&mut tat, Duration::ZERO), Some(Duration::from_secs(5)));
+    assert_eq!(limit.consume(&mut tat, Duration::from_secs(4)), Some(Duration::from_secs(1)));
+    assert_eq!(limit.consume(&mut tat, Duration::from_secs(5)), None);
+    assert_eq!(limit.consume(&mut tat, Duration::from_secs(5)), Some(Duration::from_secs(5)));
+  }
+
+  #[test]
+  fn params() {
+    let default = RateLimit::new(10, Duration::from_secs(1));
+    let api = RateLimit::new(5, Duration::from_secs(1));
+    let login = RateLimit::new(1, Duration::from_secs(1));
+    let rlm = RateLimitMiddleware::<MemoryRateLimitStore>::new(default)
+      .route("/api", api)
+      .unwrap()
+      .route("/api/login", login)
+      .unwrap()
+      .peer(OTHER, None)
+      .unwrap();
+    assert_eq!(rlm.params("/", LOCALHOST), Some(("", default)));
+    assert_eq!(rlm.params("/apix", LOCALHOST), Some(("", default)));
+    assert_eq!(rlm.params("/api/loginx", LOCALHOST), Some(("/api", api)));
+    assert_eq!(rlm.params("/api/users", LOCALHOST), Some(("/api", api)));
+    assert_eq!(rlm.params("/api/login", LOCALHOST), Some(("/api/login", login)));
+    assert_eq!(rlm.params("/api/login", OTHER), None);
+  }
+}
+
+#[cfg(all(feature = "_async-tests", test))]
+mod async_tests {
+  use crate::http::server_framework::{MemoryRateLimitStore, RateLimit, RateLimitStore};
+  use core::{
+    net::{IpAddr, Ipv4Addr},
+    time::Duration,
+  };
+
+  const LOCALHOST: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+  const OTHER: IpAddr = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+
+  #[tokio::test]
+  async fn evicts_the_oldest_bucket() {
+    let limit = RateLimit::new(1, Duration::from_secs(2));
+    let store = MemoryRateLimitStore::new().max_buckets(2);
+    let peer = |n| IpAddr::V4(Ipv4Addr::new(10, 0, 0, n));
+    let now = Duration::from_secs(100);
+    assert_eq!(store.hit("/a", &limit, now, peer(1)).await.unwrap(), None);
+    assert_eq!(store.hit("/a", &limit, now, peer(2)).await.unwrap(), None);
+    // A full store doesn't lock out new peers
+    assert_eq!(store.hit("/a", &limit, now, peer(3)).await.unwrap(), None);
+    assert_eq!(store.hit("/a", &limit, now, peer(3)).await.unwrap(), Some(Duration::from_secs(2)));
+    assert_eq!(store.hit("/a", &limit, now, peer(2)).await.unwrap(), Some(Duration::from_secs(2)));
+    // The bucket of the first peer was discarded
+    assert_eq!(store.hit("/a", &limit, now, peer(1)).await.unwrap(), None);
+    assert_eq!(store.hit("/a", &limit, now, peer(3)).await.unwrap(), Some(Duration::from_secs(2)));
+    assert_eq!(store.hit("/a", &limit, now, peer(2)).await.unwrap(), None);
+  }
+
+  #[tokio::test]
+  async fn separates_peers_and_routes() {
+    let limit = RateLimit::new(1, Duration::from_secs(60));
+    let store = MemoryRateLimitStore::new();
+    let now = Duration::from_secs(100);
+    assert_eq!(store.hit("/a", &limit, now, LOCALHOST).await.unwrap(), None);
+    assert_eq!(store.hit("/b", &limit, now, LOCALHOST).await.unwrap(), None);
+    assert_eq!(store.hit("/a", &limit, now, OTHER).await.unwrap(), None);
+    assert_eq!(
+      store.hit("/a", &limit, now, LOCALHOST).await.unwrap(),
+      Some(Duration::from_secs(60))
+    );
+  }
+}
@@ -0,0 +1,118 @@
+use crate::{
+  http::server_framework::RateLimit,
+  misc::{Arc, Deque, SyncLock},
+};
+use core::{future::Future, net::IpAddr, time::Duration};
+use hashbrown::HashMap;
+use std::sync::Mutex;
+
+const MAX_BUCKETS: usize = 64 * 1024;
+
+/// Persists the counters of [`crate::http::server_framework::RateLimitMiddleware`].
+///
+/// Custom implementations can, for example, store counters in a database so that several server
+/// instances share the same limits.
+pub trait RateLimitStore {
+  /// Registers a new request of `peer` to the routes identified by `key`. `now` is monotonic and
+  /// relative to the creation of the middleware, as such, stores shared by several server instances
+  /// should rely on their own clock.
+  ///
+  /// Returns `None` if the request is allowed or the amount of time the client should wait before
+  /// trying again if `limit` was exceeded.
+  fn hit(
+    &self,
+    key: &'static str,
+    limit: &RateLimit,
+    now: Duration,
+    peer: IpAddr,
+  ) -> impl Future<Output = crate::Result<Option<Duration>>>;
+}
+
+impl<T> RateLimitStore for Arc<T>
+where
+  T: RateLimitStore,
+{
+  #[inline]
+  async fn hit(
+    &self,
+    key: &'static str,
+    limit: &RateLimit,
+    now: Duration,
+    peer: IpAddr,
+  ) -> crate::Result<Option<Duration>> {
+    (**self).hit(key, limit, now, peer).await
+  }
+}
+
+/// In-memory token buckets.
+///
+/// When the number of stored buckets reaches the maximum, the oldest bucket is discarded to make
+/// room for the new one. Memory usage is therefore bounded even when a large number of addresses
+/// is used and requests of unknown peers are never rejected because of a full store, at the cost
+/// of resetting the limits of the discarded bucket.
+#[derive(Debug)]
+pub struct MemoryRateLimitStore {
+  max_buckets: usize,
+  state: Mutex<MemoryRateLimitStoreState>,
+}
+
+impl MemoryRateLimitStore {
+  /// New empty instance that stores at most 65536 buckets.
+  #[inline]
+  pub fn new() -> Self {
+    Self {
+      max_buckets: MAX_BUCKETS,
+      state: Mutex::new(MemoryRateLimitStoreState { buckets: HashMap::new(), order: Deque::new() }),
+    }
+  }
+
+  /// Maximum number of stored buckets. Each bucket represents a pair of route and peer.
+  #[inline]
+  #[must_use]
+  pub fn max_buckets(mut self, elem: usize) -> Self {
+    self.max_buckets = elem.max(1);
+    self
+  }
+}
+
+impl Default for MemoryRateLimitStore {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl RateLimitStore for MemoryRateLimitStore {
+  #[inline]
+  async fn hit(
+    &self,
+    key: &'static str,
+    limit: &RateLimit,
+    now: Duration,
+    peer: IpAddr,
+  ) -> crate::Result<Option<Duration>> {
+    let mut state = SyncLock::lock(&self.state);
+    let MemoryRateLimitStoreState { buckets, order } = &mut *state;
+    if let Some(tat) = buckets.get_mut(&(key, peer)) {
+      return Ok(limit.consume(tat, now));
+    }
+    while buckets.len() >= self.max_buckets {
+      let Some(oldest) = order.pop_front() else {
+        break;
+      };
+      let _ = buckets.remove(&oldest);
+    }
+    let mut tat = now;
+    let rslt = limit.consume(&mut tat, now);
+    let _ = buckets.insert((key, peer), tat);
+    order.push_back((key, peer))?;
+    Ok(rslt)
+  }
+}
+
+#[derive(Debug)]
+struct MemoryRateLimitStoreState {
+  buckets: HashMap<(&'static str, IpAddr), Duration>,
+  // Insertion order of `buckets`
+  order: Deque<(&'static str, IpAddr)>,
+}
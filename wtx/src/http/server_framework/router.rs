@@ -1,6 +1,6 @@
 use crate::{
   http::{
-    server_framework::{Endpoint, EndpointNode, Middleware, RouteMatch},
+    server_framework::{Endpoint, EndpointNode, ManualStreamSender, Middleware, RouteMatch},
     AutoStream, ManualStream, OperationMode, Response, StatusCode,
   },
   misc::{ArrayVector, Vector},
@@ -124,6 +124,7 @@ where
   E: From<crate::Error>,
   EN: EndpointNode<CA, E, S, SA>,
   M: Middleware<CA, E, SA>,
+  S: ManualStreamSender,
 {
   const OM: OperationMode = OperationMode::Auto;
 
@@ -133,51 +134,16 @@ where
     auto_stream: &mut AutoStream<CA, SA>,
     path_defs: (u8, &[RouteMatch]),
   ) -> Result<StatusCode, E> {
-    let mw_aux = &mut self.middlewares.aux();
-    if let ControlFlow::Break(el) = self
-      .middlewares
-      .req(&mut auto_stream.conn_aux, mw_aux, &mut auto_stream.req, &mut auto_stream.stream_aux)
-      .await?
-    {
-      return Ok(el);
-    }
-    let status_code = self.en.auto(auto_stream, path_defs).await?;
-    if let ControlFlow::Break(el) = self
-      .middlewares
-      .res(
-        &mut auto_stream.conn_aux,
-        mw_aux,
-        Response { rrd: &mut auto_stream.req.rrd, status_code, version: auto_stream.req.version },
-        &mut auto_stream.stream_aux,
-      )
-      .await?
-    {
-      return Ok(el);
-    }
-    Ok(status_code)
+    auto_with_middlewares(&self.en, &self.middlewares, auto_stream, path_defs).await
   }
 
   #[inline]
   async fn manual(
     &self,
-    mut manual_stream: ManualStream<CA, S, SA>,
+    manual_stream: ManualStream<CA, S, SA>,
     path_defs: (u8, &[RouteMatch]),
   ) -> Result<(), E> {
-    let mw_aux = &mut self.middlewares.aux();
-    if let ControlFlow::Break(_) = self
-      .middlewares
-      .req(
-        &mut manual_stream.conn_aux,
-        mw_aux,
-        &mut manual_stream.req,
-        &mut manual_stream.stream_aux,
-      )
-      .await?
-    {
-      return Ok(());
-    }
-    self.en.manual(manual_stream, path_defs).await?;
-    Ok(())
+    manual_with_middlewares(&self.en, &self.middlewares, manual_stream, path_defs).await
   }
 }
 
@@ -186,6 +152,7 @@ where
   E: From<crate::Error>,
   EN: EndpointNode<CA, E, S, SA>,
   M: Middleware<CA, E, SA>,
+  S: ManualStreamSender,
 {
   const IS_ROUTER: bool = true;
 
@@ -198,3 +165,90 @@ where
     self.en.paths_indices(prev, vec)
   }
 }
+
+/// Calls `en` surrounded by the request and response hooks of `middlewares`.
+#[inline]
+pub(crate) async fn auto_with_middlewares<CA, E, EN, M, S, SA>(
+  en: &EN,
+  middlewares: &M,
+  auto_stream: &mut AutoStream<CA, SA>,
+  path_defs: (u8, &[RouteMatch]),
+) -> Result<StatusCode, E>
+where
+  E: From<crate::Error>,
+  EN: Endpoint<CA, E, S, SA>,
+  M: Middleware<CA, E, SA>,
+{
+  let mw_aux = &mut middlewares.aux();
+  if let ControlFlow::Break(el) = middlewares
+    .req(
+      &mut auto_stream.conn_aux,
+      mw_aux,
+      auto_stream.peer,
+      &mut auto_stream.req,
+      &mut auto_stream.stream_aux,
+    )
+    .await?
+  {
+    return Ok(el);
+  }
+  let fut = en.auto(auto_stream, path_defs);
+  #[cfg(feature = "tracing")]
+  let status_code = match middlewares.span(mw_aux) {
+    Some(span) => tracing::Instrument::instrument(fut, span).await?,
+    None => fut.await?,
+  };
+  #[cfg(not(feature = "tracing"))]
+  let status_code = fut.await?;
+  if let ControlFlow::Break(el) = middlewares
+    .res(
+      &mut auto_stream.conn_aux,
+      mw_aux,
+      Response { rrd: &mut auto_stream.req.rrd, status_code, version: auto_stream.req.version },
+      &mut auto_stream.stream_aux,
+    )
+    .await?
+  {
+    return Ok(el);
+  }
+  Ok(status_code)
+}
+
+/// Calls `en` after the request hooks of `middlewares`. Halted requests are answered by the
+/// framework.
+#[inline]
+pub(crate) async fn manual_with_middlewares<CA, E, EN, M, S, SA>(
+  en: &EN,
+  middlewares: &M,
+  mut manual_stream: ManualStream<CA, S, SA>,
+  path_defs: (u8, &[RouteMatch]),
+) -> Result<(), E>
+where
+  E: From<crate::Error>,
+  EN: Endpoint<CA, E, S, SA>,
+  M: Middleware<CA, E, SA>,
+  S: ManualStreamSender,
+{
+  let mw_aux = &mut middlewares.aux();
+  if let ControlFlow::Break(status_code) = middlewares
+    .req(
+      &mut manual_stream.conn_aux,
+      mw_aux,
+      manual_stream.peer,
+      &mut manual_stream.req,
+      &mut manual_stream.stream_aux,
+    )
+    .await?
+  {
+    let res =
+      Response { rrd: &mut manual_stream.req.rrd, status_code, version: manual_stream.req.version };
+    manual_stream.stream.send_manual_res(res).await?;
+    return Ok(());
+  }
+  let fut = en.manual(manual_stream, path_defs);
+  #[cfg(feature = "tracing")]
+  if let Some(span) = middlewares.span(mw_aux) {
+    return tracing::Instrument::instrument(fut, span).await;
+  }
+  fut.await
+}
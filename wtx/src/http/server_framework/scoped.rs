@@ -0,0 +1,114 @@
+use crate::{
+  http::{
+    server_framework::{
+      router::{auto_with_middlewares, manual_with_middlewares},
+      Endpoint, EndpointNode, ManualStreamSender, Middleware, RouteMatch,
+    },
+    AutoStream, ManualStream, OperationMode, StatusCode,
+  },
+  misc::{ArrayVector, Vector},
+};
+
+/// Attaches middlewares to a subtree of routes, which can be a single endpoint or a nested
+/// [`Router`](crate::http::server_framework::Router).
+///
+/// Unlike a nested router created with middlewares, this node doesn't add a new path level nor
+/// builds its own matcher. The chain of middlewares of each route is known at compile time.
+#[derive(Debug)]
+pub struct Scoped<EN, M> {
+  pub(crate) en: EN,
+  pub(crate) middlewares: M,
+}
+
+/// Creates a new [`Scoped`] instance.
+#[inline]
+pub fn scoped<EN, M>(en: EN, middlewares: M) -> Scoped<EN, M> {
+  Scoped { en, middlewares }
+}
+
+impl<CA, E, EN, M, S, SA> Endpoint<CA, E, S, SA> for Scoped<EN, M>
+where
+  E: From<crate::Error>,
+  EN: Endpoint<CA, E, S, SA>,
+  M: Middleware<CA, E, SA>,
+  S: ManualStreamSender,
+{
+  const OM: OperationMode = EN::OM;
+
+  #[inline]
+  async fn auto(
+    &self,
+    auto_stream: &mut AutoStream<CA, SA>,
+    path_defs: (u8, &[RouteMatch]),
+  ) -> Result<StatusCode, E> {
+    auto_with_middlewares(&self.en, &self.middlewares, auto_stream, path_defs).await
+  }
+
+  #[inline]
+  async fn manual(
+    &self,
+    manual_stream: ManualStream<CA, S, SA>,
+    path_defs: (u8, &[RouteMatch]),
+  ) -> Result<(), E> {
+    manual_with_middlewares(&self.en, &self.middlewares, manual_stream, path_defs).await
+  }
+}
+
+impl<CA, E, EN, M, S, SA> EndpointNode<CA, E, S, SA> for Scoped<EN, M>
+where
+  E: From<crate::Error>,
+  EN: EndpointNode<CA, E, S, SA>,
+  M: Middleware<CA, E, SA>,
+  S: ManualStreamSender,
+{
+  const IS_ROUTER: bool = EN::IS_ROUTER;
+
+  #[inline]
+  fn paths_indices(
+    &self,
+    prev: ArrayVector<RouteMatch, 4>,
+    vec: &mut Vector<ArrayVector<RouteMatch, 4>>,
+  ) -> crate::Result<()> {
+    self.en.paths_indices(prev, vec)
+  }
+}
+
+#[cfg(all(feature = "_async-tests", feature = "matchit", feature = "nightly", test))]
+mod tests {
+  use crate::{
+    http::{
+      server_framework::{
+        get, middleware::tests::Halt, scoped, Router, ServerFrameworkBuilder, StateClean,
+      },
+      ReqResBuffer, StatusCode,
+    },
+    misc::{simple_seed, Xorshift64},
+  };
+
+  #[tokio::test]
+  async fn scoped_middlewares() {
+    async fn endpoint(_: StateClean<'_, (), (), ReqResBuffer>) -> crate::Result<StatusCode> {
+      Ok(StatusCode::Ok)
+    }
+
+    let deny = || Halt(StatusCode::Unauthorized);
+    let router = Router::paths(crate::paths!(
+      ("/admin", scoped(Router::paths(crate::paths!(("/users", get(endpoint)))).unwrap(), deny())),
+      ("/private", scoped(get(endpoint), deny())),
+      ("/public", get(endpoint)),
+    ))
+    .unwrap();
+    let client = ServerFrameworkBuilder::new(router)
+      .without_aux()
+      .test_client(Xorshift64::from(simple_seed()), |err: crate::Error| panic!("{err:?}"))
+      .await
+      .unwrap();
+    for (path, status_code) in [
+      ("/admin/users", StatusCode::Unauthorized),
+      ("/private", StatusCode::Unauthorized),
+      ("/public", StatusCode::Ok),
+    ] {
+      assert_eq!(client.get(path).send().await.unwrap().status_code, status_code);
+    }
+  }
+}
@@ -0,0 +1,357 @@
+use crate::http::{
+  server_framework::{content_security_policy::CspWithNonce, ContentSecurityPolicy, Middleware},
+  Header, Headers, KnownHeaderName, ReqResBuffer, Request, Response, StatusCode,
+};
+#[cfg(feature = "base64")]
+use crate::{
+  http::server_framework::CspNonce,
+  misc::{ArrayString, LeaseMut, Rng},
+};
+#[cfg(feature = "base64")]
+use base64::{engine::general_purpose::STANDARD, Engine};
+use core::{marker::PhantomData, net::IpAddr, ops::ControlFlow};
+
+/// Sets security-related headers in all responses.
+///
+/// Per-request nonces of the `Content-Security-Policy` header are only generated after calling
+/// [`SecurityHeadersMiddleware::csp_nonces`]. Policies with directives whose only sources are
+/// [`crate::http::server_framework::CspSource::Nonce`] can't be rendered without them.
+#[derive(Debug)]
+pub struct SecurityHeadersMiddleware<N = ()> {
+  content_type_options: bool,
+  coep: Option<&'static str>,
+  coop: Option<&'static str>,
+  csp: Option<ContentSecurityPolicy>,
+  hsts: Option<(u32, bool, bool)>,
+  nonces: PhantomData<N>,
+  permissions_policy: Option<&'static str>,
+  referrer_policy: Option<&'static str>,
+}
+
+impl SecurityHeadersMiddleware {
+  /// New empty instance
+  #[inline]
+  pub const fn new() -> Self {
+    Self {
+      content_type_options: false,
+      coep: None,
+      coop: None,
+      csp: None,
+      hsts: None,
+      nonces: PhantomData,
+      permissions_policy: None,
+      referrer_policy: None,
+    }
+  }
+
+  /// * HSTS of two years that includes subdomains.
+  /// * MIME sniffing is disabled.
+  /// * `strict-origin-when-cross-origin` referrer policy.
+  /// * Camera, geolocation and microphone are disabled.
+  /// * `same-origin` opener policy and `require-corp` embedder policy.
+  /// * Resources can only be loaded from the same origin, plugins are disabled and pages can't be
+  ///   embedded by other origins.
+  #[inline]
+  #[must_use]
+  pub fn strict() -> Self {
+    Self {
+      content_type_options: true,
+      coep: Some("require-corp"),
+      coop: Some("same-origin"),
+      csp: Some(ContentSecurityPolicy::strict()),
+      hsts: Some((63_072_000, true, false)),
+      nonces: PhantomData,
+      permissions_policy: Some("camera=(), geolocation=(), microphone=()"),
+      referrer_policy: Some("strict-origin-when-cross-origin"),
+    }
+  }
+
+  /// Generates a nonce for each request using the `RNG` instance of the connection auxiliary.
+  /// Nonces are inserted into the stream auxiliary as well as into every
+  /// [`crate::http::server_framework::CspSource::Nonce`] of the policy.
+  ///
+  /// Nonces are only effective if they can't be guessed, as such, `RNG` must be a
+  /// cryptographically secure generator seeded with the entropy of the operating system, like
+  /// `ChaCha20Rng::from_entropy()`. Generators like [`crate::misc::Xorshift64`] must not be used.
+  #[cfg(feature = "base64")]
+  #[inline]
+  pub fn csp_nonces<RNG>(self) -> SecurityHeadersMiddleware<fn() -> RNG> {
+    SecurityHeadersMiddleware {
+      content_type_options: self.content_type_options,
+      coep: self.coep,
+      coop: self.coop,
+      csp: self.csp,
+      hsts: self.hsts,
+      nonces: PhantomData,
+      permissions_policy: self.permissions_policy,
+      referrer_policy: self.referrer_policy,
+    }
+  }
+}
+
+impl<N> SecurityHeadersMiddleware<N> {
+  /// <https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/X-Content-Type-Options>
+  #[inline]
+  #[must_use]
+  pub fn content_type_options(mut self, elem: bool) -> Self {
+    self.content_type_options = elem;
+    self
+  }
+
+  /// <https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Content-Security-Policy>
+  #[inline]
+  #[must_use]
+  pub fn csp(mut self, elem: Option<ContentSecurityPolicy>) -> Self {
+    self.csp = elem;
+    self
+  }
+
+  /// <https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Cross-Origin-Embedder-Policy>
+  #[inline]
+  #[must_use]
+  pub fn cross_origin_embedder_policy(mut self, elem: Option<&'static str>) -> Self {
+    self.coep = elem;
+    self
+  }
+
+  /// <https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Cross-Origin-Opener-Policy>
+  #[inline]
+  #[must_use]
+  pub fn cross_origin_opener_policy(mut self, elem: Option<&'static str>) -> Self {
+    self.coop = elem;
+    self
+  }
+
+  /// <https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Strict-Transport-Security>
+  ///
+  /// `max_age` is expressed in seconds and `None` removes the header.
+  #[inline]
+  #[must_use]
+  pub fn hsts(mut self, max_age: Option<u32>, include_subdomains: bool, preload: bool) -> Self {
+    self.hsts = max_age.map(|el| (el, include_subdomains, preload));
+    self
+  }
+
+  /// <https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Permissions-Policy>
+  #[inline]
+  #[must_use]
+  pub fn permissions_policy(mut self, elem: Option<&'static str>) -> Self {
+    self.permissions_policy = elem;
+    self
+  }
+
+  /// <https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Referrer-Policy>
+  #[inline]
+  #[must_use]
+  pub fn referrer_policy(mut self, elem: Option<&'static str>) -> Self {
+    self.referrer_policy = elem;
+    self
+  }
+
+  #[inline]
+  fn push_headers(&self, headers: &mut Headers, nonce: Option<&str>) -> crate::Result<()> {
+    let str_headers = [
+      (KnownHeaderName::CrossOriginEmbedderPolicy, self.coep),
+      (KnownHeaderName::CrossOriginOpenerPolicy, self.coop),
+      (KnownHeaderName::PermissionsPolicy, self.permissions_policy),
+      (KnownHeaderName::ReferrerPolicy, self.referrer_policy),
+      (KnownHeaderName::XContentTypeOptions, self.content_type_options.then_some("nosniff")),
+    ];
+    for (name, value) in str_headers {
+      if let Some(elem) = value {
+        headers.push_from_iter(Header::from_name_and_value(name.into(), [elem.as_bytes()]))?;
+      }
+    }
+    if let Some(elem) = &self.csp {
+      headers.push_from_fmt(Header::from_name_and_value(
+        KnownHeaderName::ContentSecurityPolicy.into(),
+        format_args!("{}", CspWithNonce(elem, nonce)),
+      ))?;
+    }
+    if let Some((max_age, include_subdomains, preload)) = self.hsts {
+      headers.push_from_fmt(Header::from_name_and_value(
+        KnownHeaderName::StrictTransportSecurity.into(),
+        format_args!(
+          "max-age={max_age}{}{}",
+          if include_subdomains { "; includeSubDomains" } else { "" },
+          if preload { "; preload" } else { "" }
+        ),
+      ))?;
+    }
+    Ok(())
+  }
+}
+
+impl Default for SecurityHeadersMiddleware {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<CA, E, SA> Middleware<CA, E, SA> for SecurityHeadersMiddleware
+where
+  E: From<crate::Error>,
+{
+  type Aux = ();
+
+  #[inline]
+  fn aux(&self) -> Self::Aux {}
+
+  #[inline]
+  async fn req(
+    &self,
+    _: &mut CA,
+    _: &mut Self::Aux,
+    _: IpAddr,
+    _: &mut Request<ReqResBuffer>,
+    _: &mut SA,
+  ) -> Result<ControlFlow<StatusCode, ()>, E> {
+    Ok(ControlFlow::Continue(()))
+  }
+
+  #[inline]
+  async fn res(
+    &self,
+    _: &mut CA,
+    _: &mut Self::Aux,
+    res: Response<&mut ReqResBuffer>,
+    _: &mut SA,
+  ) -> Result<ControlFlow<StatusCode, ()>, E> {
+    self.push_headers(&mut res.rrd.headers, None)?;
+    Ok(ControlFlow::Continue(()))
+  }
+}
+
+#[cfg(feature = "base64")]
+impl<CA, E, RNG, SA> Middleware<CA, E, SA> for SecurityHeadersMiddleware<fn() -> RNG>
+where
+  CA: LeaseMut<RNG>,
+  E: From<crate::Error>,
+  RNG: Rng,
+  SA: LeaseMut<Option<CspNonce>>,
+{
+  type Aux = Option<CspNonce>;
+
+  #[inline]
+  fn aux(&self) -> Self::Aux {
+    None
+  }
+
+  #[inline]
+  async fn req(
+    &self,
+    ca: &mut CA,
+    mw_aux: &mut Self::Aux,
+    _: IpAddr,
+    _: &mut Request<ReqResBuffer>,
+    sa: &mut SA,
+  ) -> Result<ControlFlow<StatusCode, ()>, E> {
+    let mut buffer = [0; 24];
+    let bytes = ca.lease_mut().u8_16();
+    let len = STANDARD.encode_slice(bytes, &mut buffer).map_err(crate::Error::from)?;
+    let mut nonce = ArrayString::new();
+    for byte in buffer.get(..len).unwrap_or_default() {
+      nonce.push(char::from(*byte)).map_err(crate::Error::from)?;
+    }
+    *mw_aux = Some(CspNonce(nonce));
+    *sa.lease_mut() = *mw_aux;
+    Ok(ControlFlow::Continue(()))
+  }
+
+  #[inline]
+  async fn res(
+    &self,
+    _: &mut CA,
+    mw_aux: &mut Self::Aux,
+    res: Response<&mut ReqResBuffer>,
+    _: &mut SA,
+  ) -> Result<ControlFlow<StatusCode, ()>, E> {
+    self.push_headers(&mut res.rrd.headers, mw_aux.as_ref().map(CspNonce::as_str))?;
+    Ok(ControlFlow::Continue(()))
+  }
+}
+
+#[cfg(all(feature = "_async-tests", test))]
+mod tests {
+  use crate::http::{
+    server_framework::{Middleware, SecurityHeadersMiddleware},
+    KnownHeaderName, ReqResBuffer, Response, StatusCode,
+  };
+
+  #[cfg(all(feature = "base64", feature = "rand_chacha"))]
+  #[tokio::test]
+  async fn nonces() {
+    use crate::{
+      http::{
+        server_framework::{ContentSecurityPolicy, CspDirective, CspNonce, CspSource},
+        Method, Request,
+      },
+      misc::{Lease, LeaseMut},
+    };
+    use core::net::{IpAddr, Ipv4Addr};
+    use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+
+    struct ConnAux(ChaCha20Rng);
+
+    impl Lease<ChaCha20Rng> for ConnAux {
+      fn lease(&self) -> &ChaCha20Rng {
+        &self.0
+      }
+    }
+
+    impl LeaseMut<ChaCha20Rng> for ConnAux {
+      fn lease_mut(&mut self) -> &mut ChaCha20Rng {
+        &mut self.0
+      }
+    }
+
+    let csp = ContentSecurityPolicy::new()
+      .directive(CspDirective::ScriptSrc, [CspSource::SelfOrigin, CspSource::Nonce])
+      .unwrap();
+    let mw = SecurityHeadersMiddleware::new()
+      .csp(Some(csp))
+      .hsts(Some(60), true, true)
+      .csp_nonces::<ChaCha20Rng>();
+    let mut ca = ConnAux(ChaCha20Rng::from_seed([7; 32]));
+    let mut req = Request::http2(Method::Get, ReqResBuffer::empty());
+    let mut aux = Middleware::<ConnAux, crate::Error, Option<CspNonce>>::aux(&mw);
+    let mut sa = None;
+    let peer = IpAddr::V4(Ipv4Addr::LOCALHOST);
+    let _ = Middleware::<ConnAux, crate::Error, Option<CspNonce>>::req(
+      &mw, &mut ca, &mut aux, peer, &mut req, &mut sa,
+    )
+    .await
+    .unwrap();
+    let nonce = sa.unwrap();
+    assert_eq!(nonce.as_str().len(), 24);
+    let res = Response::http2(&mut req.rrd, StatusCode::Ok);
+    let _ = Middleware::<ConnAux, crate::Error, Option<CspNonce>>::res(
+      &mw, &mut ca, &mut aux, res, &mut sa,
+    )
+    .await
+    .unwrap();
+    let [csp_opt, hsts_opt] = req.rrd.headers.get_many_by_name([
+      KnownHeaderName::ContentSecurityPolicy.into(),
+      KnownHeaderName::StrictTransportSecurity.into(),
+    ]);
+    let expected = alloc::format!("script-src 'self' 'nonce-{}'", nonce.as_str());
+    assert_eq!(csp_opt.unwrap().value, expected.as_bytes());
+    assert_eq!(hsts_opt.unwrap().value, b"max-age=60; includeSubDomains; preload");
+  }
+
+  #[tokio::test]
+  async fn strict() {
+    let mw = SecurityHeadersMiddleware::strict();
+    let mut rrb = ReqResBuffer::empty();
+    let res = Response::http2(&mut rrb, StatusCode::Ok);
+    let _ =
+      Middleware::<(), crate::Error, ()>::res(&mw, &mut (), &mut (), res, &mut ()).await.unwrap();
+    let csp = rrb.headers.get_by_name(KnownHeaderName::ContentSecurityPolicy.into()).unwrap();
+    assert_eq!(
+      csp.value,
+      b"default-src 'self'; base-uri 'self'; frame-ancestors 'none'; object-src 'none'"
+    );
+    assert_eq!(rrb.headers.headers_len(), 7);
+  }
+}
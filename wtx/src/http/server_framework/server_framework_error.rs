@@ -1,16 +1,38 @@
 /// Server Framework Error
 #[derive(Debug)]
 pub enum ServerFrameworkError {
+  /// JSON Web Token whose `exp` claim is in the past
+  ExpiredJwt,
   /// Client requested a CORS header that isn't allowed
   ForbiddenCorsHeader,
   /// Client requested a CORS method that isn't allowed
   ForbiddenCorsMethod,
   /// Client requested a CORS origin that isn't allowed
   ForbiddenCorsOrigin,
+  /// `'none'` can't be combined with other sources of a Content Security Policy directive
+  InvalidCspSources,
+  /// Client sent a header that couldn't be parsed
+  InvalidHeader,
+  /// Malformed JSON Web Token or a token signed with an unexpected algorithm
+  InvalidJwt,
+  /// JSON Web Token that doesn't contain the expected audience
+  InvalidJwtAudience,
+  /// JSON Web Token that wasn't emitted by the expected issuer
+  InvalidJwtIssuer,
+  /// JSON Web Token with a signature that doesn't match its content
+  InvalidJwtSignature,
+  /// Client sent a `multipart/form-data` body with missing boundaries or malformed parts
+  InvalidMultipart,
+  /// Server-Sent Event fields other than `data` can't contain line breaks
+  InvalidSseField,
   /// Client sent a request with invalid WebSocket tunneling parameters
   InvalidWebSocketParameters,
+  /// A part or the sum of all parts of a `multipart/form-data` body exceeds the configured limits
+  MultipartIsTooLarge,
   /// Entered in a route that has an incompatible operation mode
   OperationModeMismatch,
+  /// JSON Web Token whose `nbf` claim is in the future
+  PrematureJwt,
   /// Unknown path
   UnknownPath,
 }
@@ -0,0 +1,493 @@
+use crate::{
+  http::{
+    server_framework::{
+      arguments::manage_path, entity_tag::matches_etag, Endpoint, EndpointNode, RouteMatch,
+    },
+    Header, Headers, KnownHeaderName, ManualStream, Method, Mime, OperationMode, PercentDecode,
+    StatusCode,
+  },
+  http2::{Http2Buffer, Http2Data, SendDataMode, ServerStream},
+  misc::{
+    bytes_split_once1, from_utf8_basic, str_split1, ArrayString, ArrayVector, BufferMode,
+    FromRadix10, LeaseMut, Lock, RefCounter, StreamWriter, Vector,
+  },
+};
+use core::fmt::Write;
+use std::{
+  io::{ErrorKind, SeekFrom},
+  path::{Path, PathBuf},
+  time::UNIX_EPOCH,
+};
+use tokio::{
+  fs::File,
+  io::{AsyncReadExt, AsyncSeekExt},
+};
+
+const CHUNK_LEN: usize = 32 * 1024;
+const DAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+const MONTHS: [&str; 12] =
+  ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+
+/// Serves the files of a local directory.
+///
+/// The route must end with a catch-all parameter, for example, `/assets/{*path}`, whose contents
+/// are used as the relative path of the requested file. Segments like `..` are rejected to avoid
+/// path traversals.
+///
+/// Responses contain strong `ETag` and `Last-Modified` headers, conditional requests are answered
+/// with `304 Not Modified` and single byte ranges are answered with `206 Partial Content`.
+///
+/// File operations are performed by the blocking thread pool of `tokio`, which means that a Tokio
+/// runtime is required. File contents are sent in chunks of data frames through manual streams,
+/// as such, only HTTP/2 connections are supported and HTTP/1.1 connections, including the ones
+/// handled by `tokio_h2c`, will receive an error.
+#[derive(Debug)]
+pub struct StaticFiles {
+  dir: PathBuf,
+}
+
+/// Creates a new [`StaticFiles`] instance that serves the files of `dir`.
+#[inline]
+pub fn static_files(dir: impl Into<PathBuf>) -> StaticFiles {
+  StaticFiles { dir: dir.into() }
+}
+
+impl<CA, E, HB, HD, SA, SW> Endpoint<CA, E, ServerStream<HD>, SA> for StaticFiles
+where
+  E: From<crate::Error>,
+  HB: LeaseMut<Http2Buffer>,
+  HD: RefCounter,
+  HD::Item: Lock<Resource = Http2Data<HB, SW, false>>,
+  SW: StreamWriter,
+{
+  const OM: OperationMode = OperationMode::Manual;
+
+  #[inline]
+  async fn manual(
+    &self,
+    mut manual_stream: ManualStream<CA, ServerStream<HD>, SA>,
+    path_defs: (u8, &[RouteMatch]),
+  ) -> Result<(), E> {
+    let method = manual_stream.req.method;
+    let rrd = &mut manual_stream.req.rrd;
+    let mut common = manual_stream.stream.common();
+    if method != Method::Get && method != Method::Head {
+      rrd.headers.clear();
+      rrd.headers.push_from_iter(Header::from_name_and_value(
+        KnownHeaderName::Allow.into(),
+        [b"GET, HEAD".as_slice()],
+      ))?;
+      let _ = common.send_headers(&rrd.headers, true, StatusCode::MethodNotAllowed).await?;
+      return Ok(());
+    }
+    let Some(mut file) =
+      open_file(&self.dir, manage_path(path_defs, &rrd.uri)?, &mut rrd.body).await?
+    else {
+      rrd.headers.clear();
+      let _ = common.send_headers(&rrd.headers, true, StatusCode::NotFound).await?;
+      return Ok(());
+    };
+    let metadata = file.metadata().await.map_err(crate::Error::from)?;
+    let len = metadata.len();
+    let modified = metadata.modified().map_err(crate::Error::from)?;
+    let since_epoch = modified.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let mut etag = ArrayString::<64>::new();
+    etag
+      .write_fmt(format_args!(
+        "\"{:x}.{:x}-{len:x}\"",
+        since_epoch.as_secs(),
+        since_epoch.subsec_nanos()
+      ))
+      .map_err(crate::Error::from)?;
+    let last_modified = http_date(since_epoch.as_secs())?;
+    let (status_code, range) = evaluate(&rrd.headers, etag.as_bytes(), len, since_epoch.as_secs());
+    rrd.headers.clear();
+    if status_code == StatusCode::NotModified {
+      push_validators(&mut rrd.headers, &etag, &last_modified)?;
+      let _ = common.send_headers(&rrd.headers, true, status_code).await?;
+      return Ok(());
+    }
+    if status_code == StatusCode::RequestedRangeNotSatisfiable {
+      rrd.headers.push_from_fmt(Header::from_name_and_value(
+        KnownHeaderName::ContentRange.into(),
+        format_args!("bytes */{len}"),
+      ))?;
+      let _ = common.send_headers(&rrd.headers, true, status_code).await?;
+      return Ok(());
+    }
+    let (start, body_len) = match range {
+      Some((first, last)) => {
+        rrd.headers.push_from_fmt(Header::from_name_and_value(
+          KnownHeaderName::ContentRange.into(),
+          format_args!("bytes {first}-{last}/{len}"),
+        ))?;
+        (first, last.wrapping_sub(first).wrapping_add(1))
+      }
+      None => (0, len),
+    };
+    let mime = guess_mime(rrd.uri.path());
+    rrd.headers.push_from_iter_many([
+      Header::from_name_and_value(
+        KnownHeaderName::AcceptRanges.into(),
+        [b"bytes".as_slice()].into_iter(),
+      ),
+      Header::from_name_and_value(
+        KnownHeaderName::ContentType.into(),
+        [mime.as_str().as_bytes()].into_iter(),
+      ),
+    ])?;
+    rrd.headers.push_from_fmt(Header::from_name_and_value(
+      KnownHeaderName::ContentLength.into(),
+      format_args!("{body_len}"),
+    ))?;
+    push_validators(&mut rrd.headers, &etag, &last_modified)?;
+    let is_eos = method == Method::Head || body_len == 0;
+    if common.send_headers(&rrd.headers, is_eos, status_code).await?.is_closed() || is_eos {
+      return Ok(());
+    }
+    let _ = file.seek(SeekFrom::Start(start)).await.map_err(crate::Error::from)?;
+    rrd.body.clear();
+    rrd.body.expand(BufferMode::Len(CHUNK_LEN), 0).map_err(crate::Error::from)?;
+    let mut remaining = body_len;
+    while remaining > 0 {
+      let chunk_len = usize::try_from(remaining).unwrap_or(usize::MAX).min(CHUNK_LEN);
+      let chunk = rrd.body.get_mut(..chunk_len).unwrap_or_default();
+      let read = file.read(chunk).await.map_err(crate::Error::from)?;
+      if read == 0 {
+        return Err(crate::Error::from(std::io::Error::from(ErrorKind::UnexpectedEof)).into());
+      }
+      remaining = remaining.wrapping_sub(u64::try_from(read).unwrap_or(u64::MAX));
+      let data = SendDataMode::scattered_data_frames(chunk.get(..read).unwrap_or_default());
+      if common.send_data(data, remaining == 0).await?.is_closed() {
+        return Ok(());
+      }
+    }
+    Ok(())
+  }
+}
+
+impl<CA, E, HB, HD, SA, SW> EndpointNode<CA, E, ServerStream<HD>, SA> for StaticFiles
+where
+  E: From<crate::Error>,
+  HB: LeaseMut<Http2Buffer>,
+  HD: RefCounter,
+  HD::Item: Lock<Resource = Http2Data<HB, SW, false>>,
+  SW: StreamWriter,
+{
+  const IS_ROUTER: bool = false;
+
+  #[inline]
+  fn paths_indices(
+    &self,
+    _: ArrayVector<RouteMatch, 4>,
+    _: &mut Vector<ArrayVector<RouteMatch, 4>>,
+  ) -> crate::Result<()> {
+    Ok(())
+  }
+}
+
+#[derive(Debug, PartialEq)]
+enum ByteRange {
+  Full,
+  Partial(u64, u64),
+  Unsatisfiable,
+}
+
+/// Parses a `Range` header that contains a single range. Invalid or multiple ranges are ignored,
+/// which means that the whole file is sent.
+fn byte_range(value: &[u8], len: u64) -> ByteRange {
+  let Some(spec) = value.trim_ascii().strip_prefix(b"bytes=") else {
+    return ByteRange::Full;
+  };
+  let Some((first, last)) = bytes_split_once1(spec, b'-') else {
+    return ByteRange::Full;
+  };
+  let (first, last) = (first.trim_ascii(), last.trim_ascii());
+  let last_idx = len.wrapping_sub(1);
+  match (first.is_empty(), u64::from_radix_10(first), u64::from_radix_10(last)) {
+    (true, _, Ok(suffix)) => {
+      if suffix == 0 || len == 0 {
+        return ByteRange::Unsatisfiable;
+      }
+      ByteRange::Partial(len.saturating_sub(suffix), last_idx)
+    }
+    (false, Ok(start), rslt) => {
+      if start >= len {
+        return ByteRange::Unsatisfiable;
+      }
+      match (last.is_empty(), rslt) {
+        (true, _) => ByteRange::Partial(start, last_idx),
+        (false, Ok(end)) if end >= start => ByteRange::Partial(start, end.min(last_idx)),
+        _ => ByteRange::Full,
+      }
+    }
+    _ => ByteRange::Full,
+  }
+}
+
+/// Applies the conditional and range headers of a request.
+fn evaluate(
+  headers: &Headers,
+  etag: &[u8],
+  len: u64,
+  modified: u64,
+) -> (StatusCode, Option<(u64, u64)>) {
+  let if_none_match = headers.get_by_name(KnownHeaderName::IfNoneMatch.into());
+  if let Some(header) = if_none_match {
+    if matches_etag(header.value, etag, true) {
+      return (StatusCode::NotModified, None);
+    }
+  } else if let Some(header) = headers.get_by_name(KnownHeaderName::IfModifiedSince.into()) {
+    if parse_http_date(header.value.trim_ascii()).is_some_and(|since| modified <= since) {
+      return (StatusCode::NotModified, None);
+    }
+  }
+  let Some(range) = headers.get_by_name(KnownHeaderName::Range.into()) else {
+    return (StatusCode::Ok, None);
+  };
+  if let Some(header) = headers.get_by_name(KnownHeaderName::IfRange.into()) {
+    if !matches_etag(header.value, etag, false) {
+      return (StatusCode::Ok, None);
+    }
+  }
+  match byte_range(range.value, len) {
+    ByteRange::Full => (StatusCode::Ok, None),
+    ByteRange::Partial(first, last) => (StatusCode::PartialContent, Some((first, last))),
+    ByteRange::Unsatisfiable => (StatusCode::RequestedRangeNotSatisfiable, None),
+  }
+}
+
+/// Formats seconds since the UNIX epoch as an IMF-fixdate, e.g., `Sun, 06 Nov 1994 08:49:37 GMT`.
+fn http_date(secs: u64) -> crate::Result<ArrayString<29>> {
+  let days = secs.wrapping_div(86_400);
+  let rem = secs.wrapping_rem(86_400);
+  let (year, month, day) = civil_from_days(days);
+  let mut rslt = ArrayString::new();
+  rslt.write_fmt(format_args!(
+    "{}, {day:02} {} {year:04} {:02}:{:02}:{:02} GMT",
+    DAYS
+      .get(usize::try_from(days.wrapping_rem(7)).unwrap_or_default())
+      .copied()
+      .unwrap_or_default(),
+    MONTHS.get(usize::from(month).wrapping_sub(1)).copied().unwrap_or_default(),
+    rem.wrapping_div(3600),
+    rem.wrapping_rem(3600).wrapping_div(60),
+    rem.wrapping_rem(60)
+  ))?;
+  Ok(rslt)
+}
+
+/// Returns the location of `path` inside `dir` or `None` if `path` tries to escape `dir`.
+fn file_path(dir: &Path, path: &str, buffer: &mut Vector<u8>) -> crate::Result<Option<PathBuf>> {
+  buffer.clear();
+  let decoded = if PercentDecode::new(path.as_bytes()).decode(buffer)? {
+    from_utf8_basic(buffer.as_slice()).map_err(crate::Error::from)?
+  } else {
+    path
+  };
+  let mut file_path = dir.to_path_buf();
+  for segment in str_split1(decoded, b'/') {
+    match segment {
+      "" | "." => {}
+      ".." => return Ok(None),
+      _ if segment.contains(['\0', ':', '\\']) => return Ok(None),
+      _ => file_path.push(segment),
+    }
+  }
+  Ok(Some(file_path))
+}
+
+/// Returns a read-only file located inside `dir` or `None` if such file doesn't exist or if `path`
+/// tries to escape `dir`.
+async fn open_file(dir: &Path, path: &str, buffer: &mut Vector<u8>) -> crate::Result<Option<File>> {
+  let Some(file_path) = file_path(dir, path, buffer)? else {
+    return Ok(None);
+  };
+  let file = match File::open(&file_path).await {
+    Err(err) if matches!(err.kind(), ErrorKind::NotFound | ErrorKind::PermissionDenied) => {
+      return Ok(None);
+    }
+    Err(err) => return Err(err.into()),
+    Ok(elem) => elem,
+  };
+  if !file.metadata().await?.is_file() {
+    return Ok(None);
+  }
+  Ok(Some(file))
+}
+
+/// Parses an IMF-fixdate into seconds since the UNIX epoch.
+fn parse_http_date(bytes: &[u8]) -> Option<u64> {
+  let &[_, _, _, b',', b' ', d0, d1, b' ', m0, m1, m2, b' ', y0, y1, y2, y3, b' ', h0, h1, b':', mi0, mi1, b':', s0, s1, b' ', b'G', b'M', b'T'] =
+    bytes
+  else {
+    return None;
+  };
+  let number = |digits: &[u8]| u64::from_radix_10(digits).ok();
+  let month = MONTHS.iter().position(|elem| elem.as_bytes() == [m0, m1, m2])?;
+  let days = days_from_civil(
+    number(&[y0, y1, y2, y3])?,
+    u64::try_from(month).ok()?.wrapping_add(1),
+    number(&[d0, d1])?,
+  )?;
+  let (hours, minutes, seconds) = (number(&[h0, h1])?, number(&[mi0, mi1])?, number(&[s0, s1])?);
+  if hours > 23 || minutes > 59 || seconds > 60 {
+    return None;
+  }
+  days
+    .checked_mul(86_400)?
+    .checked_add(hours.checked_mul(3600)?)?
+    .checked_add(minutes.checked_mul(60)?)?
+    .checked_add(seconds)
+}
+
+fn push_validators(headers: &mut Headers, etag: &str, last_modified: &str) -> crate::Result<()> {
+  headers.push_from_iter_many([
+    Header::from_name_and_value(KnownHeaderName::Etag.into(), [etag.as_bytes()].into_iter()),
+    Header::from_name_and_value(
+      KnownHeaderName::LastModified.into(),
+      [last_modified.as_bytes()].into_iter(),
+    ),
+  ])
+}
+
+fn guess_mime(path: &str) -> Mime {
+  let name = path.rsplit_once('/').map_or(path, |el| el.1);
+  name.rsplit_once('.').and_then(|(_, ext)| Mime::from_extension(ext)).unwrap_or(Mime::Bytes)
+}
+
+// https://howardhinnant.github.io/date_algorithms.html
+fn civil_from_days(days: u64) -> (u64, u8, u64) {
+  let z = days.wrapping_add(719_468);
+  let era = z.wrapping_div(146_097);
+  let doe = z.wrapping_rem(146_097);
+  let yoe = doe
+    .wrapping_sub(doe.wrapping_div(1460))
+    .wrapping_add(doe.wrapping_div(36_524))
+    .wrapping_sub(doe.wrapping_div(146_096))
+    .wrapping_div(365);
+  let doy = doe.wrapping_sub(
+    yoe.wrapping_mul(365).wrapping_add(yoe.wrapping_div(4)).wrapping_sub(yoe.wrapping_div(100)),
+  );
+  let mp = doy.wrapping_mul(5).wrapping_add(2).wrapping_div(153);
+  let day = doy.wrapping_sub(mp.wrapping_mul(153).wrapping_add(2).wrapping_div(5)).wrapping_add(1);
+  let month = if mp < 10 { mp.wrapping_add(3) } else { mp.wrapping_sub(9) };
+  let year = yoe.wrapping_add(era.wrapping_mul(400)).wrapping_add(u64::from(month <= 2));
+  (year, u8::try_from(month).unwrap_or_default(), day)
+}
+
+// https://howardhinnant.github.io/date_algorithms.html
+fn days_from_civil(year: u64, month: u64, day: u64) -> Option<u64> {
+  if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+    return None;
+  }
+  let local_year = if month <= 2 { year.checked_sub(1)? } else { year };
+  let era = local_year.checked_div(400)?;
+  let yoe = local_year.checked_rem(400)?;
+  let mp = if month > 2 { month.checked_sub(3)? } else { month.checked_add(9)? };
+  let doy =
+    mp.checked_mul(153)?.checked_add(2)?.checked_div(5)?.checked_add(day)?.checked_sub(1)?;
+  let doe = yoe
+    .checked_mul(365)?
+    .checked_add(yoe.checked_div(4)?)?
+    .checked_sub(yoe.checked_div(100)?)?
+    .checked_add(doy)?;
+  era.checked_mul(146_097)?.checked_add(doe)?.checked_sub(719_468)
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    http::{
+      server_framework::static_files::{
+        byte_range, evaluate, file_path, http_date, parse_http_date, ByteRange,
+      },
+      Header, Headers, KnownHeaderName, StatusCode,
+    },
+    misc::Vector,
+  };
+
+  #[test]
+  fn byte_ranges() {
+    assert_eq!(byte_range(b"bytes=0-9", 100), ByteRange::Partial(0, 9));
+    assert_eq!(byte_range(b"bytes=90-", 100), ByteRange::Partial(90, 99));
+    assert_eq!(byte_range(b"bytes=-10", 100), ByteRange::Partial(90, 99));
+    assert_eq!(byte_range(b"bytes=50-500", 100), ByteRange::Partial(50, 99));
+    assert_eq!(byte_range(b"bytes=100-", 100), ByteRange::Unsatisfiable);
+    assert_eq!(byte_range(b"bytes=-0", 100), ByteRange::Unsatisfiable);
+    assert_eq!(byte_range(b"bytes=9-0", 100), ByteRange::Full);
+    assert_eq!(byte_range(b"bytes=0-1,5-6", 100), ByteRange::Full);
+    assert_eq!(byte_range(b"items=0-1", 100), ByteRange::Full);
+  }
+
+  #[test]
+  fn conditional_requests() {
+    let etag = b"\"1-2\"";
+    let mut headers = Headers::new();
+    headers
+      .push_from_iter(Header::from_name_and_value(
+        KnownHeaderName::IfNoneMatch.into(),
+        [b"\"0-0\", W/\"1-2\"".as_slice()],
+      ))
+      .unwrap();
+    assert_eq!(evaluate(&headers, etag, 10, 0), (StatusCode::NotModified, None));
+    assert_eq!(evaluate(&headers, b"W/\"1-2\"", 10, 0), (StatusCode::NotModified, None));
+    assert_eq!(evaluate(&headers, b"\"1-3\"", 10, 0), (StatusCode::Ok, None));
+    headers.clear();
+    headers
+      .push_from_iter(Header::from_name_and_value(
+        KnownHeaderName::IfModifiedSince.into(),
+        [b"Sun, 06 Nov 1994 08:49:37 GMT".as_slice()],
+      ))
+      .unwrap();
+    assert_eq!(evaluate(&headers, etag, 10, 784_111_777), (StatusCode::NotModified, None));
+    assert_eq!(evaluate(&headers, etag, 10, 784_111_778), (StatusCode::Ok, None));
+    headers.clear();
+    headers
+      .push_from_iter_many([
+        Header::from_name_and_value(
+          KnownHeaderName::Range.into(),
+          [b"bytes=2-".as_slice()].into_iter(),
+        ),
+        Header::from_name_and_value(KnownHeaderName::IfRange.into(), [etag.as_slice()].into_iter()),
+      ])
+      .unwrap();
+    assert_eq!(evaluate(&headers, etag, 10, 0), (StatusCode::PartialContent, Some((2, 9))));
+    assert_eq!(evaluate(&headers, b"\"3-4\"", 10, 0), (StatusCode::Ok, None));
+    headers.clear();
+    headers
+      .push_from_iter_many([
+        Header::from_name_and_value(
+          KnownHeaderName::Range.into(),
+          [b"bytes=2-".as_slice()].into_iter(),
+        ),
+        Header::from_name_and_value(
+          KnownHeaderName::IfRange.into(),
+          [b"W/\"1-2\"".as_slice()].into_iter(),
+        ),
+      ])
+      .unwrap();
+    assert_eq!(evaluate(&headers, etag, 10, 0), (StatusCode::Ok, None));
+  }
+
+  #[test]
+  fn http_dates() {
+    assert_eq!(http_date(784_111_777).unwrap().as_str(), "Sun, 06 Nov 1994 08:49:37 GMT");
+    assert_eq!(http_date(0).unwrap().as_str(), "Thu, 01 Jan 1970 00:00:00 GMT");
+    assert_eq!(parse_http_date(b"Sun, 06 Nov 1994 08:49:37 GMT"), Some(784_111_777));
+    assert_eq!(parse_http_date(b"Tue, 29 Feb 2028 23:59:59 GMT"), Some(1_835_481_599));
+    assert_eq!(parse_http_date(b"Sunday, 06-Nov-94 08:49:37 GMT"), None);
+    assert_eq!(parse_http_date(b"Sun, 06 Nov 1994 99:49:37 GMT"), None);
+    assert_eq!(parse_http_date(b"Mon, 01 Jan 0000 00:00:00 GMT"), None);
+  }
+
+  #[test]
+  fn rejects_path_traversals() {
+    let dir = std::env::temp_dir();
+    let mut buffer = Vector::new();
+    assert!(file_path(&dir, "../etc/passwd", &mut buffer).unwrap().is_none());
+    assert!(file_path(&dir, "a/%2E%2E/%2E%2E/etc/passwd", &mut buffer).unwrap().is_none());
+    assert!(file_path(&dir, "..%5Cetc", &mut buffer).unwrap().is_none());
+    assert_eq!(file_path(&dir, "./a/b%20c", &mut buffer).unwrap(), Some(dir.join("a/b c")));
+  }
+}
@@ -0,0 +1,267 @@
+use crate::{
+  http::{
+    optioned_server::http2_tokio::manage_http2_conn,
+    server_framework::{
+      ConnAux, Endpoint, EndpointNode, Middleware, RouteMatch, Router, ServerFramework, StreamAux,
+    },
+    Header, KnownHeaderName, ManualServerStreamTokio, ManualStream, Method, Mime, ReqResBuffer,
+    Request, Response,
+  },
+  http2::{Http2Buffer, Http2DataTokio, Http2Params, Http2RecvStatus, Http2Tokio, ServerStream},
+  misc::{Arc, ArrayVector, Rng},
+};
+use core::{
+  net::{IpAddr, Ipv4Addr},
+  time::Duration,
+};
+use tokio::io::{DuplexStream, WriteHalf};
+
+const BUFFER_LEN: usize = 64 * 1024;
+
+type ManualStreamTokio<CA, SA> =
+  ManualServerStreamTokio<CA, Http2Buffer, SA, WriteHalf<DuplexStream>>;
+
+/// Server stream of the in-memory connections created by [`ServerFramework::test_client`].
+pub type TestServerStream =
+  ServerStream<Http2DataTokio<Http2Buffer, WriteHalf<DuplexStream>, false>>;
+
+impl<CA, CAC, E, EN, M, SA, SAC> ServerFramework<CA, CAC, E, EN, M, TestServerStream, SA, SAC>
+where
+  CA: Clone + ConnAux + Send + 'static,
+  CAC: Clone + Fn() -> CA::Init + Send + 'static,
+  E: From<crate::Error> + Send + 'static,
+  EN: EndpointNode<CA, E, TestServerStream, SA, auto(..): Send, manual(..): Send> + Send + 'static,
+  M: Middleware<CA, E, SA, req(..): Send, res(..): Send> + Send + 'static,
+  M::Aux: Send + 'static,
+  SA: StreamAux + Send + 'static,
+  SAC: Clone + Fn() -> SA::Init + Send + 'static,
+  Arc<Router<CA, E, EN, M, TestServerStream, SA>>: Send,
+  Router<CA, E, EN, M, TestServerStream, SA>: Send,
+  for<'any> &'any (SAC, Arc<Router<CA, E, EN, M, TestServerStream, SA>>): Send,
+  for<'any> &'any CA: Send,
+  for<'any> &'any EN: Send,
+  for<'any> &'any M: Send,
+  for<'any> &'any Router<CA, E, EN, M, TestServerStream, SA>: Send,
+{
+  /// Serves a single HTTP/2 connection over an in-memory pipe and returns a client attached to
+  /// the other end, which means that requests go through the whole stack without binding ports.
+  ///
+  /// Requests are seen as coming from `127.0.0.1`.
+  #[inline]
+  pub async fn test_client<RNG>(
+    self,
+    rng: RNG,
+    err_cb: impl Clone + Fn(E) + Send + 'static,
+  ) -> crate::Result<TestClient>
+  where
+    RNG: Clone + Rng + Send + 'static,
+  {
+    let Self { _ca_cb, _cp, _sa_cb, _router } = self;
+    let (client_io, server_io) = tokio::io::duplex(BUFFER_LEN);
+    let server_rng = rng.clone();
+    let server = tokio::spawn(async move {
+      let http2_buffer = Http2Buffer::new(server_rng);
+      let (frame_reader, http2) =
+        Http2Tokio::accept(http2_buffer, _cp._to_hp(), tokio::io::split(server_io)).await?;
+      Ok::<_, crate::Error>((frame_reader, http2))
+    });
+    // The dynamic table of the client encoder must support the size announced by the server.
+    let client_hp = Http2Params::default().set_max_hpack_len(_cp._max_hpack_len);
+    let (client_frame_reader, client) =
+      Http2Tokio::connect(Http2Buffer::new(rng), client_hp, tokio::io::split(client_io)).await?;
+    let _client_frame_reader_jh = tokio::spawn(client_frame_reader);
+    let (server_frame_reader, http2) =
+      server.await.map_err(|_err| crate::Error::ClosedConnection)??;
+    let _server_frame_reader_jh = tokio::spawn(server_frame_reader);
+    let conn_ca = CA::conn_aux(_ca_cb())?;
+    let _conn_jh = tokio::spawn(manage_http2_conn(
+      Self::_auto,
+      conn_ca,
+      err_cb,
+      http2,
+      Self::test_manual,
+      move |_, _, req, sa| Self::_route_params(req.rrd.uri.path(), &sa.1),
+      IpAddr::V4(Ipv4Addr::LOCALHOST),
+      core::future::pending::<Duration>(),
+      move || Ok(((_sa_cb.clone(), Arc::clone(&_router)), ReqResBuffer::empty())),
+      None,
+    ));
+    Ok(TestClient { http2: client })
+  }
+
+  #[inline]
+  async fn test_manual(
+    headers_aux: ArrayVector<RouteMatch, 4>,
+    manual_stream: ManualStreamTokio<
+      CA,
+      (impl Fn() -> SA::Init, Arc<Router<CA, E, EN, M, TestServerStream, SA>>),
+    >,
+  ) -> Result<(), E> {
+    let router_manual_stream = ManualStream {
+      conn_aux: manual_stream.conn_aux,
+      peer: manual_stream.peer,
+      protocol: manual_stream.protocol,
+      req: manual_stream.req,
+      stream: manual_stream.stream,
+      stream_aux: SA::stream_aux(manual_stream.stream_aux.0())?,
+    };
+    manual_stream.stream_aux.1.manual(router_manual_stream, (0, &headers_aux)).await?;
+    Ok(())
+  }
+}
+
+/// HTTP/2 client connected to an in-memory server. See [`ServerFramework::test_client`].
+///
+/// Each request uses a new stream of the same connection, as such, it is possible to send
+/// concurrent requests.
+#[derive(Clone, Debug)]
+pub struct TestClient {
+  http2: Http2Tokio<Http2Buffer, WriteHalf<DuplexStream>, true>,
+}
+
+impl TestClient {
+  /// Request builder of a `DELETE` request.
+  #[inline]
+  pub fn delete<'path>(&self, path: &'path str) -> TestReqBuilder<'_, 'path> {
+    self.req(Method::Delete, path)
+  }
+
+  /// Request builder of a `GET` request.
+  #[inline]
+  pub fn get<'path>(&self, path: &'path str) -> TestReqBuilder<'_, 'path> {
+    self.req(Method::Get, path)
+  }
+
+  /// Request builder of a `PATCH` request.
+  #[inline]
+  pub fn patch<'path>(&self, path: &'path str) -> TestReqBuilder<'_, 'path> {
+    self.req(Method::Patch, path)
+  }
+
+  /// Request builder of a `POST` request.
+  #[inline]
+  pub fn post<'path>(&self, path: &'path str) -> TestReqBuilder<'_, 'path> {
+    self.req(Method::Post, path)
+  }
+
+  /// Request builder of a `PUT` request.
+  #[inline]
+  pub fn put<'path>(&self, path: &'path str) -> TestReqBuilder<'_, 'path> {
+    self.req(Method::Put, path)
+  }
+
+  /// Request builder of an arbitrary `method`. `path` can contain a query, for example,
+  /// `/users?page=2`.
+  #[inline]
+  pub fn req<'path>(&self, method: Method, path: &'path str) -> TestReqBuilder<'_, 'path> {
+    TestReqBuilder { client: self, method, path, rrb: ReqResBuffer::empty() }
+  }
+
+  /// Sends `rrb` to `path` and awaits the response.
+  #[inline]
+  pub async fn send(
+    &self,
+    method: Method,
+    mut rrb: ReqResBuffer,
+    path: &str,
+  ) -> crate::Result<Response<ReqResBuffer>> {
+    rrb.uri.reset(format_args!("http://localhost{path}"))?;
+    let mut http2 = self.http2.clone();
+    let mut stream = http2.stream().await?;
+    if stream.send_req(Request::http2(method, &rrb), &rrb.uri.to_ref()).await?.is_closed() {
+      return Err(crate::Error::ClosedConnection);
+    }
+    let (hrs, res_rrb) = stream.recv_res(rrb).await?;
+    let status_code = match hrs {
+      Http2RecvStatus::Eos(elem) => elem,
+      _ => return Err(crate::Error::ClosedConnection),
+    };
+    stream.common().clear(false).await?;
+    Ok(Response::http2(res_rrb, status_code))
+  }
+}
+
+/// Request builder of [`TestClient`].
+#[derive(Debug)]
+pub struct TestReqBuilder<'client, 'path> {
+  client: &'client TestClient,
+  method: Method,
+  path: &'path str,
+  rrb: ReqResBuffer,
+}
+
+impl TestReqBuilder<'_, '_> {
+  /// Appends `elem` to the body.
+  #[inline]
+  pub fn body(mut self, elem: &[u8]) -> crate::Result<Self> {
+    self.rrb.body.extend_from_copyable_slice(elem)?;
+    Ok(self)
+  }
+
+  /// Media type of the body.
+  #[inline]
+  pub fn content_type(self, mime: Mime) -> crate::Result<Self> {
+    self.header(KnownHeaderName::ContentType.into(), mime.as_str().as_bytes())
+  }
+
+  /// Adds a header. `name` must be lowercase.
+  #[inline]
+  pub fn header(mut self, name: &str, value: &[u8]) -> crate::Result<Self> {
+    self.rrb.headers.push_from_iter(Header::from_name_and_value(name, [value]))?;
+    Ok(self)
+  }
+
+  /// Sends the request and awaits the response.
+  #[inline]
+  pub async fn send(self) -> crate::Result<Response<ReqResBuffer>> {
+    self.client.send(self.method, self.rrb, self.path).await
+  }
+}
+
+#[cfg(all(feature = "_async-tests", feature = "matchit", test))]
+mod tests {
+  use crate::{
+    http::{
+      server_framework::{get, post, PathOwned, Router, ServerFrameworkBuilder, State},
+      KnownHeaderName, Method, Mime, ReqResBuffer, StatusCode,
+    },
+    misc::{simple_seed, Xorshift64},
+  };
+
+  #[tokio::test]
+  async fn requests() {
+    async fn echo(state: State<'_, (), (), ReqResBuffer>) -> crate::Result<StatusCode> {
+      state.req.rrd.headers.clear();
+      Ok(StatusCode::Created)
+    }
+
+    async fn user(PathOwned(id): PathOwned<u32>) -> crate::Result<StatusCode> {
+      Ok(if id == 1 { StatusCode::Ok } else { StatusCode::NotFound })
+    }
+
+    let router = Router::paths(paths!(("/echo", post(echo)), ("/user/{id}", get(user)))).unwrap();
+    let client = ServerFrameworkBuilder::new(router)
+      .without_aux()
+      .test_client(Xorshift64::from(simple_seed()), |err: crate::Error| panic!("{err:?}"))
+      .await
+      .unwrap();
+
+    let res = client
+      .post("/echo")
+      .content_type(Mime::Json)
+      .unwrap()
+      .body(b"[1,2]")
+      .unwrap()
+      .send()
+      .await
+      .unwrap();
+    assert_eq!(res.status_code, StatusCode::Created);
+    assert_eq!(res.rrd.body.as_slice(), b"[1,2]");
+    assert!(res.rrd.headers.get_by_name(KnownHeaderName::ContentType.into()).is_none());
+
+    let (first, second) =
+      tokio::join!(client.get("/user/1").send(), client.req(Method::Get, "/user/2").send());
+    assert_eq!(first.unwrap().status_code, StatusCode::Ok);
+    assert_eq!(second.unwrap().status_code, StatusCode::NotFound);
+  }
+}
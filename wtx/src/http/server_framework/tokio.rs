@@ -1,13 +1,16 @@
+#[cfg(feature = "http1")]
+use crate::http1::Http1Buffer;
 use crate::{
   http::{
     server_framework::{
-      ConnAux, EndpointNode, Middleware, RouteMatch, Router, ServerFramework, StreamAux,
+      ConnAux, Endpoint, EndpointNode, Middleware, RouteMatch, Router, ServerFramework, StreamAux,
     },
     ManualServerStreamTokio, ManualStream, OptionedServer, ReqResBuffer, Request,
   },
   http2::{Http2Buffer, Http2DataTokio, ServerStream},
   misc::{Arc, ArrayVector, Rng},
 };
+use core::time::Duration;
 use tokio::net::tcp::OwnedWriteHalf;
 
 type Stream = ServerStream<Http2DataTokio<Http2Buffer, OwnedWriteHalf, false>>;
@@ -34,6 +37,7 @@ where
   Router<CA, E, EN, M, Stream, SA>: Send,
   for<'any> &'any (SAC, Arc<Router<CA, E, EN, M, Stream, SA>>): Send,
   for<'any> &'any CA: Send,
+  for<'any> &'any EN: Send,
   for<'any> &'any M: Send,
   for<'any> &'any Router<CA, E, EN, M, Stream, SA>: Send,
 {
@@ -61,12 +65,50 @@ where
         headers_cb(req)?;
         Ok(rslt)
       },
+      (core::future::pending(), Duration::ZERO),
       move || Ok(((_sa_cb.clone(), Arc::clone(&_router)), ReqResBuffer::empty())),
       (|| Ok(()), |_| {}, |_, stream| async move { Ok(stream.into_split()) }),
     )
     .await
   }
 
+  /// Starts listening to incoming cleartext HTTP/1.1 or HTTP/2 requests based on the given
+  /// `host`.
+  ///
+  /// HTTP/2 connections are established with prior knowledge or through `h2c` upgrades while
+  /// everything else is handled as HTTP/1.1. Endpoints that use manual streams are only supported
+  /// by HTTP/2 connections.
+  #[cfg(feature = "http1")]
+  #[inline]
+  pub async fn tokio_h2c<RNG>(
+    self,
+    host: &str,
+    rng: RNG,
+    err_cb: impl Clone + Fn(E) + Send + 'static,
+    headers_cb: impl Clone + Fn(Request<&mut ReqResBuffer>) -> Result<(), E> + Send + Sync + 'static,
+  ) -> crate::Result<()>
+  where
+    RNG: Clone + Rng + Send + 'static,
+  {
+    let Self { _ca_cb, _cp, _sa_cb, _router } = self;
+    OptionedServer::h2c_tokio(
+      host,
+      Self::_auto,
+      move || Ok((CA::conn_aux(_ca_cb())?, Http1Buffer::new(), _cp._to_h1p())),
+      err_cb,
+      move || Ok((Http2Buffer::new(rng.clone()), _cp._to_hp())),
+      Self::tokio_manual,
+      move |_, _, req, sa| {
+        let rslt = Self::_route_params(req.rrd.uri.path(), &sa.1)?;
+        headers_cb(req)?;
+        Ok(rslt)
+      },
+      (core::future::pending(), Duration::ZERO),
+      move || Ok(((_sa_cb.clone(), Arc::clone(&_router)), ReqResBuffer::empty())),
+    )
+    .await
+  }
+
   #[inline]
   async fn tokio_manual(
     headers_aux: ArrayVector<RouteMatch, 4>,
@@ -85,7 +127,7 @@ where
       stream: manual_stream.stream,
       stream_aux: SA::stream_aux(manual_stream.stream_aux.0())?,
     };
-    manual_stream.stream_aux.1.en.manual(router_manual_stream, (0, &headers_aux)).await?;
+    manual_stream.stream_aux.1.manual(router_manual_stream, (0, &headers_aux)).await?;
     Ok(())
   }
 }
@@ -105,6 +147,7 @@ where
   Router<CA, E, EN, M, StreamRustls, SA>: Send,
   for<'any> &'any (SAC, Arc<Router<CA, E, EN, M, StreamRustls, SA>>): Send,
   for<'any> &'any CA: Send,
+  for<'any> &'any EN: Send,
   for<'any> &'any M: Send,
   for<'any> &'any Router<CA, E, EN, M, StreamRustls, SA>: Send,
 {
@@ -133,6 +176,7 @@ where
         headers_cb(req)?;
         Ok(rslt)
       },
+      (core::future::pending(), Duration::ZERO),
       move || Ok(((_sa_cb.clone(), Arc::clone(&_router)), ReqResBuffer::empty())),
       (
         || {
@@ -165,7 +209,54 @@ where
       stream: manual_stream.stream,
       stream_aux: SA::stream_aux(manual_stream.stream_aux.0())?,
     };
-    manual_stream.stream_aux.1.en.manual(router_manual_stream, (0, &headers_aux)).await?;
+    manual_stream.stream_aux.1.manual(router_manual_stream, (0, &headers_aux)).await?;
     Ok(())
   }
 }
+
+#[cfg(feature = "http1")]
+impl<CA, CAC, E, EN, M, SA, SAC> ServerFramework<CA, CAC, E, EN, M, (), SA, SAC>
+where
+  CA: Clone + ConnAux + Send + 'static,
+  CAC: Clone + Fn() -> CA::Init + Send + 'static,
+  E: From<crate::Error> + Send + 'static,
+  EN: EndpointNode<CA, E, (), SA, auto(..): Send> + Send + 'static,
+  M: Middleware<CA, E, SA, req(..): Send, res(..): Send> + Send + 'static,
+  M::Aux: Send + 'static,
+  SA: StreamAux + Send + 'static,
+  SAC: Clone + Fn() -> SA::Init + Send + 'static,
+  Arc<Router<CA, E, EN, M, (), SA>>: Send,
+  Router<CA, E, EN, M, (), SA>: Send,
+  for<'any> &'any (SAC, Arc<Router<CA, E, EN, M, (), SA>>): Send,
+  for<'any> &'any CA: Send,
+  for<'any> &'any EN: Send,
+  for<'any> &'any M: Send,
+  for<'any> &'any Router<CA, E, EN, M, (), SA>: Send,
+{
+  /// Starts listening to incoming HTTP/1.1 requests based on the given `host`.
+  ///
+  /// Endpoints that use manual streams are not supported by HTTP/1.1 connections.
+  #[inline]
+  pub async fn tokio_http1(
+    self,
+    host: &str,
+    err_cb: impl Clone + Fn(E) + Send + 'static,
+    headers_cb: impl Clone + Fn(Request<&mut ReqResBuffer>) -> Result<(), E> + Send + Sync + 'static,
+  ) -> crate::Result<()> {
+    let Self { _ca_cb, _cp, _sa_cb, _router } = self;
+    OptionedServer::http1_tokio(
+      host,
+      Self::_auto,
+      move || Ok((CA::conn_aux(_ca_cb())?, Http1Buffer::new(), _cp._to_h1p())),
+      err_cb,
+      move |_, req, sa| {
+        let rslt = Self::_route_params(req.rrd.uri.path(), &sa.1)?.0;
+        headers_cb(req)?;
+        Ok(rslt)
+      },
+      move || Ok((_sa_cb.clone(), Arc::clone(&_router))),
+      (|| Ok(()), |_| {}, |_, stream| async move { Ok(stream.into_split()) }),
+    )
+    .await
+  }
+}
@@ -1,3 +1,4 @@
+mod session_csrf_guard;
 mod session_decoder;
 mod session_enforcer;
 mod session_error;
@@ -6,6 +7,7 @@ mod session_manager_builder;
 mod session_state;
 mod session_store;
 
+pub use session_csrf_guard::SessionCsrfGuard;
 pub use session_decoder::SessionDecoder;
 pub use session_enforcer::SessionEnforcer;
 pub use session_error::SessionError;
@@ -14,5 +16,6 @@ pub use session_manager_builder::SessionManagerBuilder;
 pub use session_state::SessionState;
 pub use session_store::SessionStore;
 
+type SessionCsrf = [u8; 16];
 type SessionId = [u8; 16];
 type SessionKey = [u8; 32];
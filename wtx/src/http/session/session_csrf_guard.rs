@@ -0,0 +1,243 @@
+use crate::{
+  http::{
+    server_framework::Middleware, KnownHeaderName, Method, ReqResBuffer, Request, Response,
+    SessionState, StatusCode,
+  },
+  misc::{bytes_split1, bytes_split_once1, LeaseMut, Vector},
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use core::{marker::PhantomData, net::IpAddr, ops::ControlFlow};
+
+/// Protects unsafe requests against Cross-Site Request Forgery.
+///
+/// Requests with methods other than `GET`, `HEAD`, `OPTIONS` or `TRACE` must contain the token
+/// returned by [`SessionState::csrf_token`] in a header or in a field of an
+/// `application/x-www-form-urlencoded` body. Requests without a session or with a missing or
+/// different token are answered with `403 Forbidden`.
+///
+/// Should be placed after [`crate::http::SessionDecoder`].
+#[derive(Debug)]
+pub struct SessionCsrfGuard<CS> {
+  field: &'static str,
+  header: &'static str,
+  origins: Option<Vector<&'static str>>,
+  phantom: PhantomData<CS>,
+  sec_fetch_site: bool,
+}
+
+impl<CS> SessionCsrfGuard<CS> {
+  /// Expects tokens in the `x-csrf-token` header or in the `csrf_token` form field.
+  #[inline]
+  pub const fn new() -> Self {
+    Self {
+      field: "csrf_token",
+      header: "x-csrf-token",
+      origins: None,
+      phantom: PhantomData,
+      sec_fetch_site: false,
+    }
+  }
+
+  /// Name of the form field that contains the token.
+  #[inline]
+  #[must_use]
+  pub const fn field(mut self, elem: &'static str) -> Self {
+    self.field = elem;
+    self
+  }
+
+  /// Lowercase name of the header that contains the token.
+  #[inline]
+  #[must_use]
+  pub const fn header(mut self, elem: &'static str) -> Self {
+    self.header = elem;
+    self
+  }
+
+  /// Unsafe requests with an `Origin` header must have one of the values of `elem`, for example,
+  /// `https://example.com`.
+  #[inline]
+  #[must_use]
+  pub fn origins(mut self, elem: Vector<&'static str>) -> Self {
+    self.origins = Some(elem);
+    self
+  }
+
+  /// Unsafe requests with a `Sec-Fetch-Site` header must be `same-origin` or `none`.
+  #[inline]
+  #[must_use]
+  pub const fn sec_fetch_site(mut self, elem: bool) -> Self {
+    self.sec_fetch_site = elem;
+    self
+  }
+
+  #[inline]
+  fn has_allowed_context(&self, req: &Request<ReqResBuffer>) -> bool {
+    if let Some(origins) = &self.origins {
+      if let Some(origin) = req.rrd.headers.get_by_name(KnownHeaderName::Origin.into()) {
+        if origins.iter().all(|elem| elem.as_bytes() != origin.value) {
+          return false;
+        }
+      }
+    }
+    if self.sec_fetch_site {
+      if let Some(site) = req.rrd.headers.get_by_name(b"sec-fetch-site") {
+        if site.value != b"same-origin" && site.value != b"none" {
+          return false;
+        }
+      }
+    }
+    true
+  }
+
+  #[inline]
+  fn has_valid_token(&self, csrf: &[u8; 16], req: &Request<ReqResBuffer>) -> bool {
+    let token = if let Some(header) = req.rrd.headers.get_by_name(self.header.as_bytes()) {
+      header.value
+    } else {
+      let is_form = req
+        .rrd
+        .headers
+        .get_by_name(KnownHeaderName::ContentType.into())
+        .is_some_and(|el| el.value.starts_with(b"application/x-www-form-urlencoded"));
+      if !is_form {
+        return false;
+      }
+      let field = bytes_split1(&req.rrd.body, b'&').find_map(|pair| {
+        let (name, value) = bytes_split_once1(pair, b'=')?;
+        (name == self.field.as_bytes()).then_some(value)
+      });
+      let Some(elem) = field else {
+        return false;
+      };
+      elem
+    };
+    let mut decoded = [0; 16];
+    if !matches!(URL_SAFE_NO_PAD.decode_slice(token, &mut decoded), Ok(16)) {
+      return false;
+    }
+    decoded.iter().zip(csrf).fold(0, |acc, (lhs, rhs)| acc | (lhs ^ rhs)) == 0
+  }
+}
+
+impl<CS> Default for SessionCsrfGuard<CS> {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<CA, CS, E, SA> Middleware<CA, E, SA> for SessionCsrfGuard<CS>
+where
+  CA: LeaseMut<Option<SessionState<CS>>>,
+  E: From<crate::Error>,
+{
+  type Aux = ();
+
+  #[inline]
+  fn aux(&self) -> Self::Aux {}
+
+  #[inline]
+  async fn req(
+    &self,
+    ca: &mut CA,
+    _: &mut Self::Aux,
+    _: IpAddr,
+    req: &mut Request<ReqResBuffer>,
+    _: &mut SA,
+  ) -> Result<ControlFlow<StatusCode, ()>, E> {
+    if matches!(req.method, Method::Get | Method::Head | Method::Options | Method::Trace) {
+      return Ok(ControlFlow::Continue(()));
+    }
+    let is_allowed = match ca.lease_mut() {
+      Some(state) => self.has_allowed_context(req) && self.has_valid_token(&state.csrf, req),
+      None => false,
+    };
+    if !is_allowed {
+      req.rrd.clear();
+      return Ok(ControlFlow::Break(StatusCode::Forbidden));
+    }
+    Ok(ControlFlow::Continue(()))
+  }
+
+  #[inline]
+  async fn res(
+    &self,
+    _: &mut CA,
+    _: &mut Self::Aux,
+    _: Response<&mut ReqResBuffer>,
+    _: &mut SA,
+  ) -> Result<ControlFlow<StatusCode, ()>, E> {
+    Ok(ControlFlow::Continue(()))
+  }
+}
+
+#[cfg(all(feature = "_async-tests", test))]
+mod tests {
+  use crate::{
+    http::{
+      server_framework::Middleware, Header, Method, ReqResBuffer, Request, SessionCsrfGuard,
+      SessionState, StatusCode,
+    },
+    misc::Vector,
+  };
+  use core::{
+    net::{IpAddr, Ipv4Addr},
+    ops::ControlFlow,
+  };
+
+  #[tokio::test]
+  async fn tokens() {
+    let state = SessionState { csrf: [7; 16], custom_state: (), expire: None, id: [0; 16] };
+    let token = state.csrf_token().unwrap();
+    let mut origins = Vector::new();
+    origins.push("https://a.com").unwrap();
+    let guard = SessionCsrfGuard::new().origins(origins).sec_fetch_site(true);
+    let form = alloc::format!("a=1&csrf_token={token}");
+    let x_csrf = ("x-csrf-token", token.as_str());
+    let form_ct = ("content-type", "application/x-www-form-urlencoded");
+    for (method, session, headers, body, status_code) in [
+      (Method::Get, None, &[][..], "", None),
+      (Method::Post, None, &[x_csrf][..], "", Some(StatusCode::Forbidden)),
+      (Method::Post, Some(state), &[][..], "", Some(StatusCode::Forbidden)),
+      (Method::Post, Some(state), &[("x-csrf-token", "abc")][..], "", Some(StatusCode::Forbidden)),
+      (Method::Post, Some(state), &[x_csrf][..], "", None),
+      (Method::Post, Some(state), &[form_ct][..], form.as_str(), None),
+      (
+        Method::Delete,
+        Some(state),
+        &[x_csrf, ("origin", "https://b.com")][..],
+        "",
+        Some(StatusCode::Forbidden),
+      ),
+      (Method::Delete, Some(state), &[x_csrf, ("origin", "https://a.com")][..], "", None),
+      (
+        Method::Put,
+        Some(state),
+        &[x_csrf, ("sec-fetch-site", "cross-site")][..],
+        "",
+        Some(StatusCode::Forbidden),
+      ),
+    ] {
+      let mut rrb = ReqResBuffer::empty();
+      rrb.body.extend_from_copyable_slice(body.as_bytes()).unwrap();
+      for (name, value) in headers {
+        rrb.headers.push_from_iter(Header::from_name_and_value(name, [value.as_bytes()])).unwrap();
+      }
+      let mut req = Request::http2(method, rrb);
+      let mut ca = session;
+      let rslt = Middleware::<_, crate::Error, ()>::req(
+        &guard,
+        &mut ca,
+        &mut (),
+        IpAddr::V4(Ipv4Addr::LOCALHOST),
+        &mut req,
+        &mut (),
+      )
+      .await
+      .unwrap();
+      let expected = status_code.map_or(ControlFlow::Continue(()), ControlFlow::Break);
+      assert_eq!(rslt, expected);
+    }
+  }
+}
@@ -9,7 +9,7 @@ use crate::{
   pool::{Pool, ResourceManager},
 };
 use chrono::DateTime;
-use core::ops::ControlFlow;
+use core::{net::IpAddr, ops::ControlFlow};
 use serde::de::DeserializeOwned;
 
 /// Decodes cookies received from requests and manages them.
@@ -53,6 +53,7 @@ where
     &self,
     ca: &mut CA,
     _: &mut Self::Aux,
+    _: IpAddr,
     req: &mut Request<ReqResBuffer>,
     _: &mut SA,
   ) -> Result<ControlFlow<StatusCode, ()>, E> {
@@ -5,7 +5,7 @@ use crate::{
   },
   misc::LeaseMut,
 };
-use core::{marker::PhantomData, ops::ControlFlow};
+use core::{marker::PhantomData, net::IpAddr, ops::ControlFlow};
 
 /// Enforces stored session in all requests.
 #[derive(Debug)]
@@ -39,6 +39,7 @@ where
     &self,
     ca: &mut CA,
     _: &mut Self::Aux,
+    _: IpAddr,
     req: &mut Request<ReqResBuffer>,
     _: &mut SA,
   ) -> Result<ControlFlow<StatusCode, ()>, E> {
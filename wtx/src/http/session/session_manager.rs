@@ -64,12 +64,16 @@ where
 
   /// Saves the session in the store and also modifies headers.
   ///
+  /// `rng` generates the encryption nonce and the CSRF token of the session, as such, it must be
+  /// a cryptographically secure generator that isn't shared by cloning. Cloned instances
+  /// produce the same sequence of values and consequently the same tokens.
+  ///
   /// The `rrd` body is used as a temporary buffer but no existing content is erased.
   #[inline]
   pub async fn set_session_cookie<RNG, RRD, S>(
     &mut self,
     custom_state: CS,
-    rng: RNG,
+    mut rng: RNG,
     rrd: &mut RRD,
     store: &mut S,
   ) -> Result<(), E>
@@ -81,13 +85,14 @@ where
   {
     let SessionManagerInner { cookie_def, phantom: _, key } = &mut *self.inner.lock().await;
     cookie_def.value.clear();
+    let csrf = rng.u8_16();
     let id = GenericTime::timestamp().map_err(Into::into)?.as_nanos().to_be_bytes();
     let local_state = if let Some(elem) = cookie_def.expire {
-      let local_state = SessionState { custom_state, expire: Some(elem), id };
+      let local_state = SessionState { csrf, custom_state, expire: Some(elem), id };
       store.create(&local_state).await?;
       local_state
     } else {
-      SessionState { custom_state, expire: None, id }
+      SessionState { csrf, custom_state, expire: None, id }
     };
     let idx = rrd.lease().body.len();
     serde_json::to_writer(&mut rrd.lease_mut().body, &local_state).map_err(Into::into)?;
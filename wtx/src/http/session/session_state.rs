@@ -1,9 +1,20 @@
-use crate::http::session::SessionId;
+use crate::{
+  http::session::{SessionCsrf, SessionId},
+  misc::ArrayString,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use chrono::{DateTime, Utc};
 
 /// Data that is saved in the corresponding store.
+///
+/// [`SessionState::csrf`] is part of the serialized cookie and of the stored data, which means
+/// that cookies and stored sessions created before its introduction are no longer decoded.
+/// PostgreSQL stores also need an additional `csrf BYTEA NOT NULL` column, as described in the
+/// implementation of [`crate::http::SessionStore`] for the PostgreSQL executor.
 #[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
 pub struct SessionState<CS> {
+  /// Synchronizer token verified by [`crate::http::SessionCsrfGuard`]
+  pub csrf: SessionCsrf,
   /// Custom state
   pub custom_state: CS,
   /// Cookie expiration
@@ -11,3 +22,14 @@ pub struct SessionState<CS> {
   /// Identifier
   pub id: SessionId,
 }
+
+impl<CS> SessionState<CS> {
+  /// Textual representation of [`SessionState::csrf`] that should be sent by clients in unsafe
+  /// requests, usually through a hidden form field or a header.
+  #[inline]
+  pub fn csrf_token(&self) -> crate::Result<ArrayString<22>> {
+    let mut buffer = [0; 22];
+    let len = URL_SAFE_NO_PAD.encode_slice(self.csrf, &mut buffer)?;
+    Ok(ArrayString::try_from(crate::misc::from_utf8_basic(buffer.get(..len).unwrap_or_default())?)?)
+  }
+}
@@ -148,7 +148,10 @@ mod postgres {
   ///
   /// ```sql
   /// CREATE TABLE session (
-  ///   expires_at TIMESTAMPTZ NOT NULL
+  ///   id BYTEA NOT NULL PRIMARY KEY,
+  ///   expires_at TIMESTAMPTZ NOT NULL,
+  ///   user_state SOME_TY NOT NULL,
+  ///   csrf BYTEA NOT NULL
   /// );
   /// ```
   ///
@@ -165,8 +168,8 @@ mod postgres {
     async fn create(&mut self, state: &SessionState<CS>) -> Result<(), E> {
       let _ = self
         .execute_with_stmt(
-          "INSERT INTO session VALUES ($1, $2, $3)",
-          (state.id.as_slice(), state.expire, &state.custom_state),
+          "INSERT INTO session VALUES ($1, $2, $3, $4)",
+          (state.id.as_slice(), state.expire, &state.custom_state, state.csrf.as_slice()),
         )
         .await?;
       Ok(())
@@ -186,10 +189,16 @@ mod postgres {
 
     #[inline]
     async fn read(&mut self, id: &SessionId) -> Result<Option<SessionState<CS>>, E> {
-      let rec = self.fetch_with_stmt("SELECT FROM session WHERE id=$1", (id.as_slice(),)).await?;
+      let rec = self
+        .fetch_with_stmt(
+          "SELECT id,expires_at,user_state,csrf FROM session WHERE id=$1",
+          (id.as_slice(),),
+        )
+        .await?;
       Ok(Some(SessionState {
-        custom_state: rec.decode(1)?,
-        expire: Some(rec.decode(2)?),
+        csrf: rec.decode::<_, &[u8]>(3)?.try_into().map_err(From::from)?,
+        custom_state: rec.decode(2)?,
+        expire: Some(rec.decode(1)?),
         id: rec.decode::<_, &[u8]>(0)?.try_into().map_err(From::from)?,
       }))
     }
@@ -198,8 +207,14 @@ mod postgres {
     async fn update(&mut self, id: &SessionId, state: &SessionState<CS>) -> Result<(), E> {
       let _ = self
         .execute_with_stmt(
-          "UPDATE session SET id=$1,expires_at=$2,user_state=$3 WHERE id=$4",
-          (state.id.as_slice(), state.expire, &state.custom_state, id.as_slice()),
+          "UPDATE session SET id=$1,expires_at=$2,user_state=$3,csrf=$4 WHERE id=$5",
+          (
+            state.id.as_slice(),
+            state.expire,
+            &state.custom_state,
+            state.csrf.as_slice(),
+            id.as_slice(),
+          ),
         )
         .await?;
       Ok(())
@@ -0,0 +1,223 @@
+//! HTTP/1.1
+//!
+//! 1. Messages are processed sequentially. Pipelined requests are answered in the same order they
+//!    were received.
+//! 2. Does not support the `CONNECT` method.
+//! 3. Messages that contain trailers are sent using the chunked transfer coding, otherwise
+//!    `Content-Length` is always used.
+//! 4. Clients always send requests in origin-form and don't pipeline requests.
+
+mod http1_buffer;
+mod http1_error;
+mod http1_params;
+pub(crate) mod misc;
+#[cfg(all(feature = "_async-tests", test))]
+mod tests;
+
+use crate::{
+  http::{Method, ReqResBuffer, ReqResData, ReqUri, Request, Response, StatusCode, Version},
+  http1::misc::{read_req, read_res, write_req, write_res},
+  misc::{Lease, StreamReader, StreamWriter},
+};
+pub use http1_buffer::Http1Buffer;
+pub use http1_error::Http1Error;
+pub use http1_params::Http1Params;
+
+pub(crate) const MAX_BODY_LEN: u32 = 4 * 1024 * 1024;
+pub(crate) const MAX_HEADERS_LEN: u32 = 8 * 1024;
+pub(crate) const MAX_HEADERS_NUM: usize = 64;
+pub(crate) const MAX_PIPELINED_REQS: u32 = 16;
+pub(crate) const READ_BUFFER_LEN: usize = 4 * 1024;
+
+/// Manages the exchange of messages of a single HTTP/1.1 connection.
+#[derive(Debug)]
+pub struct Http1<SR, SW, const IS_CLIENT: bool> {
+  hb: Http1Buffer,
+  hp: Http1Params,
+  is_closing: bool,
+  method: Method,
+  pipelined: u32,
+  sr: SR,
+  sw: SW,
+  version: Version,
+}
+
+impl<SR, SW, const IS_CLIENT: bool> Http1<SR, SW, IS_CLIENT> {
+  /// If the connection is going to be closed after the current exchange.
+  #[inline]
+  pub fn is_closing(&self) -> bool {
+    self.is_closing
+  }
+
+  /// Returns the inner buffer and the read/write parts of the connection.
+  #[inline]
+  pub fn into_parts(self) -> (Http1Buffer, SR, SW) {
+    (self.hb, self.sr, self.sw)
+  }
+
+  #[inline]
+  pub(crate) fn _set_is_closing(&mut self) {
+    self.is_closing = true;
+  }
+
+  #[inline]
+  pub(crate) fn _swap_buffer(&mut self, hb: &mut Http1Buffer) {
+    core::mem::swap(hb, &mut self.hb);
+  }
+}
+
+impl<SR, SW> Http1<SR, SW, false>
+where
+  SR: StreamReader,
+  SW: StreamWriter,
+{
+  /// Creates a new server instance.
+  ///
+  /// Bytes that were previously read from the connection and are still present in `hb` are
+  /// treated as the beginning of the first request.
+  #[inline]
+  pub fn accept(hb: Http1Buffer, hp: Http1Params, (sr, sw): (SR, SW)) -> Self {
+    Self {
+      hb,
+      hp,
+      is_closing: false,
+      method: Method::Get,
+      pipelined: 0,
+      sr,
+      sw,
+      version: Version::Http1_1,
+    }
+  }
+
+  /// Waits for a new request whose contents are written into `rrb`.
+  ///
+  /// Returns `None` if the connection was closed by the counterpart before sending any byte of a
+  /// new request or if the connection was already marked to be closed.
+  #[inline]
+  pub async fn recv_req(
+    &mut self,
+    rrb: &mut ReqResBuffer,
+  ) -> crate::Result<Option<(Method, Version)>> {
+    if self.is_closing {
+      return Ok(None);
+    }
+    let had_pending = !self.hb._pending().is_empty();
+    let rslt = read_req(&mut self.hb, &self.hp, rrb, &mut self.sr, &mut self.sw).await;
+    let (method, version, is_closing) = match rslt {
+      Err(err) => {
+        self.is_closing = true;
+        return Err(err);
+      }
+      Ok(None) => {
+        self.is_closing = true;
+        return Ok(None);
+      }
+      Ok(Some(elem)) => elem,
+    };
+    if had_pending {
+      self.pipelined = self.pipelined.wrapping_add(1);
+    } else {
+      self.pipelined = 0;
+    }
+    self.is_closing = is_closing || self.pipelined > self.hp.max_pipelined_reqs();
+    self.method = method;
+    self.version = version;
+    Ok(Some((method, version)))
+  }
+
+  /// Sends a response to the last received request.
+  ///
+  /// Returns `false` if the connection was closed after the response was written.
+  #[inline]
+  pub async fn send_res<RRD>(&mut self, res: Response<RRD>) -> crate::Result<bool>
+  where
+    RRD: ReqResData,
+    RRD::Body: Lease<[u8]>,
+  {
+    write_res(
+      res.rrd.body().lease(),
+      &mut self.hb,
+      res.rrd.headers(),
+      (self.is_closing, self.method, self.version),
+      res.status_code,
+      &mut self.sw,
+    )
+    .await?;
+    Ok(!self.is_closing)
+  }
+}
+
+impl<SR, SW> Http1<SR, SW, true>
+where
+  SR: StreamReader,
+  SW: StreamWriter,
+{
+  /// Creates a new client instance.
+  #[inline]
+  pub fn connect(mut hb: Http1Buffer, hp: Http1Params, (sr, sw): (SR, SW)) -> Self {
+    hb.clear();
+    Self {
+      hb,
+      hp,
+      is_closing: false,
+      method: Method::Get,
+      pipelined: 0,
+      sr,
+      sw,
+      version: Version::Http1_1,
+    }
+  }
+
+  /// Receives the response of the last sent request, whose contents are written into `rrb`.
+  ///
+  /// Informational responses are skipped.
+  #[inline]
+  pub async fn recv_res(&mut self, rrb: &mut ReqResBuffer) -> crate::Result<StatusCode> {
+    match read_res(&mut self.hb, &self.hp, self.method, rrb, &mut self.sr).await {
+      Err(err) => {
+        self.is_closing = true;
+        Err(err)
+      }
+      Ok((status_code, is_closing)) => {
+        self.is_closing = is_closing;
+        Ok(status_code)
+      }
+    }
+  }
+
+  /// Sends a request. The target, as well as the `Host` header, is extracted from `req_uri`.
+  ///
+  /// Must be followed by [`Self::recv_res`] before another request is sent.
+  #[inline]
+  pub async fn send_req<RRD>(
+    &mut self,
+    req: Request<RRD>,
+    req_uri: impl Into<ReqUri<'_>>,
+  ) -> crate::Result<()>
+  where
+    RRD: ReqResData,
+    RRD::Body: Lease<[u8]>,
+  {
+    if self.is_closing {
+      return Err(crate::Error::ClosedConnection);
+    }
+    let uri = match req_uri.into() {
+      ReqUri::Data => &req.rrd.uri().to_ref(),
+      ReqUri::Param(elem) => elem,
+    };
+    self.method = req.method;
+    let rslt = write_req(
+      req.rrd.body().lease(),
+      &mut self.hb,
+      req.rrd.headers(),
+      (!self.hp.keep_alive(), req.method),
+      uri,
+      &mut self.sw,
+    )
+    .await;
+    if rslt.is_err() {
+      self.is_closing = true;
+    }
+    rslt
+  }
+}
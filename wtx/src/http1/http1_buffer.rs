@@ -0,0 +1,58 @@
+use crate::misc::{Lease, LeaseMut, Vector};
+
+/// Groups all intermediate structures necessary to perform HTTP/1.1 connections.
+#[derive(Debug, Default)]
+pub struct Http1Buffer {
+  pub(crate) rb: Vector<u8>,
+  pub(crate) rb_begin: usize,
+  pub(crate) wb: Vector<u8>,
+}
+
+impl Http1Buffer {
+  /// Creates a new instance without pre-allocated resources.
+  #[inline]
+  pub const fn new() -> Self {
+    Self { rb: Vector::new(), rb_begin: 0, wb: Vector::new() }
+  }
+
+  /// Removes all stored bytes, including the ones that were read but not yet processed.
+  #[inline]
+  pub fn clear(&mut self) {
+    let Self { rb, rb_begin, wb } = self;
+    rb.clear();
+    *rb_begin = 0;
+    wb.clear();
+  }
+
+  /// Bytes that were read from the connection but not yet processed.
+  #[inline]
+  pub(crate) fn _pending(&self) -> &[u8] {
+    self.rb.get(self.rb_begin..).unwrap_or_default()
+  }
+
+  /// Moves pending bytes to the beginning of the buffer.
+  #[inline]
+  pub(crate) fn _shift(&mut self) {
+    if self.rb_begin == 0 {
+      return;
+    }
+    let len = self.rb.len();
+    self.rb.copy_within(self.rb_begin..len, 0);
+    self.rb.truncate(len.wrapping_sub(self.rb_begin));
+    self.rb_begin = 0;
+  }
+}
+
+impl Lease<Http1Buffer> for Http1Buffer {
+  #[inline]
+  fn lease(&self) -> &Http1Buffer {
+    self
+  }
+}
+
+impl LeaseMut<Http1Buffer> for Http1Buffer {
+  #[inline]
+  fn lease_mut(&mut self) -> &mut Http1Buffer {
+    self
+  }
+}
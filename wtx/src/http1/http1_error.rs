@@ -0,0 +1,44 @@
+use crate::http::StatusCode;
+
+/// HTTP/1.1 error
+#[derive(Debug)]
+pub enum Http1Error {
+  /// The body of a message exceeds the configured maximum length.
+  BodyIsTooLarge,
+  /// The section of headers exceeds the configured maximum length.
+  HeadersAreTooLarge,
+  /// Chunk sizes or chunk delimiters are malformed.
+  InvalidChunk,
+  /// `Content-Length` is not a valid number or has conflicting values.
+  InvalidContentLength,
+  /// HTTP/1.1 requests must contain a `Host` header that only contains an authority.
+  InvalidHost,
+  /// Request targets must be in origin, absolute or asterisk form.
+  InvalidRequestTarget,
+  /// `Transfer-Encoding` does not end with `chunked` or is sent along `Content-Length`.
+  InvalidTransferEncoding,
+  /// Tunnels created by the `CONNECT` method are not supported.
+  UnsupportedMethod,
+  /// Streams that are manually managed can only be used with HTTP/2 connections.
+  UnsupportedOperationMode,
+  /// Only HTTP/1.0 and HTTP/1.1 are supported.
+  UnsupportedVersion,
+}
+
+impl Http1Error {
+  /// Status code that should be sent to the counterpart when a received message is invalid.
+  #[inline]
+  pub fn status_code(&self) -> StatusCode {
+    match self {
+      Self::BodyIsTooLarge => StatusCode::PayloadTooLarge,
+      Self::HeadersAreTooLarge => StatusCode::RequestHeaderFieldsTooLarge,
+      Self::UnsupportedMethod | Self::UnsupportedOperationMode => StatusCode::NotImplemented,
+      Self::UnsupportedVersion => StatusCode::HttpVersionNotSupported,
+      Self::InvalidChunk
+      | Self::InvalidContentLength
+      | Self::InvalidHost
+      | Self::InvalidRequestTarget
+      | Self::InvalidTransferEncoding => StatusCode::BadRequest,
+    }
+  }
+}
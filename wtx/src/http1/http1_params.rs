@@ -0,0 +1,92 @@
+use crate::http1::{MAX_BODY_LEN, MAX_HEADERS_LEN, MAX_PIPELINED_REQS};
+
+/// Configurations of an HTTP/1.1 connection.
+#[derive(Clone, Copy, Debug)]
+pub struct Http1Params {
+  keep_alive: bool,
+  max_body_len: u32,
+  max_headers_len: u32,
+  max_pipelined_reqs: u32,
+}
+
+impl Http1Params {
+  /// Keep-alive
+  ///
+  /// If `false`, connections are closed after the first exchange regardless of what the
+  /// counterpart requested. Defaults to `true`.
+  #[inline]
+  pub const fn keep_alive(&self) -> bool {
+    self.keep_alive
+  }
+
+  /// Maximum request/response body length
+  ///
+  /// Applies to both fixed-length and chunked bodies. Defaults to 4 MiB.
+  #[inline]
+  pub const fn max_body_len(&self) -> u32 {
+    self.max_body_len
+  }
+
+  /// Maximum headers length
+  ///
+  /// The number of bytes of the start line plus all header fields. Defaults to 8 KiB.
+  #[inline]
+  pub const fn max_headers_len(&self) -> u32 {
+    self.max_headers_len
+  }
+
+  /// Maximum number of pipelined requests
+  ///
+  /// Pipelined requests are the ones that arrive before the response of a previous request was
+  /// sent. Once this limit is reached, the current request is answered and the connection is
+  /// closed, which forces clients to retry the remaining requests in a new connection. Defaults
+  /// to 16 requests.
+  #[inline]
+  pub const fn max_pipelined_reqs(&self) -> u32 {
+    self.max_pipelined_reqs
+  }
+
+  /// Mutable version of [`Self::keep_alive`].
+  #[inline]
+  #[must_use]
+  pub fn set_keep_alive(mut self, value: bool) -> Self {
+    self.keep_alive = value;
+    self
+  }
+
+  /// Mutable version of [`Self::max_body_len`].
+  #[inline]
+  #[must_use]
+  pub fn set_max_body_len(mut self, value: u32) -> Self {
+    self.max_body_len = value;
+    self
+  }
+
+  /// Mutable version of [`Self::max_headers_len`].
+  #[inline]
+  #[must_use]
+  pub fn set_max_headers_len(mut self, value: u32) -> Self {
+    self.max_headers_len = value;
+    self
+  }
+
+  /// Mutable version of [`Self::max_pipelined_reqs`].
+  #[inline]
+  #[must_use]
+  pub fn set_max_pipelined_reqs(mut self, value: u32) -> Self {
+    self.max_pipelined_reqs = value;
+    self
+  }
+}
+
+impl Default for Http1Params {
+  #[inline]
+  fn default() -> Self {
+    Self {
+      keep_alive: true,
+      max_body_len: MAX_BODY_LEN,
+      max_headers_len: MAX_HEADERS_LEN,
+      max_pipelined_reqs: MAX_PIPELINED_REQS,
+    }
+  }
+}
@@ -0,0 +1,504 @@
+use crate::{
+  http::{
+    Header, Headers, HttpError, KnownHeaderName, Method, ReqResBuffer, StatusCode, Version,
+    _HeaderNameBuffer,
+  },
+  http1::{Http1Buffer, Http1Error, Http1Params, MAX_HEADERS_NUM, READ_BUFFER_LEN},
+  misc::{
+    bytes_split1, from_utf8_basic, u64_array_string, BufferMode, FromRadix10, StreamReader,
+    StreamWriter, UriRef, Usize, Vector,
+  },
+};
+use core::fmt::Write as _;
+use httparse::{Status, EMPTY_HEADER};
+
+/// How the length of a body is determined.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum BodyLen {
+  /// Sequence of chunks
+  Chunked,
+  /// Known number of bytes
+  Fixed(usize),
+  /// No framing header was received
+  #[default]
+  Unspecified,
+}
+
+/// Information extracted from received headers that dictate how a message should be handled.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct HeadersInfo {
+  pub(crate) body_len: BodyLen,
+  pub(crate) expects_continue: bool,
+  pub(crate) has_close: bool,
+  pub(crate) has_keep_alive: bool,
+}
+
+/// Pushes all received `httparse` headers into `headers` with lowercase names.
+pub(crate) fn push_headers(
+  headers: &mut Headers,
+  is_trailer: bool,
+  received: &[httparse::Header<'_>],
+) -> crate::Result<HeadersInfo> {
+  let mut info = HeadersInfo::default();
+  for header in received {
+    let mut name = _HeaderNameBuffer::new();
+    name
+      .extend_from_copyable_slice(header.name.as_bytes())
+      .map_err(|_err| HttpError::HeaderFieldIsTooLarge)?;
+    name.make_ascii_lowercase();
+    headers.push_from_iter(Header {
+      is_sensitive: false,
+      is_trailer,
+      name: from_utf8_basic(&name)?,
+      value: [header.value],
+    })?;
+    if is_trailer {
+      continue;
+    }
+    let value = header.value.trim_ascii();
+    match name.as_slice() {
+      b"connection" => {
+        for token in bytes_split1(value, b',').map(<[u8]>::trim_ascii) {
+          if token.eq_ignore_ascii_case(b"close") {
+            info.has_close = true;
+          } else if token.eq_ignore_ascii_case(b"keep-alive") {
+            info.has_keep_alive = true;
+          }
+        }
+      }
+      b"content-length" => {
+        let len = usize::from_radix_10(value).map_err(|_err| Http1Error::InvalidContentLength)?;
+        info.body_len = match info.body_len {
+          BodyLen::Chunked => return Err(Http1Error::InvalidTransferEncoding.into()),
+          BodyLen::Fixed(prev) if prev != len => {
+            return Err(Http1Error::InvalidContentLength.into())
+          }
+          BodyLen::Fixed(_) | BodyLen::Unspecified => BodyLen::Fixed(len),
+        };
+      }
+      b"expect" => {
+        info.expects_continue = value.eq_ignore_ascii_case(b"100-continue");
+      }
+      b"transfer-encoding" => {
+        let last = bytes_split1(value, b',').last().unwrap_or_default().trim_ascii();
+        if info.body_len != BodyLen::Unspecified || !last.eq_ignore_ascii_case(b"chunked") {
+          return Err(Http1Error::InvalidTransferEncoding.into());
+        }
+        info.body_len = BodyLen::Chunked;
+      }
+      _ => {}
+    }
+  }
+  Ok(info)
+}
+
+/// Reads more bytes into the internal buffer, returning the number of read bytes.
+pub(crate) async fn read_more<SR>(
+  additional: usize,
+  hb: &mut Http1Buffer,
+  sr: &mut SR,
+) -> crate::Result<usize>
+where
+  SR: StreamReader,
+{
+  let prev_len = hb.rb.len();
+  hb.rb.expand(BufferMode::Additional(additional.max(READ_BUFFER_LEN)), 0)?;
+  let rslt = sr.read(hb.rb.get_mut(prev_len..).unwrap_or_default()).await;
+  let read = *rslt.as_ref().unwrap_or(&0);
+  hb.rb.truncate(prev_len.wrapping_add(read));
+  rslt
+}
+
+/// Reads a request and its body. Returns the method, the version and if the connection should be
+/// closed after the response is sent.
+pub(crate) async fn read_req<SR, SW>(
+  hb: &mut Http1Buffer,
+  hp: &Http1Params,
+  rrb: &mut ReqResBuffer,
+  sr: &mut SR,
+  sw: &mut SW,
+) -> crate::Result<Option<(Method, Version, bool)>>
+where
+  SR: StreamReader,
+  SW: StreamWriter,
+{
+  rrb.clear();
+  hb._shift();
+  let max_headers_len = *Usize::from(hp.max_headers_len());
+  let (info, method, version) = loop {
+    let pending = hb._pending();
+    if !pending.is_empty() {
+      let mut headers = [EMPTY_HEADER; MAX_HEADERS_NUM];
+      let mut req = httparse::Request::new(&mut headers);
+      if let Status::Complete(len) = req.parse(pending)? {
+        if len > max_headers_len {
+          return Err(Http1Error::HeadersAreTooLarge.into());
+        }
+        let rslt = manage_req(&req, rrb)?;
+        hb.rb_begin = hb.rb_begin.wrapping_add(len);
+        break rslt;
+      }
+      if pending.len() >= max_headers_len {
+        return Err(Http1Error::HeadersAreTooLarge.into());
+      }
+    }
+    if read_more(0, hb, sr).await? == 0 {
+      if hb._pending().is_empty() {
+        return Ok(None);
+      }
+      return Err(crate::Error::UnexpectedStreamReadEOF);
+    }
+  };
+  let is_closing =
+    !hp.keep_alive() || info.has_close || (version == Version::Http1 && !info.has_keep_alive);
+  if info.expects_continue
+    && version == Version::Http1_1
+    && info.body_len != BodyLen::Fixed(0)
+    && info.body_len != BodyLen::Unspecified
+    && hb._pending().is_empty()
+  {
+    sw.write_all(b"HTTP/1.1 100 Continue\r\n\r\n").await?;
+  }
+  read_body(info.body_len, hb, hp, &mut rrb.body, &mut rrb.headers, sr).await?;
+  Ok(Some((method, version, is_closing)))
+}
+
+/// Reads a response and its body. Returns the status code and if the connection should be closed
+/// after the current exchange.
+///
+/// Informational responses are ignored.
+pub(crate) async fn read_res<SR>(
+  hb: &mut Http1Buffer,
+  hp: &Http1Params,
+  method: Method,
+  rrb: &mut ReqResBuffer,
+  sr: &mut SR,
+) -> crate::Result<(StatusCode, bool)>
+where
+  SR: StreamReader,
+{
+  let max_headers_len = *Usize::from(hp.max_headers_len());
+  loop {
+    rrb.clear();
+    hb._shift();
+    let (code, info, version) = loop {
+      let pending = hb._pending();
+      if !pending.is_empty() {
+        let mut headers = [EMPTY_HEADER; MAX_HEADERS_NUM];
+        let mut res = httparse::Response::new(&mut headers);
+        if let Status::Complete(len) = res.parse(pending)? {
+          if len > max_headers_len {
+            return Err(Http1Error::HeadersAreTooLarge.into());
+          }
+          let version = match res.version {
+            Some(0) => Version::Http1,
+            Some(1) => Version::Http1_1,
+            _ => return Err(Http1Error::UnsupportedVersion.into()),
+          };
+          let info = push_headers(&mut rrb.headers, false, res.headers)?;
+          let code = res.code.unwrap_or_default();
+          hb.rb_begin = hb.rb_begin.wrapping_add(len);
+          break (code, info, version);
+        }
+        if pending.len() >= max_headers_len {
+          return Err(Http1Error::HeadersAreTooLarge.into());
+        }
+      }
+      if read_more(0, hb, sr).await? == 0 {
+        return Err(crate::Error::UnexpectedStreamReadEOF);
+      }
+    };
+    if (100..=199).contains(&code) && code != 101 {
+      continue;
+    }
+    let status_code = StatusCode::try_from(code)?;
+    let mut is_closing =
+      !hp.keep_alive() || info.has_close || (version == Version::Http1 && !info.has_keep_alive);
+    if method == Method::Head || matches!(code, 101 | 204 | 304) {
+      return Ok((status_code, is_closing || code == 101));
+    }
+    if info.body_len == BodyLen::Unspecified {
+      read_until_eof(hb, hp, &mut rrb.body, sr).await?;
+      is_closing = true;
+    } else {
+      read_body(info.body_len, hb, hp, &mut rrb.body, &mut rrb.headers, sr).await?;
+    }
+    return Ok((status_code, is_closing));
+  }
+}
+
+/// Writes a request whose target is always sent in origin-form.
+pub(crate) async fn write_req<SW>(
+  body: &[u8],
+  hb: &mut Http1Buffer,
+  headers: &Headers,
+  (is_closing, method): (bool, Method),
+  uri: &UriRef<'_>,
+  sw: &mut SW,
+) -> crate::Result<()>
+where
+  SW: StreamWriter,
+{
+  let wb = &mut hb.wb;
+  wb.clear();
+  let _ = wb.extend_from_copyable_slices([
+    method.strings().custom[0].as_bytes(),
+    b" ",
+    uri.relative_reference_slash().as_bytes(),
+    b" HTTP/1.1\r\n",
+  ])?;
+  if headers.get_by_name(KnownHeaderName::Host.into()).is_none() {
+    let _ = wb.extend_from_copyable_slices([b"host: ", uri.host().as_bytes(), b"\r\n"])?;
+  }
+  let has_body = !body.is_empty() || matches!(method, Method::Patch | Method::Post | Method::Put);
+  write_msg(body, (has_body, true), headers, (is_closing, Version::Http1_1), wb, sw).await
+}
+
+/// Writes a response using the framing that best suits the current connection.
+pub(crate) async fn write_res<SW>(
+  body: &[u8],
+  hb: &mut Http1Buffer,
+  headers: &Headers,
+  (is_closing, method, version): (bool, Method, Version),
+  status_code: StatusCode,
+  sw: &mut SW,
+) -> crate::Result<()>
+where
+  SW: StreamWriter,
+{
+  let code = u16::from(status_code);
+  let has_body = !matches!(code, 100..=199 | 204 | 304);
+  let wb = &mut hb.wb;
+  wb.clear();
+  let _ = wb.extend_from_copyable_slices([
+    b"HTTP/1.1 ".as_slice(),
+    u64_array_string(code.into()).as_bytes(),
+    b" \r\n",
+  ])?;
+  write_msg(body, (has_body, method != Method::Head), headers, (is_closing, version), wb, sw).await
+}
+
+async fn fill<SR>(hb: &mut Http1Buffer, len: usize, sr: &mut SR) -> crate::Result<()>
+where
+  SR: StreamReader,
+{
+  loop {
+    let pending_len = hb._pending().len();
+    if pending_len >= len {
+      return Ok(());
+    }
+    if read_more(len.wrapping_sub(pending_len), hb, sr).await? == 0 {
+      return Err(crate::Error::UnexpectedStreamReadEOF);
+    }
+  }
+}
+
+/// Headers that are managed by the HTTP/1.1 implementation.
+fn is_framing_header(name: &[u8]) -> bool {
+  name == <&[u8]>::from(KnownHeaderName::Connection)
+    || name == <&[u8]>::from(KnownHeaderName::ContentLength)
+    || name == <&[u8]>::from(KnownHeaderName::KeepAlive)
+    || name == <&[u8]>::from(KnownHeaderName::TransferEncoding)
+}
+
+fn manage_req(
+  req: &httparse::Request<'_, '_>,
+  rrb: &mut ReqResBuffer,
+) -> crate::Result<(HeadersInfo, Method, Version)> {
+  let method_bytes = req.method.unwrap_or_default().as_bytes();
+  let method = Method::ALL
+    .into_iter()
+    .find(|el| el.strings().custom[0].as_bytes() == method_bytes)
+    .ok_or(HttpError::MissingRequestMethod)?;
+  if method == Method::Connect {
+    return Err(Http1Error::UnsupportedMethod.into());
+  }
+  let version = match req.version {
+    Some(0) => Version::Http1,
+    Some(1) => Version::Http1_1,
+    _ => return Err(Http1Error::UnsupportedVersion.into()),
+  };
+  let info = push_headers(&mut rrb.headers, false, req.headers)?;
+  let host = match rrb.headers.get_by_name(KnownHeaderName::Host.into()) {
+    Some(el) => {
+      if el.value.iter().any(|byte| matches!(byte, b'/' | b'?' | b'#' | b'@' | b'\\')) {
+        return Err(Http1Error::InvalidHost.into());
+      }
+      from_utf8_basic(el.value.trim_ascii())?
+    }
+    None if version == Version::Http1 => "",
+    None => return Err(Http1Error::InvalidHost.into()),
+  };
+  let path = req.path.unwrap_or_default();
+  match path.as_bytes() {
+    [b'/', ..] => rrb.uri.reset(format_args!("http://{host}{path}"))?,
+    [b'*'] if method == Method::Options => rrb.uri.reset(format_args!("http://{host}"))?,
+    [b'h', b't', b't', b'p', b':', b'/', b'/', ..]
+    | [b'h', b't', b't', b'p', b's', b':', b'/', b'/', ..] => {
+      rrb.uri.reset(format_args!("{path}"))?
+    }
+    _ => return Err(Http1Error::InvalidRequestTarget.into()),
+  }
+  Ok((info, method, version))
+}
+
+async fn read_body<SR>(
+  body_len: BodyLen,
+  hb: &mut Http1Buffer,
+  hp: &Http1Params,
+  body: &mut Vector<u8>,
+  headers: &mut Headers,
+  sr: &mut SR,
+) -> crate::Result<()>
+where
+  SR: StreamReader,
+{
+  let max_body_len = *Usize::from(hp.max_body_len());
+  match body_len {
+    BodyLen::Chunked => {}
+    BodyLen::Fixed(len) => {
+      if len > max_body_len {
+        return Err(Http1Error::BodyIsTooLarge.into());
+      }
+      fill(hb, len, sr).await?;
+      body.extend_from_copyable_slice(hb._pending().get(..len).unwrap_or_default())?;
+      hb.rb_begin = hb.rb_begin.wrapping_add(len);
+      return Ok(());
+    }
+    BodyLen::Unspecified => return Ok(()),
+  }
+  loop {
+    let (consumed, chunk_len) = loop {
+      match httparse::parse_chunk_size(hb._pending()) {
+        Ok(Status::Complete(elem)) => break elem,
+        Ok(Status::Partial) => {
+          if read_more(0, hb, sr).await? == 0 {
+            return Err(crate::Error::UnexpectedStreamReadEOF);
+          }
+        }
+        Err(_err) => return Err(Http1Error::InvalidChunk.into()),
+      }
+    };
+    hb.rb_begin = hb.rb_begin.wrapping_add(consumed);
+    if chunk_len == 0 {
+      break;
+    }
+    let len = Usize::from_u64(chunk_len).ok_or(Http1Error::BodyIsTooLarge)?.into_usize();
+    if body.len().saturating_add(len) > max_body_len {
+      return Err(Http1Error::BodyIsTooLarge.into());
+    }
+    let len_with_delimiter = len.wrapping_add(2);
+    fill(hb, len_with_delimiter, sr).await?;
+    let Some((data, [b'\r', b'\n', ..])) = hb._pending().split_at_checked(len) else {
+      return Err(Http1Error::InvalidChunk.into());
+    };
+    body.extend_from_copyable_slice(data)?;
+    hb.rb_begin = hb.rb_begin.wrapping_add(len_with_delimiter);
+  }
+  let max_headers_len = *Usize::from(hp.max_headers_len());
+  loop {
+    let pending = hb._pending();
+    let mut trailers = [EMPTY_HEADER; MAX_HEADERS_NUM];
+    if let Status::Complete((len, parsed)) = httparse::parse_headers(pending, &mut trailers)? {
+      let _info = push_headers(headers, true, parsed)?;
+      hb.rb_begin = hb.rb_begin.wrapping_add(len);
+      return Ok(());
+    }
+    if pending.len() >= max_headers_len {
+      return Err(Http1Error::HeadersAreTooLarge.into());
+    }
+    if read_more(0, hb, sr).await? == 0 {
+      return Err(crate::Error::UnexpectedStreamReadEOF);
+    }
+  }
+}
+
+async fn read_until_eof<SR>(
+  hb: &mut Http1Buffer,
+  hp: &Http1Params,
+  body: &mut Vector<u8>,
+  sr: &mut SR,
+) -> crate::Result<()>
+where
+  SR: StreamReader,
+{
+  let max_body_len = *Usize::from(hp.max_body_len());
+  loop {
+    let pending = hb._pending();
+    if body.len().saturating_add(pending.len()) > max_body_len {
+      return Err(Http1Error::BodyIsTooLarge.into());
+    }
+    body.extend_from_copyable_slice(pending)?;
+    hb.rb_begin = hb.rb.len();
+    if read_more(0, hb, sr).await? == 0 {
+      return Ok(());
+    }
+  }
+}
+
+/// Writes the header section, whose start line is already in `wb`, followed by the body.
+///
+/// Framing headers are only sent if the message `has_body` and the actual bytes are only written
+/// if the message also `sends_body`.
+async fn write_msg<SW>(
+  body: &[u8],
+  (has_body, sends_body): (bool, bool),
+  headers: &Headers,
+  (is_closing, version): (bool, Version),
+  wb: &mut Vector<u8>,
+  sw: &mut SW,
+) -> crate::Result<()>
+where
+  SW: StreamWriter,
+{
+  let is_chunked = has_body && version == Version::Http1_1 && headers.trailers().has_any();
+  for header in headers.iter() {
+    if header.is_trailer || is_framing_header(header.name.as_bytes()) {
+      continue;
+    }
+    let _ =
+      wb.extend_from_copyable_slices([header.name.as_bytes(), b": ", header.value, b"\r\n"])?;
+  }
+  if has_body {
+    if is_chunked {
+      wb.extend_from_copyable_slice(b"transfer-encoding: chunked\r\n")?;
+    } else {
+      let _ = wb.extend_from_copyable_slices([
+        b"content-length: ".as_slice(),
+        u64_array_string(Usize::from(body.len()).into_u64()).as_bytes(),
+        b"\r\n",
+      ])?;
+    }
+  }
+  if is_closing {
+    wb.extend_from_copyable_slice(b"connection: close\r\n")?;
+  } else if version == Version::Http1 {
+    wb.extend_from_copyable_slice(b"connection: keep-alive\r\n")?;
+  }
+  wb.extend_from_copyable_slice(b"\r\n")?;
+  if !has_body || !sends_body {
+    sw.write_all(wb).await?;
+    return Ok(());
+  }
+  if !is_chunked {
+    sw.write_all_vectored(&[wb, body]).await?;
+    return Ok(());
+  }
+  if !body.is_empty() {
+    wb.write_fmt(format_args!("{:x}\r\n", body.len()))?;
+    sw.write_all_vectored(&[wb, body]).await?;
+    wb.clear();
+    wb.extend_from_copyable_slice(b"\r\n")?;
+  }
+  wb.extend_from_copyable_slice(b"0\r\n")?;
+  for header in headers.iter() {
+    if !header.is_trailer {
+      continue;
+    }
+    let _ =
+      wb.extend_from_copyable_slices([header.name.as_bytes(), b": ", header.value, b"\r\n"])?;
+  }
+  wb.extend_from_copyable_slice(b"\r\n")?;
+  sw.write_all(wb).await?;
+  Ok(())
+}
@@ -0,0 +1,104 @@
+use crate::{
+  http::{Method, ReqResBuffer, ReqUri, Request, Response, StatusCode, Version},
+  http1::{Http1, Http1Buffer, Http1Params},
+  misc::from_utf8_basic,
+};
+use tokio::io::{AsyncReadExt, AsyncWriteExt, DuplexStream};
+
+#[tokio::test]
+async fn chunked_and_pipelined_requests() {
+  let (mut client, server) = tokio::io::duplex(1024);
+  client
+    .write_all(
+      b"GET /aaa HTTP/1.1\r\nHost: localhost\r\n\r\n\
+      POST /bbb?c=d HTTP/1.1\r\nHost: localhost\r\nTransfer-Encoding: chunked\r\n\r\n\
+      3\r\nabc\r\n2\r\nde\r\n0\r\nfoo: bar\r\n\r\n",
+    )
+    .await
+    .unwrap();
+  let mut http1 =
+    Http1::accept(Http1Buffer::new(), Http1Params::default(), tokio::io::split(server));
+  let mut rrb = ReqResBuffer::empty();
+
+  assert_eq!(http1.recv_req(&mut rrb).await.unwrap(), Some((Method::Get, Version::Http1_1)));
+  assert_eq!(rrb.uri.path(), "/aaa");
+  rrb.clear();
+  rrb.body.extend_from_copyable_slice(b"hello").unwrap();
+  assert!(http1.send_res(Response::http2(&rrb, StatusCode::Ok)).await.unwrap());
+
+  assert_eq!(http1.recv_req(&mut rrb).await.unwrap(), Some((Method::Post, Version::Http1_1)));
+  assert_eq!(rrb.uri.path(), "/bbb");
+  assert_eq!(rrb.uri.query_and_fragment(), "?c=d");
+  assert_eq!(rrb.body.as_slice(), b"abcde");
+  assert_eq!(rrb.headers.get_by_name(b"foo").unwrap().value, b"bar");
+  rrb.clear();
+  assert!(http1.send_res(Response::http2(&rrb, StatusCode::NoContent)).await.unwrap());
+
+  drop(http1);
+  let mut bytes = alloc::vec::Vec::new();
+  let _ = client.read_to_end(&mut bytes).await.unwrap();
+  assert_eq!(
+    from_utf8_basic(&bytes).unwrap(),
+    "HTTP/1.1 200 \r\ncontent-length: 5\r\n\r\nhelloHTTP/1.1 204 \r\n\r\n"
+  );
+}
+
+#[tokio::test]
+async fn closes_when_requested() {
+  let (mut client, server) = tokio::io::duplex(1024);
+  client
+    .write_all(b"GET / HTTP/1.0\r\n\r\nGET / HTTP/1.1\r\nHost: localhost\r\n\r\n")
+    .await
+    .unwrap();
+  let mut http1 =
+    Http1::accept(Http1Buffer::new(), Http1Params::default(), tokio::io::split(server));
+  let mut rrb = ReqResBuffer::empty();
+  assert_eq!(http1.recv_req(&mut rrb).await.unwrap(), Some((Method::Get, Version::Http1)));
+  assert!(http1.is_closing());
+  assert!(!http1.send_res(Response::http2(&rrb, StatusCode::Ok)).await.unwrap());
+  assert_eq!(http1.recv_req(&mut rrb).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn client_exchanges() {
+  async fn read_req<'bytes>(bytes: &'bytes mut [u8], server: &mut DuplexStream) -> &'bytes str {
+    let _ = server.read_exact(bytes).await.unwrap();
+    from_utf8_basic(bytes).unwrap()
+  }
+
+  let (client, mut server) = tokio::io::duplex(1024);
+  let mut http1 =
+    Http1::connect(Http1Buffer::new(), Http1Params::default(), tokio::io::split(client));
+  let mut rrb = ReqResBuffer::empty();
+  rrb.uri.reset(format_args!("http://localhost:9000/foo?bar=baz")).unwrap();
+  rrb.body.extend_from_copyable_slice(b"abc").unwrap();
+
+  http1.send_req(Request::http2(Method::Post, &rrb), ReqUri::Data).await.unwrap();
+  assert_eq!(
+    read_req(&mut [0; 74], &mut server).await,
+    "POST /foo?bar=baz HTTP/1.1\r\nhost: localhost:9000\r\ncontent-length: 3\r\n\r\nabc"
+  );
+  server
+    .write_all(
+      b"HTTP/1.1 100 Continue\r\n\r\n\
+      HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n2\r\nhi\r\n0\r\n\r\n",
+    )
+    .await
+    .unwrap();
+  assert_eq!(http1.recv_res(&mut rrb).await.unwrap(), StatusCode::Ok);
+  assert_eq!(rrb.body.as_slice(), b"hi");
+  assert!(!http1.is_closing());
+
+  rrb.clear();
+  rrb.uri.reset(format_args!("http://localhost:9000/foo?bar=baz")).unwrap();
+  http1.send_req(Request::http2(Method::Get, &rrb), ReqUri::Data).await.unwrap();
+  assert_eq!(
+    read_req(&mut [0; 51], &mut server).await,
+    "GET /foo?bar=baz HTTP/1.1\r\nhost: localhost:9000\r\n\r\n"
+  );
+  server.write_all(b"HTTP/1.0 404 Not Found\r\n\r\nnothing").await.unwrap();
+  drop(server);
+  assert_eq!(http1.recv_res(&mut rrb).await.unwrap(), StatusCode::NotFound);
+  assert_eq!(rrb.body.as_slice(), b"nothing");
+  assert!(http1.is_closing());
+}
@@ -52,7 +52,7 @@ mod window;
 mod window_update_frame;
 
 use crate::{
-  http::{Method, Protocol, ReqResBuffer, Request},
+  http::{Method, Protocol, ReqResBuffer, Request, StatusCode},
   http2::misc::{
     frame_reader_rslt, manage_initial_stream_receiving, process_higher_operation_err, protocol_err,
     sorp_mut, write_array,
@@ -94,7 +94,7 @@ pub(crate) const MAX_FRAME_LEN_UPPER_BOUND: u32 = max_frame_len_upper_bound!();
 pub(crate) const MAX_RECV_STREAMS_NUM: u32 = max_recv_streams_num!();
 pub(crate) const READ_BUFFER_LEN: u32 = read_buffer_len!();
 
-const PREFACE: &[u8; 24] = b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n";
+pub(crate) const PREFACE: &[u8; 24] = b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n";
 
 /// [`Http2`] instance using the mutex from tokio.
 #[cfg(feature = "tokio")]
@@ -146,7 +146,7 @@ where
     let sf = hp.to_settings_frame();
     let sf_buffer = &mut [0; 45];
     let sf_bytes = sf.bytes(sf_buffer);
-    if hp.initial_window_len() == initial_window_len!() {
+    if hp.initial_window_len() <= initial_window_len!() {
       if HAS_PREFACE {
         write_array([PREFACE, sf_bytes], &hb.is_conn_open, stream_writer).await?;
       } else {
@@ -187,16 +187,44 @@ where
   /// Accepts an initial connection sending the local parameters to the remote peer.
   #[inline]
   pub async fn accept<SR>(
+    hb: HB,
+    hp: Http2Params,
+    parts: (SR, SW),
+  ) -> crate::Result<(impl Future<Output = ()>, Self)>
+  where
+    SR: StreamReader,
+  {
+    let (frame_reader, this, _) = Self::_accept(hb, hp, &[], None, parts).await?;
+    Ok((frame_reader, this))
+  }
+
+  /// Accepts a connection whose first bytes, `initial`, were already read by another component.
+  ///
+  /// If `upgrade` is present, then the connection was upgraded from HTTP/1.1 and the provided
+  /// request, along with the decoded `HTTP2-Settings` payload, is assigned to the stream `1`.
+  #[inline]
+  pub(crate) async fn _accept<SR>(
     mut hb: HB,
     hp: Http2Params,
+    initial: &[u8],
+    upgrade: Option<(Method, ReqResBuffer, &[u8])>,
     (mut stream_reader, mut stream_writer): (SR, SW),
-  ) -> crate::Result<(impl Future<Output = ()>, Self)>
+  ) -> crate::Result<(impl Future<Output = ()>, Self, Option<ServerStream<HD>>)>
   where
     SR: StreamReader,
   {
     hb.lease_mut().clear();
     let mut buffer = [0; 24];
-    let _ = stream_reader.read(&mut buffer).await?;
+    let (initial_preface, rest) = initial.split_at_checked(24).unwrap_or((initial, &[]));
+    let mut read = initial_preface.len();
+    buffer.get_mut(..read).unwrap_or_default().copy_from_slice(initial_preface);
+    while read < buffer.len() {
+      let local_read = stream_reader.read(buffer.get_mut(read..).unwrap_or_default()).await?;
+      if local_read == 0 {
+        break;
+      }
+      read = read.wrapping_add(local_read);
+    }
     if &buffer != PREFACE {
       let _rslt = stream_writer
         .write_all(
@@ -205,10 +233,57 @@ where
         .await;
       return Err(protocol_err(Http2Error::NoPreface));
     }
-    let (is_conn_open, max_frame_len, pfb, read_frame_waker) =
+    let (is_conn_open, max_frame_len, mut pfb, read_frame_waker) =
       Self::manage_initial_params::<false>(hb.lease_mut(), &hp, &mut stream_writer).await?;
-    let hd = HD::new(HD::Item::new(Http2Data::new(hb, hp, stream_writer)));
+    if !rest.is_empty() {
+      pfb._set_following(rest)?;
+    }
+    let mut hd_item = Http2Data::new(hb, hp, stream_writer);
+    let upgraded_stream_params = if let Some((method, rrb, settings)) = upgrade {
+      let hdpm = hd_item.parts_mut();
+      let fi = frame_init::FrameInit::new(
+        common_flags::CommonFlags::empty(),
+        settings.len().try_into().unwrap_or(u32::MAX),
+        u31::U31::ZERO,
+        frame_init::FrameInitTy::Settings,
+      );
+      let sf = settings_frame::SettingsFrame::read(settings, fi)?;
+      hdpm.hps.update(&mut hdpm.hb.hpack_enc, &mut hdpm.hb.scrp, &sf, &mut hdpm.hb.sorp)?;
+      *hdpm.last_stream_id = u31::U31::ONE;
+      *hdpm.recv_streams_num = 1;
+      drop(hdpm.hb.sorp.insert(
+        u31::U31::ONE,
+        stream_receiver::StreamOverallRecvParams {
+          body_len: Usize::from(rrb.body.len()).into_u64(),
+          content_length: None,
+          has_initial_header: true,
+          has_one_or_more_data_frames: false,
+          is_body_streamed: false,
+          is_stream_open: true,
+          is_window_manual: false,
+          rrb,
+          status_code: StatusCode::Ok,
+          stream_state: stream_state::StreamState::HalfClosedRemote,
+          waker: NOOP_WAKER.clone(),
+          windows: Windows::initial(hdpm.hp, hdpm.hps),
+        },
+      ));
+      Some(method)
+    } else {
+      None
+    };
+    let hd = HD::new(HD::Item::new(hd_item));
     let this = Self { hd: hd.clone(), is_conn_open: Arc::clone(&is_conn_open), ish_id: 0 };
+    let upgraded_stream = upgraded_stream_params.map(|method| {
+      ServerStream::new(
+        hd.clone(),
+        Arc::clone(&is_conn_open),
+        method,
+        None,
+        _trace_span!("New upgraded server stream", stream_id = %u31::U31::ONE),
+        u31::U31::ONE,
+      )
+    });
     Ok((
       frame_reader::frame_reader(
         hd,
@@ -219,9 +294,25 @@ where
         stream_reader,
       ),
       this,
+      upgraded_stream,
     ))
   }
 
+  /// Sends a GOAWAY frame with the identifier of the last processed stream but, unlike
+  /// [`Self::send_go_away`], keeps the connection open so that ongoing streams can finish.
+  ///
+  /// Streams with identifiers greater than the returned value should be refused and the
+  /// connection should be closed afterwards.
+  #[inline]
+  pub async fn send_graceful_go_away(&self) -> u32 {
+    let mut guard = self.hd.lock().await;
+    let hdpm = guard.parts_mut();
+    let last_stream_id = *hdpm.last_stream_id;
+    let gaf = go_away_frame::GoAwayFrame::new(Http2ErrorCode::NoError, last_stream_id);
+    let _rslt = hdpm.stream_writer.write_all(&gaf.bytes()).await;
+    last_stream_id.u32()
+  }
+
   /// Awaits for an initial header to create a stream.
   ///
   /// Returns [`Either::Left`] if the network connection has been closed, either locally
@@ -259,6 +350,14 @@ where
         ));
         Poll::Pending
       } else {
+        let Some(ish) = hdpm.hb.initial_server_headers.get_mut(&curr_ish_id) else {
+          return Poll::Ready(Err(protocol_err(Http2Error::UnknownInitialServerHeaderId)));
+        };
+        // Woken by something other than the frame reader, e.g., a future polled concurrently.
+        if ish.stream_id.is_zero() && is_conn_open.load(Ordering::Relaxed) {
+          ish.waker.clone_from(cx.waker());
+          return Poll::Pending;
+        }
         let Some(ish) = hdpm.hb.initial_server_headers.remove(&curr_ish_id) else {
           return Poll::Ready(Err(protocol_err(Http2Error::UnknownInitialServerHeaderId)));
         };
@@ -99,7 +99,9 @@ where
             content_length: None,
             has_initial_header: false,
             has_one_or_more_data_frames: false,
+            is_body_streamed: false,
             is_stream_open: true,
+            is_window_manual: false,
             rrb: elem,
             status_code: StatusCode::Ok,
             stream_state: StreamState::HalfClosedLocal,
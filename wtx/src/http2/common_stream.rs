@@ -58,6 +58,22 @@ where
     Ok(())
   }
 
+  /// Low level operation that makes the body of this stream unbounded by
+  /// [`crate::http2::Http2Params::max_body_len`]. Incoming DATA frames are then limited by the
+  /// stream window, which is only replenished after [`Self::recv_data`] retrieves buffered data.
+  ///
+  /// In other words, peers can't send more data than the initial window length until the
+  /// application consumes what was previously received.
+  #[inline]
+  pub async fn enable_body_streaming(&mut self) -> crate::Result<()> {
+    let mut lock = self.hd.lock().await;
+    let hdpm = lock.parts_mut();
+    let elem = sorp_mut(&mut hdpm.hb.sorp, self.stream_id)?;
+    elem.is_body_streamed = true;
+    elem.is_window_manual = true;
+    Ok(())
+  }
+
   /// Receive Data
   ///
   /// Low level operation that retrieves a DATA frame sent by the remote peer. Shouldn't interact
@@ -68,28 +84,23 @@ where
   pub async fn recv_data(&mut self) -> crate::Result<Http2RecvStatus<Vector<u8>, Vector<u8>>> {
     let _e = self.span._enter();
     _trace!("Fetching data");
-    let mut pin = pin!(self.hd.lock());
-    poll_fn(|cx| {
-      let mut lock = lock_pin!(cx, self.hd, pin);
+    {
+      let mut lock = self.hd.lock().await;
       let hdpm = lock.parts_mut();
-      let sorp = sorp_mut(&mut hdpm.hb.sorp, self.stream_id)?;
-      if let Some(elem) = status_recv(self.is_conn_open, sorp, |local_sorp| {
-        check_content_length(local_sorp)?;
-        Ok(mem::take(&mut local_sorp.rrb.body))
-      })? {
-        return Poll::Ready(Ok(elem));
+      let elem = sorp_mut(&mut hdpm.hb.sorp, self.stream_id)?;
+      if elem.is_window_manual && !elem.stream_state.recv_eos() {
+        WindowsPair::new(hdpm.windows, &mut elem.windows)
+          .replenish_stream_recv(
+            elem.rrb.body.len(),
+            hdpm.hp,
+            self.is_conn_open,
+            hdpm.stream_writer,
+            self.stream_id,
+          )
+          .await?;
       }
-      if sorp.has_one_or_more_data_frames && !sorp.rrb.body.is_empty() {
-        frame_reader_rslt(hdpm.frame_reader_error)?;
-        let rslt = sorp.rrb.body.clone();
-        sorp.rrb.body.clear();
-        Poll::Ready(Ok(Http2RecvStatus::Ongoing(rslt)))
-      } else {
-        sorp.waker.clone_from(cx.waker());
-        Poll::Pending
-      }
-    })
-    .await
+    }
+    self.do_recv_data().await
   }
 
   /// Receive Trailers
@@ -130,6 +141,7 @@ where
     wp.withdrawn_recv(
       hdpm.hp,
       self.is_conn_open,
+      elem.is_window_manual,
       hdpm.stream_writer,
       self.stream_id,
       U31::from_u32(value),
@@ -186,7 +198,7 @@ where
         &mut data,
         is_eos,
         &mut has_data,
-        false,
+        !is_eos,
         self.is_conn_open,
         hdpm.hps.max_frame_len,
         hdpm.stream_writer,
@@ -303,4 +315,30 @@ where
     let elem = sorp_mut(&mut hdpm.hb.sorp, self.stream_id)?;
     Ok(elem.windows)
   }
+
+  #[inline]
+  async fn do_recv_data(&self) -> crate::Result<Http2RecvStatus<Vector<u8>, Vector<u8>>> {
+    let mut pin = pin!(self.hd.lock());
+    poll_fn(|cx| {
+      let mut lock = lock_pin!(cx, self.hd, pin);
+      let hdpm = lock.parts_mut();
+      let sorp = sorp_mut(&mut hdpm.hb.sorp, self.stream_id)?;
+      if let Some(elem) = status_recv(self.is_conn_open, sorp, |local_sorp| {
+        check_content_length(local_sorp)?;
+        Ok(mem::take(&mut local_sorp.rrb.body))
+      })? {
+        return Poll::Ready(Ok(elem));
+      }
+      if sorp.has_one_or_more_data_frames && !sorp.rrb.body.is_empty() {
+        frame_reader_rslt(hdpm.frame_reader_error)?;
+        let rslt = sorp.rrb.body.clone();
+        sorp.rrb.body.clear();
+        Poll::Ready(Ok(Http2RecvStatus::Ongoing(rslt)))
+      } else {
+        sorp.waker.clone_from(cx.waker());
+        Poll::Pending
+      }
+    })
+    .await
+  }
 }
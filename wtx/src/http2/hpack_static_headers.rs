@@ -20,8 +20,8 @@ impl HpackStaticRequestHeaders<'_> {
   pub(crate) fn iter(&self) -> impl Iterator<Item = (HpackHeaderBasic, &[u8])> {
     let Self { authority, method, path, protocol, scheme } = *self;
     let enums = [
-      method.map(|el| (HpackHeaderBasic::Method(el), &[][..])),
-      protocol.map(|el| (HpackHeaderBasic::Protocol(el), &[][..])),
+      method.map(|el| (HpackHeaderBasic::Method(el), el.strings().custom[0].as_bytes())),
+      protocol.map(|el| (HpackHeaderBasic::Protocol(el), el.strings().custom[0].as_bytes())),
     ]
     .into_iter()
     .flatten();
@@ -47,6 +47,50 @@ impl HpackStaticResponseHeaders {
 
   pub(crate) fn iter(&self) -> impl Iterator<Item = (HpackHeaderBasic, &[u8])> {
     let Self { status_code } = *self;
-    status_code.map(|el| (HpackHeaderBasic::StatusCode(el), &[][..])).into_iter()
+    status_code
+      .map(|el| (HpackHeaderBasic::StatusCode(el), el.strings().number.as_bytes()))
+      .into_iter()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    http::{Method, StatusCode},
+    http2::{
+      hpack_decoder::HpackDecoder,
+      hpack_encoder::HpackEncoder,
+      hpack_header::HpackHeaderBasic,
+      hpack_static_headers::{HpackStaticRequestHeaders, HpackStaticResponseHeaders},
+    },
+    misc::{simple_seed, Vector, Xorshift64},
+  };
+
+  #[test]
+  fn encodes_and_decodes_values_without_static_entries() {
+    let mut buffer = Vector::new();
+    let mut decoder = HpackDecoder::new();
+    let mut encoder = HpackEncoder::new(Xorshift64::from(simple_seed()));
+    let req =
+      HpackStaticRequestHeaders { method: Some(Method::Patch), ..HpackStaticRequestHeaders::EMPTY };
+    let res = HpackStaticResponseHeaders { status_code: Some(StatusCode::MethodNotAllowed) };
+    encoder.encode(&mut buffer, req.iter().chain(res.iter()), []).unwrap();
+    let mut decoded = Vector::new();
+    decoder
+      .decode(&buffer, |(hhb, _, value)| {
+        decoded.push((hhb, Vector::from_slice(value)?))?;
+        Ok(())
+      })
+      .unwrap();
+    assert_eq!(
+      decoded.as_slice(),
+      &[
+        (HpackHeaderBasic::Method(Method::Patch), Vector::from_slice(b"PATCH").unwrap()),
+        (
+          HpackHeaderBasic::StatusCode(StatusCode::MethodNotAllowed),
+          Vector::from_slice(b"405").unwrap()
+        ),
+      ]
+    );
   }
 }
@@ -26,6 +26,8 @@ pub enum Http2Error {
   ExceedAmountOfOpenedStreams,
   /// The number of active concurrent streams extrapolated the threshold
   ExceedAmountOfActiveConcurrentStreams,
+  /// Remote peer sent more data than the stream window allows
+  ExceededStreamRecvWindow,
   /// Frame has a zero stream ID but shouldn't because of its type.
   FrameIdIsZeroButShouldNot,
   /// The system only supports 2 header frames when sending data
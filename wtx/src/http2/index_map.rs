@@ -40,6 +40,15 @@ where
     Some(value)
   }
 
+  #[inline]
+  pub(crate) fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+  where
+    K: Borrow<Q>,
+    Q: Eq + Hash + ?Sized,
+  {
+    self.elements.get_mut(key)
+  }
+
   #[inline]
   pub(crate) fn increase_cursor(&mut self) {
     self.cursor = self.cursor.saturating_add(1);
@@ -32,7 +32,7 @@ pub(crate) fn check_content_length(sorp: &StreamOverallRecvParams) -> crate::Res
   let Some(content_length) = sorp.content_length else {
     return Ok(());
   };
-  if sorp.rrb.body.len() != content_length {
+  if sorp.body_len != Usize::from(content_length).into_u64() {
     return Err(protocol_err(Http2Error::InvalidContentLength));
   }
   Ok(())
@@ -65,13 +65,13 @@ where
         Some(Http2Error::InvalidReceivedFrameAfterEos),
       ));
     }
-    let local_body_len_opt = elem.body_len.checked_add(self.fi.data_len);
-    let Some(local_body_len) = local_body_len_opt.filter(|el| *el <= self.hp.max_body_len()) else {
+    let local_body_len = elem.body_len.saturating_add(self.fi.data_len.into());
+    if !elem.is_body_streamed && local_body_len > self.hp.max_body_len().into() {
       return Err(protocol_err(Http2Error::LargeBodyLen(
-        local_body_len_opt,
+        u32::try_from(local_body_len).ok(),
         self.hp.max_body_len(),
       )));
-    };
+    }
     elem.body_len = local_body_len;
     let (df, body_bytes) = DataFrame::read(self.pfb._current(), self.fi)?;
     elem.rrb.body.extend_from_copyable_slice(body_bytes)?;
@@ -80,6 +80,7 @@ where
       .withdrawn_recv(
         self.hp,
         self.is_conn_open,
+        elem.is_window_manual,
         self.stream_writer,
         self.fi.stream_id,
         df.data_len(),
@@ -174,7 +175,9 @@ where
         content_length,
         has_initial_header: true,
         has_one_or_more_data_frames: false,
+        is_body_streamed: false,
         is_stream_open: true,
+        is_window_manual: true,
         rrb: mem::take(&mut ish.rrb),
         status_code: StatusCode::Ok,
         stream_state,
@@ -184,8 +184,7 @@ where
     if let Some(available_send_rest @ 1..=u32::MAX) = available_send.checked_sub(max_frame_len) {
       let tuple = split_frame_bytes(data.first_mut(), max_frame_len);
       let (left0 @ [_, ..], right0) = tuple else {
-        *has_data = true;
-        return Ok(false);
+        return write_empty_data(force_eos, has_data, is_conn_open, stream, stream_id).await;
       };
       let frame0_len = data_frame_len(left0.len());
       let mut frame0 = DataFrame::new(frame0_len.into(), stream_id);
@@ -219,8 +218,7 @@ where
     } else {
       let tuple = split_frame_bytes(data.first_mut(), available_send);
       let (left0 @ [_, ..], right0) = tuple else {
-        *has_data = true;
-        return Ok(false);
+        return write_empty_data(force_eos, has_data, is_conn_open, stream, stream_id).await;
       };
       let frame0_len = data_frame_len(left0.len());
       let mut frame0 = DataFrame::new(frame0_len.into(), stream_id);
@@ -247,6 +245,28 @@ where
   }
 }
 
+// Empty payloads are only sent when they carry the end-of-stream flag
+#[inline]
+async fn write_empty_data<SW>(
+  force_eos: bool,
+  has_data: &mut bool,
+  is_conn_open: &AtomicBool,
+  stream: &mut SW,
+  stream_id: U31,
+) -> crate::Result<bool>
+where
+  SW: StreamWriter,
+{
+  *has_data = true;
+  if !force_eos {
+    return Ok(false);
+  }
+  let mut frame = DataFrame::new(U31::ZERO, stream_id);
+  frame.set_eos();
+  write_array([&init!(0u32, frame)], is_conn_open, stream).await?;
+  Ok(true)
+}
+
 // Tries to send all initial headers
 #[inline]
 pub(crate) async fn write_standalone_headers<SW, const IS_CLIENT: bool>(
@@ -379,6 +399,8 @@ where
         change_initial_stream_state::<IS_CLIENT>(&mut elem.stream_state);
         *has_headers = true;
       }
+      // Awakened by incoming WINDOW_UPDATE frames
+      elem.waker.clone_from(waker);
       return Ok(Some(false));
     };
 
@@ -404,9 +426,10 @@ where
     }
 
     if !*has_data {
-      if write_standalone_data(
+      let mut sdm = SendDataMode::scattered_data_frames(data_bytes);
+      let is_finished = write_standalone_data(
         available_send,
-        &mut SendDataMode::scattered_data_frames(data_bytes),
+        &mut sdm,
         false,
         has_data,
         headers.trailers().has_any(),
@@ -416,8 +439,9 @@ where
         stream_id,
         &mut wp,
       )
-      .await?
-      {
+      .await?;
+      *data_bytes = *sdm.first_mut();
+      if is_finished {
         break 'msg;
       }
       // There can be an available window size
@@ -6,6 +6,7 @@ use crate::{
     send_msg::send_msg,
     stream_receiver::StreamControlRecvParams,
     u31::U31,
+    window::WindowsPair,
     CommonStream, Http2Buffer, Http2Data, Http2RecvStatus, Http2SendStatus,
   },
   misc::{
@@ -86,6 +87,10 @@ where
     let Self { hd, is_conn_open, method: _, protocol: _, span, stream_id } = self;
     let _e = span._enter();
     _trace!("Receiving request");
+    if let Err(err) = Self::buffer_body(hd, is_conn_open, *stream_id).await {
+      process_higher_operation_err(&err, hd).await;
+      return Err(err);
+    }
     let mut lock_pin = pin!(hd.lock());
     let rslt = poll_fn(|cx| {
       let mut lock = lock_pin!(cx, hd, lock_pin);
@@ -114,6 +119,24 @@ where
     rslt
   }
 
+  // Server streams initially hold their windows to avoid buffering data that the application
+  // might want to stream.
+  #[inline]
+  async fn buffer_body(hd: &HD, is_conn_open: &AtomicBool, stream_id: U31) -> crate::Result<()> {
+    let mut lock = hd.lock().await;
+    let hdpm = lock.parts_mut();
+    let Some(elem) = hdpm.hb.sorp.get_mut(&stream_id) else {
+      return Ok(());
+    };
+    elem.is_window_manual = false;
+    if elem.windows.recv().available() > 0 {
+      return Ok(());
+    }
+    WindowsPair::new(hdpm.windows, &mut elem.windows)
+      .replenish_stream_recv(0, hdpm.hp, is_conn_open, hdpm.stream_writer, stream_id)
+      .await
+  }
+
   /// Send Response
   ///
   /// Higher operation that sends all data related to a response and then closes the stream.
@@ -23,10 +23,12 @@ pub(crate) struct StreamControlRecvParams {
 #[derive(Debug)]
 pub(crate) struct StreamOverallRecvParams {
   pub(crate) content_length: Option<usize>,
-  pub(crate) body_len: u32,
+  pub(crate) body_len: u64,
   pub(crate) has_initial_header: bool,
   pub(crate) has_one_or_more_data_frames: bool,
+  pub(crate) is_body_streamed: bool,
   pub(crate) is_stream_open: bool,
+  pub(crate) is_window_manual: bool,
   pub(crate) rrb: ReqResBuffer,
   pub(crate) status_code: StatusCode,
   pub(crate) stream_state: StreamState,
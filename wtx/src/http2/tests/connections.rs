@@ -1,10 +1,10 @@
 use crate::{
   http::{Header, Headers, Method, ReqResBuffer, ReqResData, Request, StatusCode},
-  http2::{Http2Buffer, Http2ErrorCode, Http2Params, Http2Tokio},
+  http2::{Http2Buffer, Http2ErrorCode, Http2Params, Http2RecvStatus, Http2Tokio, SendDataMode},
   misc::{simple_seed, Either, UriRef, UriString, Xorshift64},
   tests::_uri,
 };
-use core::time::Duration;
+use core::{future::Future, pin::pin, task::Poll, time::Duration};
 use tokio::net::{tcp::OwnedWriteHalf, TcpListener, TcpStream};
 
 #[tokio::test]
@@ -16,6 +16,137 @@ async fn connections() {
   client(&uri).await;
 }
 
+// Data frames sent without `is_eos` must keep the stream open and an empty payload with `is_eos`
+// must still close it.
+#[tokio::test]
+async fn send_data_respects_eos() {
+  let uri = _uri();
+  let listener = TcpListener::bind(uri.hostname_with_implied_port()).await.unwrap();
+  let _server_jh = tokio::spawn(async move {
+    let (stream, _) = listener.accept().await.unwrap();
+    let (frame_reader, mut http2) = Http2Tokio::accept(
+      Http2Buffer::new(Xorshift64::from(simple_seed())),
+      Http2Params::default(),
+      stream.into_split(),
+    )
+    .await
+    .unwrap();
+    let _jh = tokio::spawn(frame_reader);
+    let Either::Right((mut stream, _)) =
+      http2.stream(ReqResBuffer::empty(), |_, _| {}).await.unwrap()
+    else {
+      panic!();
+    };
+    let mut common = stream.common();
+    let _ = common.send_headers(&Headers::new(), false, StatusCode::Ok).await.unwrap();
+    let _ = common.send_data(SendDataMode::scattered_data_frames(b"ab"), false).await.unwrap();
+    let _ = common.send_data(SendDataMode::scattered_data_frames(b"cd"), false).await.unwrap();
+    let _ = common.send_data(SendDataMode::scattered_data_frames(b""), true).await.unwrap();
+  });
+  tokio::time::sleep(Duration::from_millis(100)).await;
+  let (frame_reader, mut http2) = Http2Tokio::connect(
+    Http2Buffer::new(Xorshift64::from(simple_seed())),
+    Http2Params::default(),
+    TcpStream::connect(uri.hostname_with_implied_port()).await.unwrap().into_split(),
+  )
+  .await
+  .unwrap();
+  let _jh = tokio::spawn(frame_reader);
+  let rrb = ReqResBuffer::empty();
+  let mut stream = http2.stream().await.unwrap();
+  let _ = stream.send_req(rrb.as_http2_request(Method::Get), &uri.to_ref()).await.unwrap();
+  let recv_fut = stream.recv_res(rrb);
+  let (hrs, res_rrb) =
+    tokio::time::timeout(Duration::from_secs(5), recv_fut).await.unwrap().unwrap();
+  assert!(matches!(hrs, Http2RecvStatus::Eos(StatusCode::Ok)));
+  assert_eq!(res_rrb.body.as_slice(), b"abcd");
+}
+
+// Local windows smaller than the default length must not announce a connection `WINDOW_UPDATE`
+// and bodies larger than the window must still be transferred.
+#[tokio::test]
+async fn smaller_initial_windows() {
+  let uri = _uri();
+  let body = [7; 40_000];
+  let listener = TcpListener::bind(uri.hostname_with_implied_port()).await.unwrap();
+  let _server_jh = tokio::spawn(async move {
+    let (stream, _) = listener.accept().await.unwrap();
+    let (frame_reader, mut http2) = Http2Tokio::accept(
+      Http2Buffer::new(Xorshift64::from(simple_seed())),
+      Http2Params::default().set_initial_window_len(16 * 1024),
+      stream.into_split(),
+    )
+    .await
+    .unwrap();
+    let _jh = tokio::spawn(frame_reader);
+    let _rrb = stream_server(&mut http2, ReqResBuffer::empty(), |req| {
+      assert_eq!(req.rrd.body().len(), 40_000);
+    })
+    .await;
+  });
+  tokio::time::sleep(Duration::from_millis(100)).await;
+  let (frame_reader, mut http2) = Http2Tokio::connect(
+    Http2Buffer::new(Xorshift64::from(simple_seed())),
+    Http2Params::default().set_initial_window_len(16 * 1024),
+    TcpStream::connect(uri.hostname_with_implied_port()).await.unwrap().into_split(),
+  )
+  .await
+  .unwrap();
+  let _jh = tokio::spawn(frame_reader);
+  let mut rrb = ReqResBuffer::empty();
+  rrb.body.extend_from_copyable_slice(&body).unwrap();
+  let uri_ref = uri.to_ref();
+  let client_fut = stream_client(&mut http2, rrb, &uri_ref);
+  let res_rrb = tokio::time::timeout(Duration::from_secs(5), client_fut).await.unwrap();
+  assert_eq!(res_rrb.body.as_slice(), body.as_slice());
+}
+
+// Futures that concurrently poll `Http2::stream`, for example, a `select`, can wake it before
+// the frame reader assigns a stream.
+#[tokio::test]
+async fn stream_ignores_spurious_wakes() {
+  let uri = _uri();
+  let listener = TcpListener::bind(uri.hostname_with_implied_port()).await.unwrap();
+  let server_jh = tokio::spawn(async move {
+    let (stream, _) = listener.accept().await.unwrap();
+    let (frame_reader, mut http2) = Http2Tokio::accept(
+      Http2Buffer::new(Xorshift64::from(simple_seed())),
+      Http2Params::default(),
+      stream.into_split(),
+    )
+    .await
+    .unwrap();
+    let _jh = tokio::spawn(frame_reader);
+    let mut stream_fut = pin!(http2.stream(ReqResBuffer::empty(), |_, _| {}));
+    for _ in 0..3 {
+      core::future::poll_fn(|cx| {
+        assert!(stream_fut.as_mut().poll(cx).is_pending());
+        Poll::Ready(())
+      })
+      .await;
+    }
+    let Either::Right((mut stream, _)) = stream_fut.await.unwrap() else {
+      panic!();
+    };
+    let (_, req_rrb) = stream.recv_req().await.unwrap();
+    let _ = stream.send_res(req_rrb.as_http2_response(StatusCode::Ok)).await.unwrap();
+  });
+  tokio::time::sleep(Duration::from_millis(100)).await;
+  let (frame_reader, mut http2) = Http2Tokio::connect(
+    Http2Buffer::new(Xorshift64::from(simple_seed())),
+    Http2Params::default(),
+    TcpStream::connect(uri.hostname_with_implied_port()).await.unwrap().into_split(),
+  )
+  .await
+  .unwrap();
+  let _jh = tokio::spawn(frame_reader);
+  let uri_ref = uri.to_ref();
+  let client_fut = stream_client(&mut http2, ReqResBuffer::empty(), &uri_ref);
+  let rrb = tokio::time::timeout(Duration::from_secs(5), client_fut).await.unwrap();
+  _0(rrb.body(), rrb.headers());
+  server_jh.await.unwrap();
+}
+
 async fn client(uri: &UriString) {
   let mut rrb = ReqResBuffer::empty();
   rrb.headers.reserve(6, 1).unwrap();
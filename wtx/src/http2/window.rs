@@ -140,15 +140,50 @@ impl<'any> WindowsPair<'any> {
     self.stream.send.available()
   }
 
+  /// Replenish - Receive
+  ///
+  /// Restores the stream window to its initial size minus the amount of data that wasn't
+  /// consumed yet. Used by streams with manual windows.
+  #[inline]
+  pub(crate) async fn replenish_stream_recv<SW>(
+    &mut self,
+    buffered_len: usize,
+    hp: &Http2Params,
+    is_conn_open: &AtomicBool,
+    stream_writer: &mut SW,
+    stream_id: U31,
+  ) -> crate::Result<()>
+  where
+    SW: StreamWriter,
+  {
+    let iwl = U31::from_u32(hp.initial_window_len()).i32();
+    let buffered = i32::try_from(buffered_len).unwrap_or(i32::MAX);
+    let stream_value = iwl.wrapping_sub(self.stream.recv.available()).saturating_sub(buffered);
+    if stream_value <= 0 {
+      return Ok(());
+    }
+    self.stream.recv.deposit(Some(stream_id), stream_value)?;
+    write_array(
+      [&WindowUpdateFrame::new(U31::from_i32(stream_value), stream_id)?.bytes()],
+      is_conn_open,
+      stream_writer,
+    )
+    .await
+  }
+
   /// Withdrawn - Receive
   ///
   /// Controls window sizes received from external sources. Invalid or negative values trigger a
   /// frame dispatch to return to the default window size.
+  ///
+  /// If `is_stream_manual` is true, then the stream window is never replenished here and peers
+  /// that send more data than allowed receive a flow control error.
   #[inline]
   pub(crate) async fn withdrawn_recv<SW>(
     &mut self,
     hp: &Http2Params,
     is_conn_open: &AtomicBool,
+    is_stream_manual: bool,
     stream_writer: &mut SW,
     stream_id: U31,
     value: U31,
@@ -159,7 +194,21 @@ impl<'any> WindowsPair<'any> {
     let iwl = U31::from_u32(hp.initial_window_len()).i32();
     self.conn.recv.withdrawn(None, value.i32())?;
     self.stream.recv.withdrawn(Some(stream_id), value.i32())?;
-    match (self.conn.recv.is_invalid(), self.stream.recv.is_invalid()) {
+    let is_stream_invalid = if is_stream_manual {
+      // Peers are allowed to use the default window length until local settings are received.
+      let tolerance = i32::wrapping_sub(initial_window_len!(), iwl).max(0);
+      if self.stream.recv.available().wrapping_add(tolerance) < 0 {
+        return Err(crate::Error::Http2ErrorReset(
+          Http2ErrorCode::FlowControlError,
+          Some(Http2Error::ExceededStreamRecvWindow),
+          stream_id.u32(),
+        ));
+      }
+      false
+    } else {
+      self.stream.recv.is_invalid()
+    };
+    match (self.conn.recv.is_invalid(), is_stream_invalid) {
       (false, false) => {}
       (false, true) => {
         let stream_value = self.stream.recv.available().abs().wrapping_add(iwl);
@@ -211,3 +260,106 @@ impl<'any> WindowsPair<'any> {
     Ok(())
   }
 }
+
+#[cfg(all(feature = "_async-tests", test))]
+mod tests {
+  use crate::{
+    http2::{
+      http2_params_send::Http2ParamsSend, u31::U31, window::WindowsPair, Http2Error,
+      Http2ErrorCode, Http2Params, Windows,
+    },
+    misc::{BytesStream, StreamReader},
+  };
+  use alloc::vec::Vec;
+  use core::sync::atomic::AtomicBool;
+
+  const STREAM_ID: U31 = U31::from_u32(1);
+
+  #[tokio::test]
+  async fn automatic_windows_are_replenished() {
+    let hp = Http2Params::default();
+    let (mut conn, mut stream) = windows(&hp);
+    let mut sw = BytesStream::default();
+    let mut pair = WindowsPair::new(&mut conn, &mut stream);
+    pair
+      .withdrawn_recv(&hp, &AtomicBool::new(true), false, &mut sw, STREAM_ID, 1000.into())
+      .await
+      .unwrap();
+    assert_eq!((conn.recv().available(), stream.recv().available()), (64535, 64535));
+    assert_eq!(increments(&mut sw).await, []);
+    let mut pair = WindowsPair::new(&mut conn, &mut stream);
+    pair
+      .withdrawn_recv(&hp, &AtomicBool::new(true), false, &mut sw, STREAM_ID, 64535.into())
+      .await
+      .unwrap();
+    assert_eq!((conn.recv().available(), stream.recv().available()), (65535, 65535));
+    assert_eq!(increments(&mut sw).await, [(0, 65535), (1, 65535)]);
+  }
+
+  #[tokio::test]
+  async fn manual_windows_are_only_replenished_on_demand() {
+    let hp = Http2Params::default();
+    let is_conn_open = AtomicBool::new(true);
+    let (mut conn, mut stream) = windows(&hp);
+    let mut sw = BytesStream::default();
+    let mut pair = WindowsPair::new(&mut conn, &mut stream);
+    pair.withdrawn_recv(&hp, &is_conn_open, true, &mut sw, STREAM_ID, 65535.into()).await.unwrap();
+    assert_eq!((pair.conn.recv().available(), pair.stream.recv().available()), (65535, 0));
+    assert_eq!(increments(&mut sw).await, [(0, 65535)]);
+    pair.replenish_stream_recv(15535, &hp, &is_conn_open, &mut sw, STREAM_ID).await.unwrap();
+    assert_eq!(pair.stream.recv().available(), 50000);
+    assert_eq!(increments(&mut sw).await, [(1, 50000)]);
+    pair.replenish_stream_recv(20000, &hp, &is_conn_open, &mut sw, STREAM_ID).await.unwrap();
+    assert_eq!(pair.stream.recv().available(), 50000);
+    assert_eq!(increments(&mut sw).await, []);
+  }
+
+  #[tokio::test]
+  async fn manual_windows_reject_excessive_data() {
+    let hp = Http2Params::default();
+    let is_conn_open = AtomicBool::new(true);
+    let (mut conn, mut stream) = windows(&hp);
+    let mut sw = BytesStream::default();
+    let mut pair = WindowsPair::new(&mut conn, &mut stream);
+    pair.withdrawn_recv(&hp, &is_conn_open, true, &mut sw, STREAM_ID, 65535.into()).await.unwrap();
+    assert!(matches!(
+      pair.withdrawn_recv(&hp, &is_conn_open, true, &mut sw, STREAM_ID, 1.into()).await,
+      Err(crate::Error::Http2ErrorReset(
+        Http2ErrorCode::FlowControlError,
+        Some(Http2Error::ExceededStreamRecvWindow),
+        1
+      ))
+    ));
+  }
+
+  #[tokio::test]
+  async fn manual_windows_tolerate_the_default_length() {
+    let hp = Http2Params::default().set_initial_window_len(16384);
+    let is_conn_open = AtomicBool::new(true);
+    let (mut conn, mut stream) = windows(&hp);
+    let mut sw = BytesStream::default();
+    let mut pair = WindowsPair::new(&mut conn, &mut stream);
+    pair.withdrawn_recv(&hp, &is_conn_open, true, &mut sw, STREAM_ID, 65535.into()).await.unwrap();
+    assert_eq!(pair.stream.recv().available(), -49151);
+    assert!(pair
+      .withdrawn_recv(&hp, &is_conn_open, true, &mut sw, STREAM_ID, 1.into())
+      .await
+      .is_err());
+  }
+
+  async fn increments(sw: &mut BytesStream) -> Vec<(u32, u32)> {
+    let mut rslt = Vec::new();
+    let mut frame = [0; 13];
+    while sw.read(&mut frame).await.unwrap() == 13 {
+      assert_eq!(frame[3], 8);
+      let [_, _, _, _, _, a, b, c, d, e, f, g, h] = frame;
+      rslt.push((u32::from_be_bytes([a, b, c, d]), u32::from_be_bytes([e, f, g, h])));
+    }
+    rslt
+  }
+
+  fn windows(hp: &Http2Params) -> (Windows, Windows) {
+    let hps = Http2ParamsSend::default();
+    (Windows::initial(hp, &hps), Windows::initial(hp, &hps))
+  }
+}
@@ -25,6 +25,8 @@ pub(crate) mod mem_transfer;
 mod noop_waker;
 mod optimization;
 pub(crate) mod partitioned_filled_buffer;
+#[cfg(feature = "argon2")]
+pub mod password;
 mod query_writer;
 mod ref_counter;
 mod rng;
@@ -49,7 +51,15 @@ pub use blocks_deque::{Block, BlocksDeque, BlocksDequeBuilder, BlocksDequeError}
 pub use buffer_mode::BufferMode;
 pub use bytes_fmt::BytesFmt;
 pub use connection_state::ConnectionState;
-use core::{any::type_name, fmt::Write as _, ops::Range, time::Duration};
+use core::{
+  any::type_name,
+  fmt::Write as _,
+  future::{poll_fn, Future},
+  ops::Range,
+  pin::pin,
+  task::Poll,
+  time::Duration,
+};
 pub use deque::{Deque, DequeueError};
 pub use either::Either;
 pub use enum_var_strings::EnumVarStrings;
@@ -239,6 +249,27 @@ pub(crate) fn char_slice(buffer: &mut [u8; 4], ch: char) -> &[u8] {
   }
 }
 
+/// Polls both futures until one of them finishes, giving priority to `left`.
+#[inline]
+pub(crate) async fn _select<L, R>(left: L, right: R) -> Either<L::Output, R::Output>
+where
+  L: Future,
+  R: Future,
+{
+  let mut left_pin = pin!(left);
+  let mut right_pin = pin!(right);
+  poll_fn(|cx| {
+    if let Poll::Ready(elem) = left_pin.as_mut().poll(cx) {
+      return Poll::Ready(Either::Left(elem));
+    }
+    if let Poll::Ready(elem) = right_pin.as_mut().poll(cx) {
+      return Poll::Ready(Either::Right(elem));
+    }
+    Poll::Pending
+  })
+  .await
+}
+
 #[inline]
 pub(crate) fn _conservative_size_hint_len(size_hint: (usize, Option<usize>)) -> Option<usize> {
   match size_hint {
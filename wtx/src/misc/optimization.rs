@@ -119,7 +119,7 @@ pub fn str_pos1(str: &str, elem: u8) -> Option<usize> {
 /// Internally uses `memchr` if the feature is active.
 #[inline]
 pub fn str_rsplit_once1(str: &str, elem: u8) -> Option<(&str, &str)> {
-  let idx = str_pos1(str, elem)?;
+  let idx = bytes_rpos1(str.as_bytes(), elem)?;
   Some((str.get(..idx)?, str.get(idx.wrapping_add(1)..)?))
 }
 
@@ -145,3 +145,22 @@ pub fn str_split_once1(str: &str, elem: u8) -> Option<(&str, &str)> {
   let idx = str_pos1(str, elem)?;
   Some((str.get(..idx)?, str.get(idx.wrapping_add(1)..)?))
 }
+
+#[cfg(test)]
+mod tests {
+  use crate::misc::{bytes_rpos1, str_rsplit_once1, str_split_once1};
+
+  #[test]
+  fn bytes_rpos1_returns_the_last_occurrence() {
+    assert_eq!(bytes_rpos1(b"a.b.c", b'.'), Some(3));
+    assert_eq!(bytes_rpos1(b"abc", b'.'), None);
+  }
+
+  #[test]
+  fn str_rsplit_once1_splits_at_the_last_occurrence() {
+    assert_eq!(str_rsplit_once1("a.b.c", b'.'), Some(("a.b", "c")));
+    assert_eq!(str_split_once1("a.b.c", b'.'), Some(("a", "b.c")));
+    assert_eq!(str_rsplit_once1("abc.", b'.'), Some(("abc", "")));
+    assert_eq!(str_rsplit_once1("abc", b'.'), None);
+  }
+}
@@ -145,6 +145,13 @@ impl PartitionedFilledBuffer {
     Ok(())
   }
 
+  #[inline]
+  pub(crate) fn _set_following(&mut self, bytes: &[u8]) -> crate::Result<()> {
+    self._buffer._extend_from_slice(bytes)?;
+    self._set_indices(0, 0, bytes.len())?;
+    Ok(())
+  }
+
   #[inline]
   pub(crate) fn _set_indices(
     &mut self,
@@ -0,0 +1,111 @@
+//! Password hashing with `Argon2id` and the default parameters of the `argon2` crate, which
+//! follow the recommendations of OWASP (19 MiB of memory, 2 iterations and 1 degree of
+//! parallelism).
+//!
+//! Hashes are encoded as PHC strings, in other words, the algorithm, the parameters and the salt
+//! are stored alongside the hash.
+//!
+//! All functions are CPU-intensive by design and shouldn't be called in places that can't block
+//! for a few milliseconds.
+
+use crate::misc::Rng;
+use alloc::string::{String, ToString};
+use argon2::{
+  password_hash::{self, SaltString},
+  Algorithm, Argon2, Params, PasswordHash, PasswordHasher, PasswordVerifier, Version,
+};
+
+/// Hashes `pwd` with a random salt generated by `rng`.
+#[inline]
+pub fn hash<RNG>(pwd: &[u8], rng: &mut RNG) -> crate::Result<String>
+where
+  RNG: Rng,
+{
+  let salt = SaltString::encode_b64(&rng.u8_16())?;
+  Ok(argon2().hash_password(pwd, &salt)?.to_string())
+}
+
+/// If `phc` was generated by another algorithm or with parameters that differ from the current
+/// ones.
+#[inline]
+pub fn needs_rehash(phc: &str) -> crate::Result<bool> {
+  let parsed = PasswordHash::new(phc)?;
+  if parsed.algorithm != Algorithm::Argon2id.ident()
+    || parsed.version != Some(Version::V0x13.into())
+  {
+    return Ok(true);
+  }
+  let params = Params::try_from(&parsed)?;
+  let default = Params::default();
+  Ok(
+    params.m_cost() != default.m_cost()
+      || params.t_cost() != default.t_cost()
+      || params.p_cost() != default.p_cost(),
+  )
+}
+
+/// Verifies `pwd` and, if successful, returns a new hash when [`needs_rehash`] is `true`. The
+/// new hash should then replace `phc` in the underlying storage.
+///
+/// Returns `Err` if `pwd` doesn't match `phc`.
+#[inline]
+pub fn rehash_if_needed<RNG>(pwd: &[u8], phc: &str, rng: &mut RNG) -> crate::Result<Option<String>>
+where
+  RNG: Rng,
+{
+  if !verify(pwd, phc)? {
+    return Err(password_hash::Error::Password.into());
+  }
+  if !needs_rehash(phc)? {
+    return Ok(None);
+  }
+  Ok(Some(hash(pwd, rng)?))
+}
+
+/// If `pwd` matches `phc`. Parameters are extracted from `phc`, which means that outdated hashes
+/// can still be verified.
+#[inline]
+pub fn verify(pwd: &[u8], phc: &str) -> crate::Result<bool> {
+  match argon2().verify_password(pwd, &PasswordHash::new(phc)?) {
+    Ok(()) => Ok(true),
+    Err(password_hash::Error::Password) => Ok(false),
+    Err(err) => Err(err.into()),
+  }
+}
+
+#[inline]
+fn argon2() -> Argon2<'static> {
+  Argon2::new(Algorithm::Argon2id, Version::V0x13, Params::default())
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::misc::{
+    password::{hash, needs_rehash, rehash_if_needed, verify},
+    Xorshift64,
+  };
+
+  const OUTDATED: &str =
+    "$argon2id$v=19$m=4096,t=1,p=1$c2FsdHNhbHRzYWx0$UE1zHugfLs+haFJQuZ8eJrBEDMkZJs5SfRFx8ms6Ijs";
+
+  #[test]
+  fn hash_and_verify() {
+    let mut rng = Xorshift64::from(123);
+    let phc = hash(b"pwd", &mut rng).unwrap();
+    assert!(phc.starts_with("$argon2id$v=19$m=19456,t=2,p=1$"));
+    assert!(verify(b"pwd", &phc).unwrap());
+    assert!(!verify(b"other", &phc).unwrap());
+    assert!(!needs_rehash(&phc).unwrap());
+    assert_eq!(rehash_if_needed(b"pwd", &phc, &mut rng).unwrap(), None);
+    assert!(rehash_if_needed(b"other", &phc, &mut rng).is_err());
+  }
+
+  #[test]
+  fn rehash() {
+    let mut rng = Xorshift64::from(123);
+    assert!(needs_rehash(OUTDATED).unwrap());
+    let new = rehash_if_needed(b"pwd", OUTDATED, &mut rng).unwrap().unwrap();
+    assert!(!needs_rehash(&new).unwrap());
+    assert!(verify(b"pwd", &new).unwrap());
+  }
+}
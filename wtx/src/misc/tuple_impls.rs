@@ -46,13 +46,17 @@ macro_rules! impl_0_16 {
     mod http_server_framework {
       use crate::{
         http::{
-          OperationMode, HttpError, StatusCode, AutoStream, ManualStream, Request,
+          OperationMode, HttpError, Method, StatusCode, AutoStream, ManualStream, Request,
           ReqResBuffer, Response,
-          server_framework::{ConnAux, Endpoint, Middleware, StreamAux, RouteMatch, EndpointNode, PathParams}
+          server_framework::{
+            ConnAux, Endpoint, ManualStreamSender, MethodEndpoint, Methods, Middleware, StreamAux,
+            RouteMatch, EndpointNode, PathParams,
+            methods::dispatch::{_answer_unknown_method, _methods_om}
+          }
         },
         misc::{ArrayVector, Vector}
       };
-      use core::ops::ControlFlow;
+      use core::{net::IpAddr, ops::ControlFlow};
 
       $(
         impl<$($T,)*> ConnAux for ($($T,)*)
@@ -96,16 +100,33 @@ macro_rules! impl_0_16 {
             &self,
             _conn_aux: &mut CA,
             _mw_aux: &mut Self::Aux,
+            _peer: IpAddr,
             _req: &mut Request<ReqResBuffer>,
             _stream_aux: &mut SA,
           ) -> Result<ControlFlow<StatusCode, ()>, ERR> {
-            $({
-              let rslt = self.$N.req(_conn_aux, &mut _mw_aux.$N, _req, _stream_aux).await?;
-              if let ControlFlow::Break(status_code) = rslt {
-                return Ok(ControlFlow::Break(status_code));
+            let mut _halted: Option<(usize, StatusCode)> = None;
+            $(
+              if _halted.is_none() {
+                let rslt = self.$N.req(_conn_aux, &mut _mw_aux.$N, _peer, _req, _stream_aux).await?;
+                if let ControlFlow::Break(status_code) = rslt {
+                  _halted = Some(($N, status_code));
+                }
               }
-            })*
-            Ok(ControlFlow::Continue(()))
+            )*
+            let Some((_idx, _status_code)) = _halted else {
+              return Ok(ControlFlow::Continue(()));
+            };
+            $(
+              if $N < _idx {
+                let local_res =
+                  Response { rrd: &mut _req.rrd, status_code: _status_code, version: _req.version };
+                let rslt = self.$N.res(_conn_aux, &mut _mw_aux.$N, local_res, _stream_aux).await?;
+                if let ControlFlow::Break(status_code) = rslt {
+                  return Ok(ControlFlow::Break(status_code));
+                }
+              }
+            )*
+            Ok(ControlFlow::Break(_status_code))
           }
 
           #[inline]
@@ -129,6 +150,17 @@ macro_rules! impl_0_16 {
             })*
             Ok(ControlFlow::Continue(()))
           }
+
+          #[cfg(feature = "tracing")]
+          #[inline]
+          fn span(&self, _mw_aux: &Self::Aux) -> Option<tracing::Span> {
+            $(
+              if let Some(elem) = self.$N.span(&_mw_aux.$N) {
+                return Some(elem);
+              }
+            )*
+            None
+          }
         }
 
         impl<$($T,)* CA, ERR, S, SA> Endpoint<CA, ERR, S, SA> for ($(PathParams<$T>,)*)
@@ -207,6 +239,58 @@ macro_rules! impl_0_16 {
           }
         }
 
+        impl<$($T,)* CA, ERR, S, SA> Endpoint<CA, ERR, S, SA> for Methods<($($T,)*)>
+        where
+          $($T: MethodEndpoint<CA, ERR, S, SA>,)*
+          ERR: From<crate::Error>,
+          S: ManualStreamSender,
+        {
+          const OM: OperationMode = _methods_om(&[$($T::OM,)*]);
+
+          #[inline]
+          async fn auto(
+            &self,
+            _auto_stream: &mut AutoStream<CA, SA>,
+            _path_defs: (u8, &[RouteMatch]),
+          ) -> Result<StatusCode, ERR> {
+            let methods = [$($T::METHOD,)*];
+            let method = _auto_stream.req.method;
+            let _is_head = method == Method::Head && methods.contains(&Method::Get);
+            let _local_method = if _is_head { Method::Get } else { method };
+            $(
+              if $T::METHOD == _local_method {
+                _auto_stream.req.method = _local_method;
+                let rslt = self.0.$N.auto(_auto_stream, _path_defs).await;
+                if _is_head {
+                  _auto_stream.req.method = Method::Head;
+                }
+                return rslt;
+              }
+            )*
+            Ok(_answer_unknown_method(true, method, &methods, &mut _auto_stream.req.rrd)?)
+          }
+
+          #[inline]
+          async fn manual(
+            &self,
+            mut _manual_stream: ManualStream<CA, S, SA>,
+            _path_defs: (u8, &[RouteMatch]),
+          ) -> Result<(), ERR> {
+            let method = _manual_stream.req.method;
+            $(
+              if $T::METHOD == method {
+                return self.0.$N.manual(_manual_stream, _path_defs).await;
+              }
+            )*
+            let methods = [$($T::METHOD,)*];
+            let rrd = &mut _manual_stream.req.rrd;
+            let status_code = _answer_unknown_method(false, method, &methods, rrd)?;
+            let version = _manual_stream.req.version;
+            _manual_stream.stream.send_manual_res(Response { rrd, status_code, version }).await?;
+            Ok(())
+          }
+        }
+
         impl<$($T,)* CA, ERR, S, SA> EndpointNode<CA, ERR, S, SA> for ($(PathParams<$T>,)*)
         where
           $($T: EndpointNode<CA, ERR, S, SA>,)*
@@ -235,6 +319,53 @@ macro_rules! impl_0_16 {
       )+
     }
 
+    #[cfg(all(feature = "http-server-framework", feature = "serde_json"))]
+    mod http_server_framework_openapi {
+      use crate::http::server_framework::{Methods, OpenApiNode, OpenApiOperation, PathParams};
+      use alloc::string::String;
+      use serde_json::{Map, Value};
+
+      $(
+        impl<$($T,)*> OpenApiNode for Methods<($($T,)*)>
+        where
+          $($T: OpenApiNode,)*
+        {
+          #[inline]
+          fn openapi(
+            &self,
+            _path: &mut String,
+            _op: Option<&OpenApiOperation>,
+            _paths: &mut Map<String, Value>,
+          ) -> crate::Result<()> {
+            $( self.0.$N.openapi(_path, _op, _paths)?; )*
+            Ok(())
+          }
+        }
+
+        impl<$($T,)*> OpenApiNode for ($(PathParams<$T>,)*)
+        where
+          $($T: OpenApiNode,)*
+        {
+          #[inline]
+          fn openapi(
+            &self,
+            _path: &mut String,
+            _op: Option<&OpenApiOperation>,
+            _paths: &mut Map<String, Value>,
+          ) -> crate::Result<()> {
+            $({
+              let len = _path.len();
+              _path.push_str(self.$N.full_path);
+              let rslt = self.$N.value.openapi(_path, None, _paths);
+              _path.truncate(len);
+              rslt?;
+            })*
+            Ok(())
+          }
+        }
+      )+
+    }
+
     mod lease {
       use crate::misc::{Lease, LeaseMut};
 
@@ -364,6 +364,7 @@ impl UriString {
     self.href_start = href_start;
     self.initial_len = initial_len;
     self.query_start = query_start;
+    self.process_port();
     Ok(())
   }
 
@@ -425,4 +426,15 @@ mod tests {
     assert_eq!(uri.query_and_fragment(), "");
     assert_eq!(uri.as_str(), "");
   }
+
+  #[test]
+  fn reset_updates_the_port() {
+    let mut uri = UriString::new("http://localhost:8080/a".into());
+    assert_eq!(uri.port(), Some(8080));
+    uri.reset(format_args!("http://localhost:9090/b")).unwrap();
+    assert_eq!(uri.port(), Some(9090));
+    assert_eq!(uri.hostname_with_implied_port(), ("localhost", 9090));
+    uri.reset(format_args!("https://localhost/c")).unwrap();
+    assert_eq!(uri.port(), Some(443));
+  }
 }
@@ -35,7 +35,22 @@ where
   ///
   /// If `0`, then `len` will be stored as `1`.
   #[inline]
-  pub fn new(mut len: usize, rm: RM) -> Self {
+  pub fn new(len: usize, rm: RM) -> Self {
+    Self::_with_shared_rm(len, Arc::new(rm))
+  }
+
+  /// Sometimes it is desirable to eagerly initialize all instances.
+  #[inline]
+  pub async fn init_all(&self, ca: &RM::CreateAux, ra: &RM::RecycleAux) -> Result<(), RM::Error> {
+    for _ in 0..self.locks.len() {
+      let _guard = self.get(ca, ra).await?;
+    }
+    Ok(())
+  }
+
+  /// Version of [`Self::new`] where the resource manager is shared with other pools.
+  #[inline]
+  pub(crate) fn _with_shared_rm(mut len: usize, rm: Arc<RM>) -> Self {
     len = len.max(1);
     Self {
       available_idxs: Arc::new(Mutex::new((0..len).collect())),
@@ -44,18 +59,16 @@ where
         rslt.extend((0..len).map(|_| RL::new(SimplePoolResource(None))));
         Arc::new(rslt)
       },
-      rm: Arc::new(rm),
+      rm,
       waker: Arc::new(Mutex::new(Vec::new())),
     }
   }
 
-  /// Sometimes it is desirable to eagerly initialize all instances.
+  /// If there are no other instances that share the same resources, i.e., if resources can't be
+  /// in use.
   #[inline]
-  pub async fn init_all(&self, ca: &RM::CreateAux, ra: &RM::RecycleAux) -> Result<(), RM::Error> {
-    for _ in 0..self.locks.len() {
-      let _guard = self.get(ca, ra).await?;
-    }
-    Ok(())
+  pub(crate) fn _is_unique(&mut self) -> bool {
+    Arc::get_mut(&mut self.locks).is_some()
   }
 
   #[inline]